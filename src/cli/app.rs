@@ -52,6 +52,33 @@ pub enum Commands {
         package: String,
     },
     ClearInject,
+    Freezer,
+    Prewarm,
+    /// Show what the daemon derived about this device (SoC, energy-model
+    /// efficient frequencies per cluster)
+    Doctor,
+
+    Thermal {
+        #[command(subcommand)]
+        action: ThermalAction,
+    },
+
+    Game {
+        #[command(subcommand)]
+        action: GameAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ThermalAction {
+    /// List every thermal zone with its type, live reading and roles
+    Zones,
+}
+
+#[derive(Subcommand)]
+pub enum GameAction {
+    /// Show the learned per-game parameters and the evidence behind them
+    Suggest { package: String },
 }
 
 #[derive(Clone, ValueEnum)]
@@ -109,6 +109,35 @@ pub async fn execute(cli: Cli) -> Result<()> {
             let resp = client.send("CLEAR_INJECT").await?;
             output::print_success(&format!("Inject cleared: {resp}"));
         }
+
+        Commands::Freezer => {
+            let resp = client.send("FREEZER").await?;
+            println!("{resp}");
+        }
+
+        Commands::Prewarm => {
+            let resp = client.send("PREWARM").await?;
+            println!("{resp}");
+        }
+
+        Commands::Doctor => {
+            let resp = client.send("DOCTOR").await?;
+            println!("{resp}");
+        }
+
+        Commands::Thermal {
+            action: ThermalAction::Zones,
+        } => {
+            let resp = client.send("THERMAL_ZONES").await?;
+            println!("Thermal zones:\n{resp}");
+        }
+
+        Commands::Game {
+            action: GameAction::Suggest { package },
+        } => {
+            let resp = client.send(&format!("GAME_SUGGEST {package}")).await?;
+            println!("{resp}");
+        }
     }
 
     Ok(())
@@ -0,0 +1,186 @@
+// Frame-rate-driven display refresh rate.
+//
+// A game that never renders faster than 60 fps gains nothing from a
+// 120 Hz panel, it only pays for it. While a game holds at or below a
+// lower supported rate for `hold`, the panel is asked for the lowest rate
+// that still covers the highest frame rate seen over that window.
+//
+// Stepping back up is the hard half: under vsync a game can't render
+// faster than the panel, so a game that wants more only shows up as one
+// pinned at the lowered rate. A pin steps one rate up as a probe. If the
+// game then fails to use the headroom, the probe counts as failed and
+// the next one from that rate waits exponentially longer. That keeps a
+// locked-60 title from bouncing between 60 and 90 Hz.
+
+use std::time::{Duration, Instant};
+
+/// A rate covers frame rates up to this much above it (meter jitter).
+const COVER_SLACK_FPS: f64 = 1.0;
+/// Frame rate within this much of the lowered rate counts as pinned.
+const PIN_SLACK_FPS: f64 = 2.0;
+/// How long a pin must last before probing the next rate up.
+const PIN_HOLD: Duration = Duration::from_secs(2);
+const PROBE_BACKOFF_INITIAL: Duration = Duration::from_secs(30);
+const PROBE_BACKOFF_MAX: Duration = Duration::from_secs(600);
+
+#[derive(Debug)]
+pub struct AdaptiveRefresh {
+    /// Ascending, deduplicated; the last entry is the panel's top rate.
+    rates: Vec<u32>,
+    hold: Duration,
+    current: u32,
+    low_since: Option<Instant>,
+    window_peak: f64,
+    pinned_since: Option<Instant>,
+    /// Rate a probe stepped up from, until the game proves it needed more.
+    probe_from: Option<u32>,
+    probe_backoff: Duration,
+    next_probe: Option<Instant>,
+}
+
+impl AdaptiveRefresh {
+    /// `None` when fewer than two supported rates sit at or above `min_hz`,
+    /// leaving nothing to choose between. Starts at the top rate.
+    pub fn new(supported: &[u32], min_hz: u32, hold: Duration) -> Option<Self> {
+        let top = supported.iter().copied().max()?;
+        let mut rates: Vec<u32> = supported
+            .iter()
+            .copied()
+            .filter(|&r| r >= min_hz || r == top)
+            .collect();
+        rates.sort_unstable();
+        rates.dedup();
+        if rates.len() < 2 {
+            return None;
+        }
+        Some(Self {
+            rates,
+            hold,
+            current: top,
+            low_since: None,
+            window_peak: 0.0,
+            pinned_since: None,
+            probe_from: None,
+            probe_backoff: PROBE_BACKOFF_INITIAL,
+            next_probe: None,
+        })
+    }
+
+    pub fn top(&self) -> u32 {
+        self.rates[self.rates.len() - 1]
+    }
+
+    /// Lowest rate that covers `fps`.
+    fn cover(&self, fps: f64) -> u32 {
+        self.rates
+            .iter()
+            .copied()
+            .find(|&r| f64::from(r) + COVER_SLACK_FPS >= fps)
+            .unwrap_or(self.top())
+    }
+
+    fn step(&mut self, to: u32) -> Option<u32> {
+        self.current = to;
+        self.low_since = None;
+        self.window_peak = 0.0;
+        self.pinned_since = None;
+        Some(to)
+    }
+
+    /// Feed one measured frame rate; returns the rate to request when it
+    /// should change.
+    pub fn update(&mut self, fps: f64, now: Instant) -> Option<u32> {
+        if let Some(from) = self.probe_from
+            && fps > f64::from(from) + COVER_SLACK_FPS
+        {
+            // The game used the probe's headroom: it really wanted more.
+            self.probe_from = None;
+            self.probe_backoff = PROBE_BACKOFF_INITIAL;
+            self.next_probe = None;
+        }
+
+        if self.current < self.top() && fps >= f64::from(self.current) - PIN_SLACK_FPS {
+            let since = *self.pinned_since.get_or_insert(now);
+            if now.duration_since(since) >= PIN_HOLD && self.next_probe.is_none_or(|t| now >= t) {
+                let up = self
+                    .rates
+                    .iter()
+                    .copied()
+                    .find(|&r| r > self.current)
+                    .unwrap_or(self.top());
+                self.probe_from = Some(self.current);
+                return self.step(up);
+            }
+        } else {
+            self.pinned_since = None;
+        }
+
+        if self.cover(fps) >= self.current {
+            self.low_since = None;
+            self.window_peak = 0.0;
+            return None;
+        }
+        self.window_peak = self.window_peak.max(fps);
+        let since = *self.low_since.get_or_insert(now);
+        if now.duration_since(since) < self.hold {
+            return None;
+        }
+        let down = self.cover(self.window_peak);
+        if down >= self.current {
+            return None;
+        }
+        if self.probe_from.take() == Some(down) {
+            self.next_probe = Some(now + self.probe_backoff);
+            self.probe_backoff = (self.probe_backoff * 2).min(PROBE_BACKOFF_MAX);
+        }
+        self.step(down)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOLD: Duration = Duration::from_secs(10);
+
+    /// Feed `fps` every 500 ms for `secs`, collecting requested rates.
+    fn run(ar: &mut AdaptiveRefresh, t: &mut Instant, fps: f64, secs: u64) -> Vec<u32> {
+        let mut out = Vec::new();
+        for _ in 0..secs * 2 {
+            *t += Duration::from_millis(500);
+            out.extend(ar.update(fps, *t));
+        }
+        out
+    }
+
+    #[test]
+    fn filters_rates_below_min() {
+        let ar = AdaptiveRefresh::new(&[30, 60, 60, 120], 60, HOLD).unwrap();
+        assert_eq!(ar.rates, vec![60, 120]);
+        assert!(AdaptiveRefresh::new(&[120], 60, HOLD).is_none());
+        assert!(AdaptiveRefresh::new(&[30, 120], 60, HOLD).is_none());
+    }
+
+    #[test]
+    fn locked_sixty_steps_down_after_hold_and_settles() {
+        let mut t = Instant::now();
+        let mut ar = AdaptiveRefresh::new(&[60, 90, 120], 60, HOLD).unwrap();
+        assert_eq!(run(&mut ar, &mut t, 60.0, 9), Vec::<u32>::new());
+        assert_eq!(run(&mut ar, &mut t, 60.0, 2), vec![60]);
+        // Pinned at 60 Hz: one probe to 90, which the game doesn't use.
+        assert_eq!(run(&mut ar, &mut t, 59.8, 3), vec![90]);
+        assert_eq!(run(&mut ar, &mut t, 60.0, 11), vec![60]);
+        // Backed off: no new probe for the next 30 s.
+        assert_eq!(run(&mut ar, &mut t, 59.8, 25), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn rising_frame_rate_climbs_back_up() {
+        let mut t = Instant::now();
+        let mut ar = AdaptiveRefresh::new(&[60, 90, 120], 60, HOLD).unwrap();
+        assert_eq!(run(&mut ar, &mut t, 58.0, 11), vec![60]);
+        assert_eq!(run(&mut ar, &mut t, 60.0, 3), vec![90]);
+        assert_eq!(run(&mut ar, &mut t, 90.0, 3), vec![120]);
+        assert_eq!(run(&mut ar, &mut t, 110.0, 20), Vec::<u32>::new());
+    }
+}
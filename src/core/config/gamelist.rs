@@ -99,6 +99,29 @@ pub struct GameProfile {
     pub mode: Option<String>,
     #[serde(default)]
     pub ceiling: Option<String>,
+    /// Per-game override of `[touch_boost] enabled`.
+    #[serde(default)]
+    pub touch_boost: Option<bool>,
+    /// Per-game override of `[ingame_idle] enabled`.
+    #[serde(default)]
+    pub idle_detect: Option<bool>,
+    /// FPS headroom for FAS, replacing the active mode's `margin`.
+    #[serde(default)]
+    pub margin: Option<f64>,
+    /// Display size as `WIDTHxHEIGHT`, snapped to a supported mode.
+    #[serde(default)]
+    pub resolution: Option<String>,
+    /// Per-game override of `[memory] oom_protect`.
+    #[serde(default)]
+    pub oom_protect: Option<bool>,
+    /// `cpu.uclamp.min` (percent) for the game: its own boosted group
+    /// when `[cgroups] game_group` is set, top-app otherwise.
+    #[serde(default)]
+    pub uclamp_min: Option<f32>,
+    /// GPU devfreq governor for the session; the original one is put
+    /// back afterwards.
+    #[serde(default)]
+    pub gpu_governor: Option<String>,
 }
 
 impl GameList {
@@ -174,6 +197,27 @@ impl GameList {
             if upd.ceiling.is_some() {
                 profile.ceiling = upd.ceiling;
             }
+            if upd.touch_boost.is_some() {
+                profile.touch_boost = upd.touch_boost;
+            }
+            if upd.idle_detect.is_some() {
+                profile.idle_detect = upd.idle_detect;
+            }
+            if upd.margin.is_some() {
+                profile.margin = upd.margin;
+            }
+            if upd.resolution.is_some() {
+                profile.resolution = upd.resolution;
+            }
+            if upd.oom_protect.is_some() {
+                profile.oom_protect = upd.oom_protect;
+            }
+            if upd.uclamp_min.is_some() {
+                profile.uclamp_min = upd.uclamp_min;
+            }
+            if upd.gpu_governor.is_some() {
+                profile.gpu_governor = upd.gpu_governor;
+            }
             Ok(())
         } else {
             anyhow::bail!("Game {} not found", package)
@@ -190,4 +234,11 @@ pub struct GameProfileUpdate {
     pub mode: Option<String>,
     pub fps_array: Option<Vec<u32>>,
     pub ceiling: Option<String>,
+    pub touch_boost: Option<bool>,
+    pub idle_detect: Option<bool>,
+    pub margin: Option<f64>,
+    pub resolution: Option<String>,
+    pub oom_protect: Option<bool>,
+    pub uclamp_min: Option<f32>,
+    pub gpu_governor: Option<String>,
 }
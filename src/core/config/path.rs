@@ -3,6 +3,7 @@ pub use super::settings::*;
 use std::path::PathBuf;
 
 pub const CONFIG_DIR: &str = "/data/adb/.config/auriya";
+pub const STATE_DIR: &str = "/data/adb/auriya";
 
 pub fn settings_path() -> PathBuf {
     PathBuf::from(CONFIG_DIR).join("settings.toml")
@@ -12,6 +13,27 @@ pub fn gamelist_path() -> PathBuf {
     PathBuf::from(CONFIG_DIR).join("gamelist.toml")
 }
 
+/// Daemon-owned state (learned parameters) kept apart from user config.
+pub fn learned_path() -> PathBuf {
+    PathBuf::from(STATE_DIR).join("learned.json")
+}
+
+/// Efficient CPU frequencies derived from the energy model.
+pub fn energy_model_path() -> PathBuf {
+    PathBuf::from(STATE_DIR).join("energy_model.json")
+}
+
+/// Background apps frozen by the running session, for thawing after a
+/// crash.
+pub fn frozen_apps_path() -> PathBuf {
+    PathBuf::from(STATE_DIR).join("frozen.json")
+}
+
+/// Per-game launch times.
+pub fn launch_path() -> PathBuf {
+    PathBuf::from(STATE_DIR).join("launch.json")
+}
+
 pub fn load_all() -> anyhow::Result<(crate::core::config::Settings, crate::core::config::GameList)>
 {
     let settings = crate::core::config::Settings::load(settings_path())?;
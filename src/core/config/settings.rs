@@ -14,6 +14,34 @@ pub struct Settings {
     pub dynamic_governor: DynamicGovernorConfig,
     #[serde(default)]
     pub ceiling: CeilingConfig,
+    #[serde(default)]
+    pub thermal: ThermalConfig,
+    #[serde(default)]
+    pub touch_boost: TouchBoostConfig,
+    #[serde(default)]
+    pub ingame_idle: InGameIdleConfig,
+    #[serde(default)]
+    pub learning: LearningConfig,
+    #[serde(default)]
+    pub adaptive_refresh: AdaptiveRefreshConfig,
+    #[serde(default)]
+    pub launch: LaunchConfig,
+    #[serde(default)]
+    pub memory: MemoryConfig,
+    #[serde(default)]
+    pub freezer: FreezerConfig,
+    #[serde(default)]
+    pub io: IoConfig,
+    #[serde(default)]
+    pub prewarm: PrewarmConfig,
+    #[serde(default)]
+    pub psi: PsiConfig,
+    #[serde(default)]
+    pub swap: SwapConfig,
+    #[serde(default)]
+    pub cgroups: CgroupConfig,
+    #[serde(default)]
+    pub walt: WaltConfig,
     pub modes: HashMap<String, FasMode>,
 }
 
@@ -27,6 +55,12 @@ pub struct DaemonConfig {
 
     #[serde(default = "default_mode")]
     pub default_mode: String,
+
+    /// How long a game session survives while its process is alive but
+    /// another app has focus (shade, overlays, a quick chat). `0` ends
+    /// the session immediately.
+    #[serde(default = "default_session_grace")]
+    pub session_grace_ms: u64,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -82,6 +116,10 @@ pub struct CeilingConfig {
     pub default: String,
     pub low_freq_little_khz: Option<u64>,
     pub low_freq_big_khz: Option<u64>,
+    /// Named levels (`[ceiling.levels.<name>]`), usable as `default` or a
+    /// game's `ceiling`. Built-in names win over a level of the same name.
+    #[serde(default)]
+    pub levels: HashMap<String, CustomCeiling>,
 }
 
 impl Default for CeilingConfig {
@@ -90,10 +128,572 @@ impl Default for CeilingConfig {
             default: default_ceiling_level(),
             low_freq_little_khz: None,
             low_freq_big_khz: None,
+            levels: HashMap::new(),
+        }
+    }
+}
+
+/// A user-defined ceiling level.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct CustomCeiling {
+    #[serde(default)]
+    pub little: ClusterLimit,
+    #[serde(default)]
+    pub big: ClusterLimit,
+    #[serde(default)]
+    pub prime: ClusterLimit,
+    /// CPUs taken offline while the level is active.
+    #[serde(default)]
+    pub offline: Vec<usize>,
+    /// GPU frequency cap, in the GPU's own table units or a percentage.
+    #[serde(default)]
+    pub gpu_max: Option<FreqValue>,
+}
+
+/// `scaling_max_freq`/`scaling_min_freq` for every core of a cluster.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct ClusterLimit {
+    #[serde(default)]
+    pub max: Option<FreqValue>,
+    #[serde(default)]
+    pub min: Option<FreqValue>,
+}
+
+/// An absolute frequency (`1200000`) or a share of the hardware maximum
+/// (`"60%"`).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum FreqValue {
+    Absolute(u64),
+    Spec(String),
+}
+
+impl FreqValue {
+    /// The frequency this stands for on hardware topping out at `max`.
+    pub fn resolve(&self, max: u64) -> Option<u64> {
+        match self {
+            Self::Absolute(v) => Some(*v),
+            Self::Spec(s) => {
+                let s = s.trim();
+                match s.strip_suffix('%') {
+                    Some(pct) => {
+                        let pct = pct.trim().parse::<f64>().ok()?.clamp(0.0, 100.0);
+                        Some((max as f64 * pct / 100.0) as u64)
+                    }
+                    None => s.parse().ok(),
+                }
+            }
+        }
+    }
+}
+
+/// `[thermal]` — which thermal zones feed which decision. Entries are zone
+/// `type` names or globs; `"virtual_skin"` expands to the weighted
+/// composite in `[thermal.virtual_skin]`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ThermalConfig {
+    #[serde(default = "default_thermal_cpu")]
+    pub cpu: Vec<String>,
+    #[serde(default = "default_thermal_gpu")]
+    pub gpu: Vec<String>,
+    /// When nothing on the device matches, FAS reads the hottest zone that
+    /// isn't a PMIC or battery sensor.
+    #[serde(default = "default_thermal_fas")]
+    pub fas: Vec<String>,
+    #[serde(default = "default_thermal_fas")]
+    pub ceiling: Vec<String>,
+    #[serde(default = "default_thermal_safety")]
+    pub safety: Vec<String>,
+    #[serde(default)]
+    pub virtual_skin: HashMap<String, f64>,
+    #[serde(default = "default_safety_threshold")]
+    pub safety_threshold: f64,
+    pub ceiling_threshold: Option<f64>,
+}
+
+impl Default for ThermalConfig {
+    fn default() -> Self {
+        Self {
+            cpu: default_thermal_cpu(),
+            gpu: default_thermal_gpu(),
+            fas: default_thermal_fas(),
+            ceiling: default_thermal_fas(),
+            safety: default_thermal_safety(),
+            virtual_skin: HashMap::new(),
+            safety_threshold: default_safety_threshold(),
+            ceiling_threshold: None,
+        }
+    }
+}
+
+/// `[touch_boost]` — raise the little/big frequency floor for a short
+/// window after each touch while a game is in the foreground. Unset
+/// frequencies use the cluster's median available frequency.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TouchBoostConfig {
+    #[serde(default = "default_touch_boost_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_touch_boost_duration")]
+    pub duration_ms: u64,
+    pub little_freq_khz: Option<u64>,
+    pub big_freq_khz: Option<u64>,
+}
+
+impl Default for TouchBoostConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_touch_boost_enabled(),
+            duration_ms: default_touch_boost_duration(),
+            little_freq_khz: None,
+            big_freq_khz: None,
+        }
+    }
+}
+
+/// `[ingame_idle]` — drop to `idle_mode` while the focused game sits in a
+/// lobby/pause/AFK screen, and hold off FAS boosts while a level loads.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InGameIdleConfig {
+    #[serde(default = "default_ingame_idle_enabled")]
+    pub enabled: bool,
+    /// Seconds without touch input before idle is considered.
+    #[serde(default = "default_idle_after")]
+    pub idle_after_s: u64,
+    /// A steady frame rate at or below this counts as an idle screen.
+    #[serde(default = "default_idle_max_fps")]
+    pub idle_max_fps: f64,
+    /// Profile applied while idle: `balance` or `powersave`.
+    #[serde(default = "default_idle_mode")]
+    pub idle_mode: String,
+    #[serde(default = "default_loading_read_kbps")]
+    pub loading_read_kbps: f64,
+    #[serde(default = "default_loading_iowait_pct")]
+    pub loading_iowait_pct: f64,
+    #[serde(default = "default_loading_io_psi_pct")]
+    pub loading_io_psi_pct: f64,
+}
+
+impl Default for InGameIdleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_ingame_idle_enabled(),
+            idle_after_s: default_idle_after(),
+            idle_max_fps: default_idle_max_fps(),
+            idle_mode: default_idle_mode(),
+            loading_read_kbps: default_loading_read_kbps(),
+            loading_iowait_pct: default_loading_iowait_pct(),
+            loading_io_psi_pct: default_loading_io_psi_pct(),
+        }
+    }
+}
+
+/// `[learning]` — observe a game's first `sessions` sessions and propose
+/// `target_fps`, `margin`, `mode` and `ceiling` from what it did. With
+/// `auto_apply` (off by default; `GAME_SUGGEST` shows the proposal
+/// either way), proposals fill in whichever of those the game's gamelist
+/// entry leaves unset.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LearningConfig {
+    #[serde(default = "default_learning_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_learning_sessions")]
+    pub sessions: u32,
+    #[serde(default = "default_learning_auto_apply")]
+    pub auto_apply: bool,
+    /// Frame-rate samples required before anything is proposed.
+    #[serde(default = "default_learning_min_samples")]
+    pub min_samples: u64,
+}
+
+impl Default for LearningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_learning_enabled(),
+            sessions: default_learning_sessions(),
+            auto_apply: default_learning_auto_apply(),
+            min_samples: default_learning_min_samples(),
+        }
+    }
+}
+
+/// `[adaptive_refresh]` — follow the game's frame rate with the panel's
+/// refresh rate: drop to the lowest supported rate that covers it after
+/// `hold_s` seconds, step back up when it rises. Games with a fixed
+/// `refresh_rate` in the gamelist are left alone.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AdaptiveRefreshConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_adaptive_hold")]
+    pub hold_s: u64,
+    /// Never go below this rate.
+    #[serde(default = "default_adaptive_min_hz")]
+    pub min_hz: u32,
+}
+
+impl Default for AdaptiveRefreshConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hold_s: default_adaptive_hold(),
+            min_hz: default_adaptive_min_hz(),
+        }
+    }
+}
+
+/// `[launch]` — boost a whitelisted game's cold start (CPU floors at max,
+/// storage locked high, top best-effort I/O priority) for at most
+/// `max_boost_ms`, and record how long the launch took.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LaunchConfig {
+    #[serde(default = "default_launch_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_launch_max_boost")]
+    pub max_boost_ms: u64,
+}
+
+impl Default for LaunchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_launch_enabled(),
+            max_boost_ms: default_launch_max_boost(),
+        }
+    }
+}
+
+/// `[memory]` — while a game session runs (grace window included), lower
+/// the game's `oom_score_adj` so LMKD picks other apps first. With
+/// `memcg_protect`, also raise `memory.low` on its cgroup v2 memory
+/// cgroup. Original values come back when the session ends.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MemoryConfig {
+    #[serde(default = "default_oom_protect")]
+    pub oom_protect: bool,
+    #[serde(default = "default_oom_score_adj")]
+    pub oom_score_adj: i32,
+    #[serde(default)]
+    pub memcg_protect: bool,
+    /// At game session start, compact memory when memory PSI `some
+    /// avg10` is at or above this, and also trim slab caches when
+    /// `MemAvailable` is under `prep_min_available_mb`.
+    #[serde(default = "default_prep_psi_threshold")]
+    pub prep_psi_threshold: f32,
+    #[serde(default = "default_prep_min_available")]
+    pub prep_min_available_mb: u64,
+    /// During a session, turn on memcg protection for the game (as if
+    /// `memcg_protect` were set) once memory PSI `some` reaches this.
+    #[serde(default = "default_memcg_escalate_psi")]
+    pub memcg_escalate_psi: f32,
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self {
+            oom_protect: default_oom_protect(),
+            oom_score_adj: default_oom_score_adj(),
+            memcg_protect: false,
+            prep_psi_threshold: default_prep_psi_threshold(),
+            prep_min_available_mb: default_prep_min_available(),
+            memcg_escalate_psi: default_memcg_escalate_psi(),
+        }
+    }
+}
+
+/// `[freezer]` — while a game has focus, freeze app processes ranked at
+/// or above `min_adj` (previous app, background services, cached apps).
+/// Packages in `allowlist` keep running; everything is thawed when the
+/// session ends, focus leaves the game, or the daemon stops.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FreezerConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_freezer_min_adj")]
+    pub min_adj: i32,
+    #[serde(default = "default_freezer_allowlist")]
+    pub allowlist: Vec<String>,
+}
+
+impl Default for FreezerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_adj: default_freezer_min_adj(),
+            allowlist: default_freezer_allowlist(),
+        }
+    }
+}
+
+/// `[io]` — I/O QoS for game sessions: top best-effort I/O priority for
+/// the game, the idle class for `background_hogs` (packages, or binary
+/// names for native processes), F2FS background GC deferred, and the
+/// block scheduler from `scheduler` for the applied profile. A scheduler
+/// entry is a comma-separated preference list; profiles without one keep
+/// the device's scheduler.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IoConfig {
+    #[serde(default = "default_io_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_io_background_hogs")]
+    pub background_hogs: Vec<String>,
+    #[serde(default = "default_io_pause_f2fs_gc")]
+    pub pause_f2fs_gc: bool,
+    #[serde(default = "default_io_scheduler")]
+    pub scheduler: HashMap<String, String>,
+}
+
+impl Default for IoConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_io_enabled(),
+            background_hogs: default_io_background_hogs(),
+            pause_f2fs_gc: default_io_pause_f2fs_gc(),
+            scheduler: default_io_scheduler(),
+        }
+    }
+}
+
+impl IoConfig {
+    /// Scheduler preference list for `mode`, if one is configured.
+    pub fn scheduler_for(&self, mode: crate::core::profile::ProfileMode) -> Option<&str> {
+        self.scheduler
+            .get(&mode.to_string().to_lowercase())
+            .map(String::as_str)
+            .filter(|s| !s.trim().is_empty())
+    }
+}
+
+/// `[prewarm]` — read a whitelisted game's APKs, OBBs and data dirs into
+/// the page cache when it cold-starts and, with `idle`, ahead of time
+/// while the device charges with the screen off (at most once per
+/// `idle_interval_h`). At most `budget_mb` not already cached is read per
+/// run; nothing is read while memory PSI `some avg10` is above
+/// `max_memory_psi`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PrewarmConfig {
+    #[serde(default = "default_prewarm_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_prewarm_budget_mb")]
+    pub budget_mb: u64,
+    #[serde(default = "default_prewarm_idle")]
+    pub idle: bool,
+    #[serde(default = "default_prewarm_idle_interval")]
+    pub idle_interval_h: u64,
+    #[serde(default = "default_prewarm_max_psi")]
+    pub max_memory_psi: f32,
+}
+
+impl Default for PrewarmConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_prewarm_enabled(),
+            budget_mb: default_prewarm_budget_mb(),
+            idle: default_prewarm_idle(),
+            idle_interval_h: default_prewarm_idle_interval(),
+            max_memory_psi: default_prewarm_max_psi(),
+        }
+    }
+}
+
+/// `[psi]` — during a game session, register kernel PSI triggers so a
+/// memory or I/O stall is handled as soon as it happens rather than on
+/// the next tick. A trigger fires once `*_stall_ms` of stall accumulate
+/// within `window_ms` (the kernel accepts 500–10000 ms windows).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PsiConfig {
+    #[serde(default = "default_psi_triggers")]
+    pub triggers: bool,
+    #[serde(default = "default_psi_memory_stall")]
+    pub memory_stall_ms: u64,
+    #[serde(default = "default_psi_io_stall")]
+    pub io_stall_ms: u64,
+    #[serde(default = "default_psi_window")]
+    pub window_ms: u64,
+}
+
+impl Default for PsiConfig {
+    fn default() -> Self {
+        Self {
+            triggers: default_psi_triggers(),
+            memory_stall_ms: default_psi_memory_stall(),
+            io_stall_ms: default_psi_io_stall(),
+            window_ms: default_psi_window(),
+        }
+    }
+}
+
+/// `[swap]` — swappiness and `page-cluster` per profile and, with
+/// `manage_zram`, the zram0 layout. `swappiness` and `page_cluster` map
+/// profile names to values; a profile without a `swappiness` entry gets a
+/// RAM-based default, and one without a `page_cluster` entry keeps the
+/// device's value.
+///
+/// The layout (`comp_algorithm` as a comma-separated preference list,
+/// `disksize_mb`, `backing_dev` for writeback, `recomp_algorithm`) is
+/// applied once at startup, which resets zram0; empty or zero values keep
+/// the device's own. `writeback_on_entry` and `recompress_on_entry` push
+/// idle pages out to the backing device or recompress them once per game
+/// session, after any launch boost. Everything is put back when the
+/// daemon stops.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SwapConfig {
+    #[serde(default)]
+    pub manage_zram: bool,
+    #[serde(default = "default_zram_comp_algorithm")]
+    pub comp_algorithm: String,
+    #[serde(default)]
+    pub disksize_mb: u64,
+    #[serde(default)]
+    pub backing_dev: String,
+    #[serde(default = "default_zram_recomp_algorithm")]
+    pub recomp_algorithm: String,
+    #[serde(default)]
+    pub writeback_on_entry: bool,
+    #[serde(default)]
+    pub recompress_on_entry: bool,
+    #[serde(default)]
+    pub swappiness: HashMap<String, u32>,
+    #[serde(default = "default_page_cluster")]
+    pub page_cluster: HashMap<String, u32>,
+}
+
+impl Default for SwapConfig {
+    fn default() -> Self {
+        Self {
+            manage_zram: false,
+            comp_algorithm: default_zram_comp_algorithm(),
+            disksize_mb: 0,
+            backing_dev: String::new(),
+            recomp_algorithm: default_zram_recomp_algorithm(),
+            writeback_on_entry: false,
+            recompress_on_entry: false,
+            swappiness: HashMap::new(),
+            page_cluster: default_page_cluster(),
+        }
+    }
+}
+
+impl SwapConfig {
+    /// Configured swappiness for `mode`, if any.
+    pub fn swappiness_for(&self, mode: crate::core::profile::ProfileMode) -> Option<u32> {
+        self.swappiness
+            .get(&mode.to_string().to_lowercase())
+            .copied()
+    }
+
+    /// Configured `page-cluster` for `mode`, if any.
+    pub fn page_cluster_for(&self, mode: crate::core::profile::ProfileMode) -> Option<u32> {
+        self.page_cluster
+            .get(&mode.to_string().to_lowercase())
+            .copied()
+    }
+}
+
+/// `[cgroups]` — utilization clamps and cpusets for Android's role
+/// groups, per profile (`[cgroups.profiles.<mode>]`). Clamps are percent;
+/// cpu lists are cpuset syntax (`0-3`) or `little`. Profiles without an
+/// entry leave the groups as the device set them. With `game_group` set,
+/// the game runs in a group of that name created next to top-app, with
+/// `uclamp.min` from its gamelist `uclamp_min` or `game_uclamp_min`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CgroupConfig {
+    #[serde(default = "default_cgroups_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub game_group: String,
+    #[serde(default = "default_game_uclamp_min")]
+    pub game_uclamp_min: f32,
+    #[serde(default = "default_cgroup_profiles")]
+    pub profiles: HashMap<String, GroupPolicy>,
+}
+
+impl Default for CgroupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_cgroups_enabled(),
+            game_group: String::new(),
+            game_uclamp_min: default_game_uclamp_min(),
+            profiles: default_cgroup_profiles(),
+        }
+    }
+}
+
+impl CgroupConfig {
+    /// Group policy for `mode`, if one is configured.
+    pub fn policy_for(&self, mode: crate::core::profile::ProfileMode) -> Option<&GroupPolicy> {
+        self.profiles.get(&mode.to_string().to_lowercase())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct GroupPolicy {
+    #[serde(default)]
+    pub top_app: Option<UclampRange>,
+    #[serde(default)]
+    pub foreground: Option<UclampRange>,
+    #[serde(default)]
+    pub background: Option<UclampRange>,
+    #[serde(default)]
+    pub foreground_cpus: Option<String>,
+    #[serde(default)]
+    pub background_cpus: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub struct UclampRange {
+    #[serde(default)]
+    pub min: Option<f32>,
+    #[serde(default)]
+    pub max: Option<f32>,
+}
+
+/// `[walt]` — Qualcomm WALT and core_ctl tunables per profile
+/// (`[walt.profiles.<mode>]`), on kernels that have them. `upmigrate`
+/// and `downmigrate` are percent of a core's capacity and go to every
+/// cluster boundary; `core_ctl_min_cpus`/`core_ctl_max_cpus` are a count
+/// or `all`, per cluster. Profiles without an entry keep the device's
+/// values.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WaltConfig {
+    #[serde(default = "default_walt_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_walt_profiles")]
+    pub profiles: HashMap<String, WaltPolicy>,
+}
+
+impl Default for WaltConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_walt_enabled(),
+            profiles: default_walt_profiles(),
         }
     }
 }
 
+impl WaltConfig {
+    /// WALT policy for `mode`, if one is configured.
+    pub fn policy_for(&self, mode: crate::core::profile::ProfileMode) -> Option<&WaltPolicy> {
+        self.profiles.get(&mode.to_string().to_lowercase())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct WaltPolicy {
+    #[serde(default)]
+    pub sched_boost: Option<u32>,
+    #[serde(default)]
+    pub upmigrate: Option<u32>,
+    #[serde(default)]
+    pub downmigrate: Option<u32>,
+    #[serde(default)]
+    pub core_ctl_min_cpus: Option<String>,
+    #[serde(default)]
+    pub core_ctl_max_cpus: Option<String>,
+    #[serde(default)]
+    pub busy_up_thres: Option<u32>,
+    #[serde(default)]
+    pub busy_down_thres: Option<u32>,
+}
+
 impl Settings {
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
@@ -113,6 +713,10 @@ fn default_check_interval() -> u64 {
     2000
 }
 
+fn default_session_grace() -> u64 {
+    10_000
+}
+
 fn default_poll_interval() -> u64 {
     100
 }
@@ -140,3 +744,257 @@ fn default_dg_debounce() -> u32 {
 fn default_ceiling_level() -> String {
     "balance".to_string()
 }
+
+fn default_thermal_cpu() -> Vec<String> {
+    vec!["cpu*".to_string()]
+}
+
+fn default_thermal_gpu() -> Vec<String> {
+    ["gpu*", "kgsl*", "mali*", "adreno*"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn default_thermal_fas() -> Vec<String> {
+    let mut v = default_thermal_cpu();
+    v.extend(default_thermal_gpu());
+    v
+}
+
+fn default_thermal_safety() -> Vec<String> {
+    vec!["*".to_string()]
+}
+
+fn default_safety_threshold() -> f64 {
+    100.0
+}
+
+fn default_touch_boost_enabled() -> bool {
+    true
+}
+
+fn default_touch_boost_duration() -> u64 {
+    100
+}
+
+fn default_ingame_idle_enabled() -> bool {
+    true
+}
+
+fn default_idle_after() -> u64 {
+    30
+}
+
+fn default_idle_max_fps() -> f64 {
+    35.0
+}
+
+fn default_idle_mode() -> String {
+    "balance".to_string()
+}
+
+fn default_loading_read_kbps() -> f64 {
+    8192.0
+}
+
+fn default_loading_iowait_pct() -> f64 {
+    15.0
+}
+
+fn default_loading_io_psi_pct() -> f64 {
+    20.0
+}
+
+fn default_learning_enabled() -> bool {
+    true
+}
+
+fn default_learning_sessions() -> u32 {
+    5
+}
+
+fn default_learning_auto_apply() -> bool {
+    false
+}
+
+fn default_learning_min_samples() -> u64 {
+    600
+}
+
+fn default_adaptive_hold() -> u64 {
+    10
+}
+
+fn default_adaptive_min_hz() -> u32 {
+    60
+}
+
+fn default_launch_enabled() -> bool {
+    true
+}
+
+fn default_launch_max_boost() -> u64 {
+    20_000
+}
+
+fn default_oom_protect() -> bool {
+    true
+}
+
+fn default_oom_score_adj() -> i32 {
+    -800
+}
+
+fn default_prep_psi_threshold() -> f32 {
+    10.0
+}
+
+fn default_prep_min_available() -> u64 {
+    1024
+}
+
+fn default_memcg_escalate_psi() -> f32 {
+    15.0
+}
+
+fn default_freezer_min_adj() -> i32 {
+    700
+}
+
+fn default_freezer_allowlist() -> Vec<String> {
+    [
+        "com.spotify.music",
+        "com.google.android.apps.youtube.music",
+        "com.discord",
+        "com.whatsapp",
+        "org.telegram.messenger",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_io_enabled() -> bool {
+    true
+}
+
+fn default_io_background_hogs() -> Vec<String> {
+    [
+        "com.android.vending",
+        "com.google.android.apps.photos",
+        "com.android.providers.media.module",
+        "com.google.android.providers.media.module",
+        "dex2oat",
+        "dex2oat64",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_io_pause_f2fs_gc() -> bool {
+    true
+}
+
+fn default_io_scheduler() -> HashMap<String, String> {
+    ["performance", "fast"]
+        .into_iter()
+        .map(|m| (m.to_string(), "bfq,mq-deadline".to_string()))
+        .collect()
+}
+
+fn default_prewarm_enabled() -> bool {
+    true
+}
+
+fn default_prewarm_budget_mb() -> u64 {
+    512
+}
+
+fn default_prewarm_idle() -> bool {
+    true
+}
+
+fn default_prewarm_idle_interval() -> u64 {
+    6
+}
+
+fn default_prewarm_max_psi() -> f32 {
+    10.0
+}
+
+fn default_psi_triggers() -> bool {
+    true
+}
+
+fn default_psi_memory_stall() -> u64 {
+    100
+}
+
+fn default_psi_io_stall() -> u64 {
+    150
+}
+
+fn default_psi_window() -> u64 {
+    1000
+}
+
+fn default_zram_comp_algorithm() -> String {
+    "lz4,lzo-rle".to_string()
+}
+
+fn default_zram_recomp_algorithm() -> String {
+    "zstd".to_string()
+}
+
+fn default_page_cluster() -> HashMap<String, u32> {
+    ["performance", "fast"]
+        .into_iter()
+        .map(|m| (m.to_string(), 0))
+        .collect()
+}
+
+fn default_cgroups_enabled() -> bool {
+    true
+}
+
+fn default_game_uclamp_min() -> f32 {
+    20.0
+}
+
+fn default_cgroup_profiles() -> HashMap<String, GroupPolicy> {
+    let gaming = GroupPolicy {
+        top_app: Some(UclampRange {
+            min: Some(10.0),
+            max: None,
+        }),
+        background: Some(UclampRange {
+            min: None,
+            max: Some(50.0),
+        }),
+        background_cpus: Some("little".to_string()),
+        ..GroupPolicy::default()
+    };
+    ["performance", "fast"]
+        .into_iter()
+        .map(|m| (m.to_string(), gaming.clone()))
+        .collect()
+}
+
+fn default_walt_enabled() -> bool {
+    true
+}
+
+fn default_walt_profiles() -> HashMap<String, WaltPolicy> {
+    let gaming = WaltPolicy {
+        upmigrate: Some(80),
+        downmigrate: Some(65),
+        core_ctl_min_cpus: Some("all".to_string()),
+        ..WaltPolicy::default()
+    };
+    ["performance", "fast"]
+        .into_iter()
+        .map(|m| (m.to_string(), gaming.clone()))
+        .collect()
+}
@@ -1,14 +1,16 @@
 // The daemon used to drive `min_refresh_rate` / `peak_refresh_rate`
 // directly via `settings put`, but those writes are now delegated to
 // the Android companion through `auriya_cmd` (see `core::cmd_writer`).
-// Only the dumpsys-based mode enumeration is left in this module — the
-// daemon still needs it to validate user-requested rates against
-// `appsSupportedModes` before forwarding them.
+// What is left here: the dumpsys-based mode enumeration — the daemon
+// still needs it to validate user-requested rates against
+// `appsSupportedModes` before forwarding them — the `settings put`
+// fallback for when the companion is down, and the `wm size` override
+// used for per-game resolutions.
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use tokio::process::Command;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DisplayMode {
     pub id: u32,
     pub width: u32,
@@ -16,6 +18,154 @@ pub struct DisplayMode {
     pub fps: f32,
 }
 
+impl DisplayMode {
+    pub fn rate(&self) -> u32 {
+        self.fps.round() as u32
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+/// Parse a `WIDTHxHEIGHT` resolution.
+pub fn parse_resolution(s: &str) -> Option<(u32, u32)> {
+    let (w, h) = s.trim().split_once(['x', 'X'])?;
+    Some((w.trim().parse().ok()?, h.trim().parse().ok()?))
+}
+
+/// The supported resolution closest to `want` by pixel count (exact match
+/// first). Portrait/landscape order is ignored.
+pub fn resolve_resolution(modes: &[DisplayMode], want: (u32, u32)) -> Option<(u32, u32)> {
+    let pixels = |(w, h): (u32, u32)| u64::from(w) * u64::from(h);
+    let same = |(w, h): (u32, u32)| (w, h) == want || (h, w) == want;
+    modes
+        .iter()
+        .map(DisplayMode::size)
+        .min_by_key(|&s| (!same(s), pixels(s).abs_diff(pixels(want))))
+}
+
+/// Refresh rates offered at `resolution` (every mode when `None`),
+/// ascending and deduplicated.
+pub fn rates_for(modes: &[DisplayMode], resolution: Option<(u32, u32)>) -> Vec<u32> {
+    let mut rates: Vec<u32> = modes
+        .iter()
+        .filter(|m| resolution.is_none_or(|r| m.size() == r || (m.height, m.width) == r))
+        .map(DisplayMode::rate)
+        .collect();
+    rates.sort_unstable();
+    rates.dedup();
+    rates
+}
+
+/// The supported rate nearest to `hz`; ties go to the higher rate so a
+/// game never gets less than it asked for.
+pub fn resolve_rate(rates: &[u32], hz: u32) -> Option<u32> {
+    rates
+        .iter()
+        .copied()
+        .min_by_key(|&r| (r.abs_diff(hz), std::cmp::Reverse(r)))
+}
+
+/// The user's `min_refresh_rate` / `peak_refresh_rate` system settings,
+/// captured before the first fallback write so they can be put back
+/// exactly. `None` means the key was unset.
+#[derive(Debug, Clone, Default)]
+pub struct RefreshSettings {
+    pub min: Option<String>,
+    pub peak: Option<String>,
+}
+
+impl RefreshSettings {
+    pub fn capture() -> Self {
+        Self {
+            min: settings_get("min_refresh_rate"),
+            peak: settings_get("peak_refresh_rate"),
+        }
+    }
+
+    /// Write the captured values back, deleting keys that were unset.
+    pub fn restore(&self) -> bool {
+        let min = settings_restore("min_refresh_rate", self.min.as_deref());
+        let peak = settings_restore("peak_refresh_rate", self.peak.as_deref());
+        min && peak
+    }
+}
+
+/// Pin both refresh-rate settings to `hz` (fallback path, no companion).
+pub fn force_refresh_rate(hz: u32) -> bool {
+    let hz = hz.to_string();
+    let min = settings(&["put", "system", "min_refresh_rate", &hz]);
+    let peak = settings(&["put", "system", "peak_refresh_rate", &hz]);
+    min && peak
+}
+
+fn settings(args: &[&str]) -> bool {
+    std::process::Command::new("settings")
+        .args(args)
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+fn settings_get(key: &str) -> Option<String> {
+    let out = std::process::Command::new("settings")
+        .args(["get", "system", key])
+        .output()
+        .ok()?;
+    let value = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    (out.status.success() && !value.is_empty() && value != "null").then_some(value)
+}
+
+fn settings_restore(key: &str, value: Option<&str>) -> bool {
+    match value {
+        Some(v) => settings(&["put", "system", key, v]),
+        None => settings(&["delete", "system", key]),
+    }
+}
+
+/// The user's own `wm size` override, captured before the first per-game
+/// resolution so releasing it puts theirs back instead of resetting to
+/// the panel's size. `None` means no override was set.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResolutionOverride(pub Option<(u32, u32)>);
+
+impl ResolutionOverride {
+    pub async fn capture() -> Self {
+        let out = match Command::new("wm").arg("size").output().await {
+            Ok(out) if out.status.success() => out,
+            _ => return Self::default(),
+        };
+        Self(parse_wm_size_override(&String::from_utf8_lossy(
+            &out.stdout,
+        )))
+    }
+
+    pub async fn restore(&self) -> bool {
+        set_resolution(self.0).await
+    }
+}
+
+/// `Override size: WxH` from `wm size` output.
+fn parse_wm_size_override(out: &str) -> Option<(u32, u32)> {
+    out.lines()
+        .find_map(|l| l.trim().strip_prefix("Override size:"))
+        .and_then(parse_resolution)
+}
+
+/// Override the logical display size with `wm size`, or drop the override
+/// with `None`. Returns `true` on success.
+pub async fn set_resolution(size: Option<(u32, u32)>) -> bool {
+    let arg = match size {
+        Some((w, h)) => format!("{w}x{h}"),
+        None => "reset".to_string(),
+    };
+    Command::new("wm")
+        .args(["size", &arg])
+        .status()
+        .await
+        .is_ok_and(|s| s.success())
+}
+
 pub async fn get_app_supported_modes() -> Result<Vec<DisplayMode>> {
     let output = Command::new("dumpsys").arg("display").output().await?;
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -95,3 +245,36 @@ fn parse_app_supported_modes(input: &str) -> Result<Vec<DisplayMode>> {
 
     Ok(modes)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_modes_and_resolves_requests() {
+        let dump = "appsSupportedModes [{id=1, width=1080, height=2400, fps=60.000004, \
+                    alternativeRefreshRates=[120.00001]}, {id=2, width=1080, height=2400, \
+                    fps=120.00001, alternativeRefreshRates=[60.000004]}, {id=3, width=720, \
+                    height=1600, fps=60.0, alternativeRefreshRates=[]}]";
+        let modes = parse_app_supported_modes(dump).unwrap();
+        assert_eq!(modes.len(), 3);
+        assert_eq!(rates_for(&modes, None), vec![60, 120]);
+        assert_eq!(rates_for(&modes, Some((1600, 720))), vec![60]);
+
+        let rates = rates_for(&modes, None);
+        assert_eq!(resolve_rate(&rates, 120), Some(120));
+        assert_eq!(resolve_rate(&rates, 144), Some(120));
+        assert_eq!(resolve_rate(&rates, 90), Some(120));
+        assert_eq!(resolve_rate(&rates, 45), Some(60));
+        assert_eq!(resolve_rate(&[], 60), None);
+
+        assert_eq!(parse_resolution("720x1600"), Some((720, 1600)));
+        assert_eq!(parse_resolution("720"), None);
+        assert_eq!(resolve_resolution(&modes, (1600, 720)), Some((720, 1600)));
+        assert_eq!(resolve_resolution(&modes, (1440, 3200)), Some((1080, 2400)));
+
+        let wm = "Physical size: 1080x2400\nOverride size: 900x2000\n";
+        assert_eq!(parse_wm_size_override(wm), Some((900, 2000)));
+        assert_eq!(parse_wm_size_override("Physical size: 1080x2400\n"), None);
+    }
+}
@@ -4,7 +4,8 @@
 // module just provides the cheap liveness check the tick loop uses to
 // detect whether the cached PID still corresponds to a running process.
 // Heavy lifting (scanning dumpsys for matching processes) is no longer
-// needed.
+// needed; `game_processes` walks `/proc` directly to find the rest of a
+// game's process set (child render processes, restarted main process).
 
 use memchr::memchr;
 use std::fs;
@@ -41,3 +42,95 @@ pub fn verify_pid_package(pid: i32, package: &str) -> bool {
     // those too via substring match.
     String::from_utf8_lossy(&cmdline[..]).contains(package)
 }
+
+/// First app UID. Below this are system UIDs shared by many unrelated
+/// processes, so UID matching is only trusted from here up.
+pub(crate) const FIRST_APPLICATION_UID: u32 = 10_000;
+
+/// Every live process belonging to `package`: the main process, its
+/// `<pkg>:<suffix>` children (separate render/unity processes), and —
+/// when `uid` is an app UID — anything else running under that UID.
+/// `primary` is always included while alive, even if its cmdline has
+/// not been set yet (freshly forked zygote children). Sorted ascending.
+pub fn game_processes(package: &str, uid: Option<i32>, primary: Option<i32>) -> Vec<i32> {
+    let uid = uid
+        .and_then(|u| u32::try_from(u).ok())
+        .filter(|&u| u >= FIRST_APPLICATION_UID);
+    let mut out: Vec<i32> = Vec::new();
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return primary.filter(|&p| is_pid_valid(p)).into_iter().collect();
+    };
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|n| n.parse::<i32>().ok())
+        else {
+            continue;
+        };
+        let by_name =
+            fs::read(format!("/proc/{pid}/cmdline")).is_ok_and(|c| cmdline_belongs_to(&c, package));
+        let by_uid = !by_name
+            && uid.is_some()
+            && fs::read_to_string(format!("/proc/{pid}/status"))
+                .ok()
+                .and_then(|s| parse_status_uid(&s))
+                == uid;
+        if by_name || by_uid || primary == Some(pid) {
+            out.push(pid);
+        }
+    }
+    out.sort_unstable();
+    out
+}
+
+/// `argv[0]` is exactly `package` or `package:<suffix>`.
+fn cmdline_belongs_to(cmdline: &[u8], package: &str) -> bool {
+    let end = memchr(b'\0', cmdline).unwrap_or(cmdline.len());
+    let argv0 = &cmdline[..end];
+    match argv0.strip_prefix(package.as_bytes()) {
+        Some(rest) => rest.is_empty() || rest.first() == Some(&b':'),
+        None => false,
+    }
+}
+
+/// Real UID from the `Uid:` line of `/proc/<pid>/status`.
+pub(crate) fn parse_status_uid(status: &str) -> Option<u32> {
+    status
+        .lines()
+        .find_map(|l| l.strip_prefix("Uid:"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|v| v.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cmdline_matches_main_and_child_processes() {
+        assert!(cmdline_belongs_to(b"com.game\0", "com.game"));
+        assert!(cmdline_belongs_to(b"com.game:render\0", "com.game"));
+        assert!(!cmdline_belongs_to(b"com.gamehub\0", "com.game"));
+        assert!(!cmdline_belongs_to(
+            b"/system/bin/sh\0com.game\0",
+            "com.game"
+        ));
+        assert!(!cmdline_belongs_to(b"", "com.game"));
+    }
+
+    #[test]
+    fn parses_real_uid_from_status() {
+        let status =
+            "Name:\tUnityMain\nTgid:\t4242\nUid:\t10234\t10234\t10234\t10234\nGid:\t10234\n";
+        assert_eq!(parse_status_uid(status), Some(10234));
+        assert_eq!(parse_status_uid("Name:\tkthreadd\n"), None);
+    }
+
+    #[test]
+    fn includes_own_process_as_primary() {
+        let me = std::process::id() as i32;
+        let pids = game_processes("auriya.test.nonexistent", None, Some(me));
+        assert_eq!(pids, vec![me]);
+    }
+}
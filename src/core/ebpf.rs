@@ -9,14 +9,21 @@
 // command channel, so the thread costs zero CPU while idle instead of
 // draining frames from whatever happens to be on screen.
 //
+// Every broadcast frame carries the PID that presented it, and the worker
+// remembers the most recent presenter so the daemon can attach to a whole
+// game process set and then narrow down to the one actually rendering.
+//
 // API:
 //   - new()         — load probe, spawn worker, fail if unavailable
 //   - attach(pid)   — start tracking a target process
 //   - detach(pid)   — stop tracking a target process
 //   - subscribe()   — get a new broadcast receiver
+//   - presenter()   — PID of the last process seen presenting a frame
 
 use anyhow::{Result, anyhow};
 use kala::FrameProbe;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI32, Ordering};
 use std::sync::mpsc as std_mpsc;
 use std::thread;
 use std::time::Duration;
@@ -27,9 +34,19 @@ enum Cmd {
     Detach(i32, std_mpsc::Sender<Result<bool>>),
 }
 
+/// One presented frame: the delta since the previous frame of `pid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameEvent {
+    pub pid: i32,
+    pub frametime: Duration,
+}
+
 pub struct EbpfFrameStream {
     cmd_tx: std_mpsc::Sender<Cmd>,
-    rx: broadcast::Receiver<Duration>,
+    rx: broadcast::Receiver<FrameEvent>,
+    /// PID of the most recent frame, 0 when none seen since the last
+    /// detach of that PID.
+    presenter: Arc<AtomicI32>,
 }
 
 impl EbpfFrameStream {
@@ -46,6 +63,8 @@ impl EbpfFrameStream {
 
         let (cmd_tx, cmd_rx) = std_mpsc::channel::<Cmd>();
         let (frame_tx, rx) = broadcast::channel(4096);
+        let presenter = Arc::new(AtomicI32::new(0));
+        let presenter_t = presenter.clone();
 
         thread::Builder::new()
             .name("auriya-ebpf".into())
@@ -57,7 +76,7 @@ impl EbpfFrameStream {
                     // app is currently on screen.
                     if probe.attached() == 0 {
                         match cmd_rx.recv() {
-                            Ok(cmd) => handle_cmd(&mut probe, cmd),
+                            Ok(cmd) => handle_cmd(&mut probe, &presenter_t, cmd),
                             Err(_) => return, // all senders dropped
                         }
                         continue;
@@ -67,28 +86,41 @@ impl EbpfFrameStream {
                     // then poll for the next frame.
                     loop {
                         match cmd_rx.try_recv() {
-                            Ok(cmd) => handle_cmd(&mut probe, cmd),
+                            Ok(cmd) => handle_cmd(&mut probe, &presenter_t, cmd),
                             Err(std_mpsc::TryRecvError::Empty) => break,
                             Err(std_mpsc::TryRecvError::Disconnected) => return,
                         }
                     }
 
-                    if let Some((_pid, frametime)) = probe.recv_with_deadline(poll) {
-                        let _ = frame_tx.send(frametime);
+                    if let Some((pid, frametime)) = probe.recv_with_deadline(poll) {
+                        presenter_t.store(pid, Ordering::Relaxed);
+                        let _ = frame_tx.send(FrameEvent { pid, frametime });
                     }
                 }
             })
             .map_err(|e| anyhow!("spawn ebpf worker: {e}"))?;
 
-        Ok(Self { cmd_tx, rx })
+        Ok(Self {
+            cmd_tx,
+            rx,
+            presenter,
+        })
     }
 
     /// Subscribe to the frame broadcast stream.
     /// Each subscriber receives every frame delta independently.
-    pub fn subscribe(&self) -> broadcast::Receiver<Duration> {
+    pub fn subscribe(&self) -> broadcast::Receiver<FrameEvent> {
         self.rx.resubscribe()
     }
 
+    /// PID of the last attached process that presented a frame.
+    pub fn presenter(&self) -> Option<i32> {
+        match self.presenter.load(Ordering::Relaxed) {
+            0 => None,
+            pid => Some(pid),
+        }
+    }
+
     /// Attach the BPF program to a target process.
     /// Blocking with a 1s timeout.
     pub fn attach(&self, pid: i32) -> Result<()> {
@@ -126,13 +158,14 @@ impl EbpfFrameStream {
     }
 }
 
-fn handle_cmd(probe: &mut FrameProbe, cmd: Cmd) {
+fn handle_cmd(probe: &mut FrameProbe, presenter: &AtomicI32, cmd: Cmd) {
     match cmd {
         Cmd::Attach(pid, reply) => {
             let res = probe.attach(pid).map_err(|e| anyhow!("attach({pid}): {e}"));
             let _ = reply.send(res);
         }
         Cmd::Detach(pid, reply) => {
+            let _ = presenter.compare_exchange(pid, 0, Ordering::Relaxed, Ordering::Relaxed);
             let res = probe.detach(pid).map_err(|e| anyhow!("detach({pid}): {e}"));
             let _ = reply.send(res);
         }
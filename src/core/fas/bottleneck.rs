@@ -1,5 +1,25 @@
+// Bottleneck classification.
+//
+// Primary path: real load signals (`LoadSignals` — GPU busy %, the game's
+// hottest render/main thread, busiest CPU cluster). Whichever side sits
+// clearly closer to saturation is the bottleneck; the gap sets the
+// confidence. When signals are missing or inconclusive, the frametime
+// coefficient-of-variation heuristic decides instead (steady slow frames
+// → GPU, spiky → CPU), reported with a capped confidence.
+
 use std::time::Duration;
 
+use crate::core::fas::signals::LoadSignals;
+
+/// Gap between GPU and CPU load needed to call a side the bottleneck.
+const SIGNAL_MARGIN: f32 = 0.15;
+/// Both sides above this with no clear gap → genuinely balanced.
+const SATURATED: f32 = 0.85;
+/// Signal verdicts below this confidence defer to the CV heuristic.
+const MIN_SIGNAL_CONFIDENCE: f32 = 0.3;
+/// CV is a guess; never report it as more certain than this.
+const CV_CONFIDENCE_CAP: f32 = 0.5;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BottleneckType {
     Gpu,
@@ -8,6 +28,48 @@ pub enum BottleneckType {
     Unknown,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClassSource {
+    Signals,
+    FrametimeCv,
+}
+
+/// A (debounced) bottleneck class plus how sure the raw verdict behind it
+/// was, in `0.0..=1.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Classification {
+    pub kind: BottleneckType,
+    pub confidence: f32,
+    pub source: ClassSource,
+}
+
+/// Classify from load signals alone. `None` when GPU load or every CPU
+/// input is missing, or when neither side is near saturation (the frame
+/// is slow for some other reason and the signals say nothing).
+///
+/// CPU pressure adds to the CPU side: time spent waiting for a core is
+/// demand that utilisation alone doesn't show, e.g. a render thread
+/// sharing its core with background work.
+pub fn classify_signals(s: &LoadSignals) -> Option<(BottleneckType, f32)> {
+    let gpu = s.gpu_busy?.clamp(0.0, 1.0);
+    let cpu = match (s.hot_thread, s.cluster_util) {
+        (None, None) => return None,
+        (a, b) => {
+            (a.unwrap_or(0.0).max(b.unwrap_or(0.0)) + s.cpu_pressure.unwrap_or(0.0)).clamp(0.0, 1.0)
+        }
+    };
+    let margin = gpu - cpu;
+    if margin >= SIGNAL_MARGIN {
+        Some((BottleneckType::Gpu, (margin / 0.5).min(1.0) * gpu))
+    } else if -margin >= SIGNAL_MARGIN {
+        Some((BottleneckType::Cpu, (-margin / 0.5).min(1.0) * cpu))
+    } else if gpu >= SATURATED && cpu >= SATURATED {
+        Some((BottleneckType::Balanced, gpu.min(cpu)))
+    } else {
+        None
+    }
+}
+
 pub struct BottleneckDetector {
     cv_threshold: f64,
     debounce_frames: u32,
@@ -25,10 +87,21 @@ impl BottleneckDetector {
         }
     }
 
-    pub fn classify(&mut self, frametimes: &[Duration], target_fps: u32) -> BottleneckType {
+    /// Classify using `signals` when they give a confident verdict, else
+    /// the frametime CV heuristic. Both paths share one debounce.
+    pub fn classify_with_signals(
+        &mut self,
+        frametimes: &[Duration],
+        target_fps: u32,
+        signals: Option<&LoadSignals>,
+    ) -> Classification {
         let n = frametimes.len();
         if n < 3 {
-            return BottleneckType::Unknown;
+            return Classification {
+                kind: BottleneckType::Unknown,
+                confidence: 0.0,
+                source: ClassSource::FrametimeCv,
+            };
         }
 
         let target_interval = Duration::from_secs_f64(1.0 / f64::from(target_fps));
@@ -40,7 +113,21 @@ impl BottleneckDetector {
             / n as f64;
 
         if mean_ns <= target_interval.as_nanos() as f64 {
-            return self.debounce(BottleneckType::Balanced);
+            return Classification {
+                kind: self.debounce(BottleneckType::Balanced),
+                confidence: 1.0,
+                source: ClassSource::FrametimeCv,
+            };
+        }
+
+        if let Some((raw, confidence)) = signals.and_then(classify_signals)
+            && confidence >= MIN_SIGNAL_CONFIDENCE
+        {
+            return Classification {
+                kind: self.debounce(raw),
+                confidence,
+                source: ClassSource::Signals,
+            };
         }
 
         let variance = frametimes
@@ -59,8 +146,18 @@ impl BottleneckDetector {
         } else {
             BottleneckType::Cpu
         };
+        let confidence = if self.cv_threshold > 0.0 {
+            (((cv - self.cv_threshold).abs() / self.cv_threshold) as f32).min(1.0)
+                * CV_CONFIDENCE_CAP
+        } else {
+            0.0
+        };
 
-        self.debounce(raw)
+        Classification {
+            kind: self.debounce(raw),
+            confidence,
+            source: ClassSource::FrametimeCv,
+        }
     }
 
     fn debounce(&mut self, raw: BottleneckType) -> BottleneckType {
@@ -109,7 +206,7 @@ mod tests {
             .take(30)
             .collect();
 
-        let bt = det.classify(&frametimes, target);
+        let bt = det.classify_with_signals(&frametimes, target, None).kind;
         assert_eq!(bt, BottleneckType::Gpu);
     }
 
@@ -125,7 +222,7 @@ mod tests {
             frametimes.push(ft_ns(interval_ns + jitter));
         }
 
-        let bt = det.classify(&frametimes, target);
+        let bt = det.classify_with_signals(&frametimes, target, None).kind;
         assert_eq!(bt, BottleneckType::Cpu);
     }
 
@@ -139,7 +236,7 @@ mod tests {
             .take(30)
             .collect();
 
-        let bt = det.classify(&frametimes, target);
+        let bt = det.classify_with_signals(&frametimes, target, None).kind;
         assert_eq!(bt, BottleneckType::Balanced);
     }
 
@@ -153,7 +250,7 @@ mod tests {
             .take(30)
             .collect();
         assert_eq!(
-            det.classify(&fast, target),
+            det.classify_with_signals(&fast, target, None).kind,
             BottleneckType::Balanced,
             "initial classification goes straight through",
         );
@@ -162,17 +259,17 @@ mod tests {
             .take(30)
             .collect();
         assert_eq!(
-            det.classify(&slow, target),
+            det.classify_with_signals(&slow, target, None).kind,
             BottleneckType::Balanced,
             "first slow call still debouncing",
         );
         assert_eq!(
-            det.classify(&slow, target),
+            det.classify_with_signals(&slow, target, None).kind,
             BottleneckType::Balanced,
             "second slow call still debouncing",
         );
         assert_eq!(
-            det.classify(&slow, target),
+            det.classify_with_signals(&slow, target, None).kind,
             BottleneckType::Gpu,
             "third slow call passes debounce threshold",
         );
@@ -182,7 +279,76 @@ mod tests {
     fn too_few_frames_returns_unknown() {
         let mut det = BottleneckDetector::new(0.15, 3);
         let frametimes = vec![ft_ns(16_000_000), ft_ns(17_000_000)];
-        assert_eq!(det.classify(&frametimes, 60), BottleneckType::Unknown);
+        assert_eq!(
+            det.classify_with_signals(&frametimes, 60, None).kind,
+            BottleneckType::Unknown
+        );
+    }
+
+    fn signals(gpu: f32, thread: f32, cluster: f32) -> LoadSignals {
+        LoadSignals {
+            hot_thread: Some(thread),
+            gpu_busy: Some(gpu),
+            cluster_util: Some(cluster),
+            cpu_pressure: None,
+        }
+    }
+
+    #[test]
+    fn cpu_pressure_tips_inconclusive_signals_to_cpu() {
+        let calm = signals(0.55, 0.6, 0.5);
+        assert_eq!(classify_signals(&calm), None);
+        let contended = LoadSignals {
+            cpu_pressure: Some(0.25),
+            ..calm
+        };
+        let (kind, _) = classify_signals(&contended).unwrap();
+        assert_eq!(kind, BottleneckType::Cpu);
+    }
+
+    #[test]
+    fn saturated_gpu_wins_over_spiky_frametimes() {
+        let mut det = BottleneckDetector::new(0.15, 3);
+        let interval_ns = 1_000_000_000 / 60;
+        let mut frametimes = Vec::with_capacity(30);
+        for i in 0..30 {
+            let jitter = if i % 3 == 0 { 30_000_000 } else { 1_000_000 };
+            frametimes.push(ft_ns(interval_ns + jitter));
+        }
+
+        let c = det.classify_with_signals(&frametimes, 60, Some(&signals(0.98, 0.55, 0.40)));
+        assert_eq!(c.kind, BottleneckType::Gpu);
+        assert_eq!(c.source, ClassSource::Signals);
+        assert!(c.confidence > 0.8, "confidence {}", c.confidence);
+    }
+
+    #[test]
+    fn pegged_render_thread_is_cpu_bound() {
+        let (kind, conf) = classify_signals(&signals(0.45, 0.97, 0.50)).unwrap();
+        assert_eq!(kind, BottleneckType::Cpu);
+        assert!(conf > 0.9);
+    }
+
+    #[test]
+    fn inconclusive_signals_fall_back_to_cv() {
+        assert_eq!(classify_signals(&signals(0.5, 0.45, 0.3)), None);
+        assert_eq!(
+            classify_signals(&LoadSignals {
+                gpu_busy: Some(0.9),
+                ..LoadSignals::default()
+            }),
+            None
+        );
+
+        let mut det = BottleneckDetector::new(0.15, 3);
+        let interval_ns = 1_000_000_000 / 60;
+        let steady: Vec<_> = std::iter::repeat(ft_ns(interval_ns + 5_000_000))
+            .take(30)
+            .collect();
+        let c = det.classify_with_signals(&steady, 60, Some(&signals(0.5, 0.45, 0.3)));
+        assert_eq!(c.kind, BottleneckType::Gpu);
+        assert_eq!(c.source, ClassSource::FrametimeCv);
+        assert!(c.confidence <= CV_CONFIDENCE_CAP);
     }
 
     #[test]
@@ -194,9 +360,9 @@ mod tests {
         let slow: Vec<_> = std::iter::repeat(ft_ns(interval_ns + 10_000_000))
             .take(30)
             .collect();
-        det.classify(&slow, target);
-        det.classify(&slow, target);
-        det.classify(&slow, target);
+        det.classify_with_signals(&slow, target, None);
+        det.classify_with_signals(&slow, target, None);
+        det.classify_with_signals(&slow, target, None);
         assert_eq!(det.current, BottleneckType::Gpu);
 
         det.reset();
@@ -207,7 +373,7 @@ mod tests {
             .take(30)
             .collect();
         assert_eq!(
-            det.classify(&fast, target),
+            det.classify_with_signals(&fast, target, None).kind,
             BottleneckType::Balanced,
             "after reset, first classify goes through immediately",
         );
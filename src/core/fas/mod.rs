@@ -1,3 +1,4 @@
 pub mod bottleneck;
 pub mod buffer;
+pub mod signals;
 pub mod source;
@@ -0,0 +1,322 @@
+// Load signals for bottleneck classification.
+//
+// Four independent inputs, each optional — a missing proc/sysfs node just
+// drops that input and the classifier works with what is left:
+//
+//   - hot thread:   busiest game thread's share of one core, from
+//                   `/proc/<pid>/task/*/schedstat` runtime deltas across
+//                   every process of the game. Known render/main threads
+//                   (by `comm`) win over the rest.
+//   - GPU busy:     `GpuCollector` load, 0..1.
+//   - cluster util: busiest CPU cluster's mean utilisation, from per-cpu
+//                   `/proc/stat` deltas grouped by `CoreLayout`.
+//   - CPU pressure: share of time the game's tasks sat runnable but
+//                   waiting for a CPU, from its processes' cgroup
+//                   `cpu.pressure`, worst one wins (system-wide
+//                   `/proc/pressure/cpu` without one).
+//
+// All four are rates, so the first sample after a (re)start only seeds
+// the counters and returns nothing.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::core::psi::StallRate;
+
+use crate::core::telemetry::gpu::GpuCollector;
+use crate::core::tweaks::ceiling::CoreLayout;
+
+/// Don't resample faster than this; shorter windows make the runtime
+/// ratios noisy and cost a full task-dir walk each time.
+const MIN_SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Thread-name prefixes of engine render/main threads.
+pub const RENDER_THREAD_PREFIXES: &[&str] = &[
+    "UnityMain",
+    "UnityGfx",
+    "RenderThread",
+    "GameThread",
+    "RHIThread",
+    "MainThread",
+    "GLThread",
+    "Thread-Render",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LoadSignals {
+    /// Busiest render/main thread runtime as a fraction of one core.
+    pub hot_thread: Option<f32>,
+    /// GPU busy fraction.
+    pub gpu_busy: Option<f32>,
+    /// Busiest cluster's mean utilisation.
+    pub cluster_util: Option<f32>,
+    /// Share of time the game waited for a CPU (PSI `some`), 0..1.
+    pub cpu_pressure: Option<f32>,
+}
+
+pub struct SignalSampler {
+    gpu: GpuCollector,
+    clusters: Vec<Vec<usize>>,
+    pids: Vec<i32>,
+    last_at: Option<Instant>,
+    last: Option<LoadSignals>,
+    /// tid → cumulative runtime (ns).
+    thread_runtime: HashMap<i32, u64>,
+    /// cpu id → (busy, total) jiffies.
+    cpu_times: HashMap<usize, (u64, u64)>,
+    /// `cpu.pressure` file → its stall rate, one per distinct cgroup.
+    cpu_stall: HashMap<PathBuf, StallRate>,
+}
+
+impl Default for SignalSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignalSampler {
+    pub fn new() -> Self {
+        let layout = CoreLayout::detect();
+        let clusters = [layout.little_ids, layout.big_ids, layout.prime_ids]
+            .into_iter()
+            .filter(|c| !c.is_empty())
+            .collect();
+        Self {
+            gpu: GpuCollector::new(),
+            clusters,
+            pids: Vec::new(),
+            last_at: None,
+            last: None,
+            thread_runtime: HashMap::new(),
+            cpu_times: HashMap::new(),
+            cpu_stall: HashMap::new(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.pids.clear();
+        self.last_at = None;
+        self.last = None;
+        self.thread_runtime.clear();
+        self.cpu_times.clear();
+        self.cpu_stall.clear();
+    }
+
+    /// Sample signals for the game's processes `pids`. Returns the
+    /// previous result when called again within [`MIN_SAMPLE_INTERVAL`],
+    /// and `None` until two samples exist to diff.
+    pub fn sample(&mut self, pids: &[i32]) -> Option<LoadSignals> {
+        if pids.is_empty() {
+            return None;
+        }
+        if self.pids != pids {
+            // A process joining or leaving keeps the counters; a whole
+            // new set is a new game.
+            if !pids.iter().any(|p| self.pids.contains(p)) {
+                self.reset();
+            }
+            self.pids = pids.to_vec();
+            let paths: Vec<PathBuf> = pids.iter().map(|&p| cpu_pressure_path(p)).collect();
+            self.cpu_stall.retain(|p, _| paths.contains(p));
+            for path in paths {
+                self.cpu_stall.entry(path).or_default();
+            }
+        }
+        let now = Instant::now();
+        if let Some(at) = self.last_at
+            && now.duration_since(at) < MIN_SAMPLE_INTERVAL
+        {
+            return self.last;
+        }
+        let wall_ns = self
+            .last_at
+            .map(|at| now.duration_since(at).as_nanos() as f64);
+        self.last_at = Some(now);
+
+        let threads: Vec<_> = pids
+            .iter()
+            .flat_map(|&pid| read_thread_runtimes(pid))
+            .collect();
+        let hot_thread = wall_ns.and_then(|wall| {
+            hot_thread_share(&self.thread_runtime, &threads, wall).map(|v| v as f32)
+        });
+        self.thread_runtime = threads
+            .into_iter()
+            .map(|(tid, _, runtime)| (tid, runtime))
+            .collect();
+
+        let cpu_times = fs::read_to_string("/proc/stat")
+            .map(|s| parse_proc_stat_cpus(&s))
+            .unwrap_or_default();
+        let cluster_util = self.busiest_cluster(&cpu_times);
+        self.cpu_times = cpu_times;
+
+        let gpu_busy = self
+            .gpu
+            .snapshot()
+            .load_pct
+            .map(|p| (p.min(100) as f32) / 100.0);
+
+        let cpu_pressure = self
+            .cpu_stall
+            .iter_mut()
+            .filter_map(|(path, rate)| {
+                crate::core::psi::read_path(path).map(|p| rate.update(now, p).some / 100.0)
+            })
+            .reduce(f32::max);
+
+        self.last = wall_ns.map(|_| LoadSignals {
+            hot_thread,
+            gpu_busy,
+            cluster_util,
+            cpu_pressure,
+        });
+        self.last
+    }
+
+    fn busiest_cluster(&self, now: &HashMap<usize, (u64, u64)>) -> Option<f32> {
+        self.clusters
+            .iter()
+            .filter_map(|cluster| {
+                let utils: Vec<f64> = cluster
+                    .iter()
+                    .filter_map(|id| {
+                        let (b1, t1) = *now.get(id)?;
+                        let (b0, t0) = *self.cpu_times.get(id)?;
+                        let dt = t1.checked_sub(t0).filter(|&d| d > 0)?;
+                        Some(b1.saturating_sub(b0) as f64 / dt as f64)
+                    })
+                    .collect();
+                (!utils.is_empty()).then(|| (utils.iter().sum::<f64>() / utils.len() as f64) as f32)
+            })
+            .reduce(f32::max)
+    }
+}
+
+/// The game's own cgroup `cpu.pressure` when cgroup v2 exposes one,
+/// else the system-wide file.
+fn cpu_pressure_path(pid: i32) -> PathBuf {
+    use crate::core::tweaks::memory::{CGROUP_V2_ROOT, parse_cgroup_v2_path};
+
+    fs::read_to_string(format!("/proc/{pid}/cgroup"))
+        .ok()
+        .and_then(|c| {
+            let path = PathBuf::from(CGROUP_V2_ROOT)
+                .join(parse_cgroup_v2_path(&c)?.trim_start_matches('/'))
+                .join("cpu.pressure");
+            path.exists().then_some(path)
+        })
+        .unwrap_or_else(|| PathBuf::from("/proc/pressure/cpu"))
+}
+
+pub fn is_render_thread(comm: &str) -> bool {
+    RENDER_THREAD_PREFIXES.iter().any(|p| comm.starts_with(p))
+}
+
+/// `(tid, comm, runtime_ns)` for every thread of `pid`.
+pub fn read_thread_runtimes(pid: i32) -> Vec<(i32, String, u64)> {
+    let Ok(entries) = fs::read_dir(format!("/proc/{pid}/task")) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|e| {
+            let tid = e.file_name().to_str()?.parse::<i32>().ok()?;
+            let runtime = parse_schedstat(&fs::read_to_string(e.path().join("schedstat")).ok()?)?;
+            let comm = fs::read_to_string(e.path().join("comm"))
+                .map(|s| s.trim().to_string())
+                .unwrap_or_default();
+            Some((tid, comm, runtime))
+        })
+        .collect()
+}
+
+/// First field of `schedstat`: time spent on-cpu, in ns.
+pub fn parse_schedstat(raw: &str) -> Option<u64> {
+    raw.split_whitespace().next()?.parse().ok()
+}
+
+/// Busiest thread's runtime delta over `wall_ns`. Render/main threads are
+/// considered first; only if none of them ran does any thread count.
+fn hot_thread_share(
+    before: &HashMap<i32, u64>,
+    now: &[(i32, String, u64)],
+    wall_ns: f64,
+) -> Option<f64> {
+    if wall_ns <= 0.0 {
+        return None;
+    }
+    let delta = |tid: &i32, runtime: u64| before.get(tid).map(|b| runtime.saturating_sub(*b));
+    let best = |render_only: bool| {
+        now.iter()
+            .filter(|(_, comm, _)| !render_only || is_render_thread(comm))
+            .filter_map(|(tid, _, rt)| delta(tid, *rt))
+            .max()
+            .filter(|&d| d > 0)
+    };
+    best(true)
+        .or_else(|| best(false))
+        .map(|d| (d as f64 / wall_ns).min(1.0))
+}
+
+/// Per-cpu `(busy, total)` jiffies from `/proc/stat`. iowait counts as idle.
+pub fn parse_proc_stat_cpus(raw: &str) -> HashMap<usize, (u64, u64)> {
+    raw.lines()
+        .filter_map(|line| {
+            let mut it = line.split_whitespace();
+            let id = it.next()?.strip_prefix("cpu")?.parse::<usize>().ok()?;
+            let fields: Vec<u64> = it.filter_map(|v| v.parse().ok()).collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            let total: u64 = fields.iter().sum();
+            let idle = fields[3] + fields[4];
+            Some((id, (total.saturating_sub(idle), total)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_schedstat_runtime() {
+        assert_eq!(parse_schedstat("123456789 2000 15\n"), Some(123_456_789));
+        assert_eq!(parse_schedstat(""), None);
+    }
+
+    #[test]
+    fn parses_per_cpu_lines_only() {
+        let raw = "cpu  10 0 10 70 10 0 0 0 0 0\n\
+                   cpu0 5 0 5 30 10 0 0 0 0 0\n\
+                   cpu7 50 0 30 20 0 0 0 0 0 0\n\
+                   intr 1 2 3\n";
+        let m = parse_proc_stat_cpus(raw);
+        assert_eq!(m.len(), 2);
+        assert_eq!(m[&0], (10, 50));
+        assert_eq!(m[&7], (80, 100));
+    }
+
+    #[test]
+    fn render_thread_beats_busier_worker() {
+        let before = HashMap::from([(1, 0), (2, 0)]);
+        let now = vec![
+            (1, "UnityMain".to_string(), 80_000_000),
+            (2, "Worker".to_string(), 95_000_000),
+        ];
+        let share = hot_thread_share(&before, &now, 100_000_000.0).unwrap();
+        assert!((share - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn falls_back_to_any_thread_when_no_render_thread_ran() {
+        let before = HashMap::from([(2, 10)]);
+        let now = vec![(2, "Job.Worker 0".to_string(), 50_000_010)];
+        let share = hot_thread_share(&before, &now, 100_000_000.0).unwrap();
+        assert!((share - 0.5).abs() < 1e-9);
+        assert_eq!(hot_thread_share(&HashMap::new(), &now, 1.0), None);
+    }
+}
@@ -13,13 +13,15 @@ use anyhow::Result;
 use std::time::Duration;
 use tokio::sync::broadcast;
 
+use crate::core::ebpf::FrameEvent;
+
 pub struct FrameSource {
     package: String,
-    rx: broadcast::Receiver<Duration>,
+    rx: broadcast::Receiver<FrameEvent>,
 }
 
 impl FrameSource {
-    pub fn new(rx: broadcast::Receiver<Duration>) -> Self {
+    pub fn new(rx: broadcast::Receiver<FrameEvent>) -> Self {
         tracing::info!(
             target: "auriya::fas",
             "FAS    | Frame source: eBPF broadcast (shared with FpsMeter)"
@@ -42,9 +44,9 @@ impl FrameSource {
         let mut out = Vec::new();
         loop {
             match self.rx.try_recv() {
-                Ok(dt) => {
-                    if dt < Duration::from_millis(500) {
-                        out.push(dt);
+                Ok(ev) => {
+                    if ev.frametime < Duration::from_millis(500) {
+                        out.push(ev.frametime);
                     }
                 }
                 Err(broadcast::error::TryRecvError::Empty) => break,
@@ -14,6 +14,8 @@ use std::path::Path;
 use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
 
+use crate::core::ebpf::FrameEvent;
+
 const FPS_SYSFS_PATHS: &[&str] = &[
     "/sys/class/drm/sde-crtc-0/measured_fps",
     "/sys/class/drm/card0/sde-crtc-0/measured_fps",
@@ -43,7 +45,7 @@ pub struct FpsReading {
 }
 
 pub struct FpsMeter {
-    ebpf_rx: Option<broadcast::Receiver<Duration>>,
+    ebpf_rx: Option<broadcast::Receiver<FrameEvent>>,
     frametimes: VecDeque<Duration>,
 
     sysfs_path: Option<String>,
@@ -55,7 +57,7 @@ pub struct FpsMeter {
 }
 
 impl FpsMeter {
-    pub fn new(ebpf_rx: Option<broadcast::Receiver<Duration>>) -> Self {
+    pub fn new(ebpf_rx: Option<broadcast::Receiver<FrameEvent>>) -> Self {
         let sysfs_path = Self::detect_sysfs();
         tracing::debug!(
             target: "auriya::fps",
@@ -129,9 +131,9 @@ impl FpsMeter {
         let mut new_frames = false;
         loop {
             match rx.try_recv() {
-                Ok(dt) => {
-                    if dt < Duration::from_millis(500) {
-                        self.frametimes.push_front(dt);
+                Ok(ev) => {
+                    if ev.frametime < Duration::from_millis(500) {
+                        self.frametimes.push_front(ev.frametime);
                         new_frames = true;
                     }
                 }
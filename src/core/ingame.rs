@@ -0,0 +1,346 @@
+// In-game activity detection: is the focused game actually being played?
+//
+// Two states besides normal play, both from cheap per-tick signals:
+//
+//   - loading: the game (or the system) is reading hard — `read_bytes`
+//     from `/proc/<pid>/io`, iowait in `/proc/stat` or I/O pressure —
+//     while frames are slow. Boosting here only burns power; the
+//     bottleneck is storage.
+//   - idle:    lobby, pause menu or AFK screen — no touch for a while and
+//     a low, steady frame rate.
+//
+// Loading wins over idle. Both clear with hysteresis so a single quiet
+// sample doesn't bounce the profile.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::time::{Duration, Instant};
+
+/// Samples (≈500 ms ticks) of frame rate kept for the idle check.
+const IDLE_WINDOW: usize = 20;
+/// Frame-rate CV at or below which the rate counts as steady.
+const IDLE_MAX_CV: f64 = 0.1;
+/// Idle ends once the recent mean climbs this far above `idle_max_fps`.
+const IDLE_EXIT_MARGIN_FPS: f64 = 5.0;
+/// Recent samples averaged for the idle exit check.
+const IDLE_EXIT_SAMPLES: usize = 4;
+/// Consecutive I/O-heavy slow samples needed to enter loading.
+const LOADING_ENTER_SAMPLES: u32 = 2;
+/// Consecutive quiet samples needed to leave loading.
+const LOADING_EXIT_SAMPLES: u32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InGameState {
+    #[default]
+    Active,
+    Idle,
+    Loading,
+}
+
+impl std::fmt::Display for InGameState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Active => write!(f, "active"),
+            Self::Idle => write!(f, "idle"),
+            Self::Loading => write!(f, "loading"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Thresholds {
+    pub idle_after: Duration,
+    pub idle_max_fps: f64,
+    pub loading_read_kbps: f64,
+    pub loading_iowait_pct: f64,
+    pub loading_io_psi_pct: f64,
+}
+
+/// One tick's worth of inputs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sample {
+    pub fps: Option<f64>,
+    /// Frames are below what the game is aiming for.
+    pub slow: bool,
+    /// `None` while no touchscreen is being watched; idle is never
+    /// detected then, as untouched can't be told from unknown.
+    pub since_touch: Option<Duration>,
+    pub iowait_pct: Option<f64>,
+    pub read_kbps: Option<f64>,
+    /// Share of time some task stalled on I/O (`/proc/pressure/io`).
+    pub io_pressure_pct: Option<f64>,
+}
+
+#[derive(Debug, Default)]
+pub struct InGameDetector {
+    state: InGameState,
+    fps: VecDeque<f64>,
+    loading_streak: u32,
+    quiet_streak: u32,
+}
+
+impl InGameDetector {
+    pub fn state(&self) -> InGameState {
+        self.state
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Feed one sample; returns the new state when it changed.
+    pub fn update(&mut self, s: &Sample, t: &Thresholds) -> Option<InGameState> {
+        if let Some(fps) = s.fps {
+            if self.fps.len() == IDLE_WINDOW {
+                self.fps.pop_front();
+            }
+            self.fps.push_back(fps);
+        }
+
+        let io_heavy = s.read_kbps.is_some_and(|r| r >= t.loading_read_kbps)
+            || s.iowait_pct.is_some_and(|w| w >= t.loading_iowait_pct)
+            || s.io_pressure_pct.is_some_and(|p| p >= t.loading_io_psi_pct);
+        if io_heavy && s.slow {
+            self.loading_streak += 1;
+            self.quiet_streak = 0;
+        } else {
+            self.loading_streak = 0;
+            self.quiet_streak += 1;
+        }
+
+        let next = if self.loading_streak >= LOADING_ENTER_SAMPLES
+            || (self.state == InGameState::Loading && self.quiet_streak < LOADING_EXIT_SAMPLES)
+        {
+            InGameState::Loading
+        } else if s.since_touch.is_none_or(|d| d < t.idle_after) {
+            InGameState::Active
+        } else if self.state == InGameState::Idle {
+            if self.woke(t) {
+                InGameState::Active
+            } else {
+                InGameState::Idle
+            }
+        } else if self.steady_low(t) {
+            InGameState::Idle
+        } else {
+            InGameState::Active
+        };
+        if next == self.state {
+            return None;
+        }
+        if self.state == InGameState::Idle {
+            // Start the idle window over so stale lobby samples can't
+            // pull the game straight back in.
+            self.fps.clear();
+        }
+        self.state = next;
+        Some(next)
+    }
+
+    /// Full window, mean at or under `idle_max_fps`, and steady.
+    fn steady_low(&self, t: &Thresholds) -> bool {
+        if self.fps.len() < IDLE_WINDOW {
+            return false;
+        }
+        let n = self.fps.len() as f64;
+        let mean = self.fps.iter().sum::<f64>() / n;
+        if mean <= 0.0 || mean > t.idle_max_fps {
+            return false;
+        }
+        let var = self.fps.iter().map(|f| (f - mean).powi(2)).sum::<f64>() / n;
+        var.sqrt() / mean <= IDLE_MAX_CV
+    }
+
+    /// Frame rate recovered well above the idle band.
+    fn woke(&self, t: &Thresholds) -> bool {
+        let recent: Vec<f64> = self
+            .fps
+            .iter()
+            .rev()
+            .take(IDLE_EXIT_SAMPLES)
+            .copied()
+            .collect();
+        !recent.is_empty()
+            && recent.iter().sum::<f64>() / recent.len() as f64
+                > t.idle_max_fps + IDLE_EXIT_MARGIN_FPS
+    }
+}
+
+/// Rates behind the loading check: system iowait share and the game's
+/// storage reads.
+#[derive(Debug, Default)]
+pub struct IoSampler {
+    at: Option<Instant>,
+    cpu: Option<(u64, u64)>,
+    /// pid → cumulative `read_bytes`.
+    reads: HashMap<i32, u64>,
+}
+
+impl IoSampler {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// `(iowait %, read KiB/s)` since the previous call; `None`s on the
+    /// first call or when the files can't be read.
+    pub fn sample(&mut self, pids: &[i32]) -> (Option<f64>, Option<f64>) {
+        let now = Instant::now();
+        let elapsed = self.at.map(|at| now.duration_since(at).as_secs_f64());
+        self.at = Some(now);
+
+        let cpu = fs::read_to_string("/proc/stat")
+            .ok()
+            .and_then(|s| parse_cpu_iowait(&s));
+        let iowait = match (self.cpu, cpu) {
+            (Some((w0, t0)), Some((w1, t1))) if t1 > t0 => {
+                Some(w1.saturating_sub(w0) as f64 * 100.0 / (t1 - t0) as f64)
+            }
+            _ => None,
+        };
+        self.cpu = cpu;
+
+        let reads: HashMap<i32, u64> = pids
+            .iter()
+            .filter_map(|&pid| {
+                let io = fs::read_to_string(format!("/proc/{pid}/io")).ok()?;
+                Some((pid, parse_read_bytes(&io)?))
+            })
+            .collect();
+        let read_kbps = elapsed.filter(|&e| e > 0.0).and_then(|e| {
+            let delta: u64 = reads
+                .iter()
+                .filter_map(|(pid, now)| Some(now.saturating_sub(*self.reads.get(pid)?)))
+                .sum();
+            (!reads.is_empty()).then(|| delta as f64 / 1024.0 / e)
+        });
+        self.reads = reads;
+
+        (iowait, read_kbps)
+    }
+}
+
+/// `(iowait, total)` jiffies from the aggregate `cpu` line.
+fn parse_cpu_iowait(stat: &str) -> Option<(u64, u64)> {
+    let line = stat.lines().find(|l| l.starts_with("cpu "))?;
+    let fields: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|v| v.parse().ok())
+        .collect();
+    Some((*fields.get(4)?, fields.iter().sum()))
+}
+
+/// `read_bytes` from `/proc/<pid>/io` (bytes actually fetched from storage).
+fn parse_read_bytes(io: &str) -> Option<u64> {
+    io.lines()
+        .find_map(|l| l.strip_prefix("read_bytes:"))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thresholds() -> Thresholds {
+        Thresholds {
+            idle_after: Duration::from_secs(30),
+            idle_max_fps: 35.0,
+            loading_read_kbps: 8192.0,
+            loading_iowait_pct: 15.0,
+            loading_io_psi_pct: 20.0,
+        }
+    }
+
+    fn sample(fps: f64, since_touch_s: u64) -> Sample {
+        Sample {
+            fps: Some(fps),
+            slow: false,
+            since_touch: Some(Duration::from_secs(since_touch_s)),
+            iowait_pct: Some(1.0),
+            read_kbps: Some(0.0),
+            io_pressure_pct: None,
+        }
+    }
+
+    #[test]
+    fn parses_proc_files() {
+        let stat = "cpu  100 0 50 800 40 0 10 0 0 0\ncpu0 1 2 3 4 5\n";
+        assert_eq!(parse_cpu_iowait(stat), Some((40, 1000)));
+        let io = "rchar: 1234\nwchar: 10\nread_bytes: 4096\nwrite_bytes: 0\n";
+        assert_eq!(parse_read_bytes(io), Some(4096));
+        assert_eq!(parse_read_bytes("rchar: 1\n"), None);
+    }
+
+    #[test]
+    fn low_steady_fps_without_touch_goes_idle_and_touch_wakes() {
+        let t = thresholds();
+        let mut d = InGameDetector::default();
+        for _ in 0..IDLE_WINDOW - 1 {
+            assert_eq!(d.update(&sample(30.0, 40), &t), None);
+        }
+        assert_eq!(d.update(&sample(30.0, 40), &t), Some(InGameState::Idle));
+        assert_eq!(d.update(&sample(30.0, 40), &t), None);
+        assert_eq!(d.update(&sample(30.0, 0), &t), Some(InGameState::Active));
+    }
+
+    #[test]
+    fn recently_touched_or_busy_game_stays_active() {
+        let t = thresholds();
+        let mut d = InGameDetector::default();
+        for _ in 0..IDLE_WINDOW * 2 {
+            assert_eq!(d.update(&sample(30.0, 5), &t), None);
+        }
+        let mut d = InGameDetector::default();
+        for i in 0..IDLE_WINDOW * 2 {
+            // Untouched but a fluctuating, high rate: cutscene, not a lobby.
+            let fps = if i % 2 == 0 { 60.0 } else { 45.0 };
+            assert_eq!(d.update(&sample(fps, 60), &t), None);
+        }
+        // No touch input at all: a 30 fps game is just a 30 fps game.
+        let mut d = InGameDetector::default();
+        for _ in 0..IDLE_WINDOW * 2 {
+            let s = Sample {
+                since_touch: None,
+                ..sample(30.0, 0)
+            };
+            assert_eq!(d.update(&s, &t), None);
+        }
+    }
+
+    #[test]
+    fn io_heavy_slow_frames_are_loading_with_hysteresis() {
+        let t = thresholds();
+        let mut d = InGameDetector::default();
+        let loading = Sample {
+            slow: true,
+            read_kbps: Some(20_000.0),
+            ..sample(20.0, 0)
+        };
+        assert_eq!(d.update(&loading, &t), None);
+        assert_eq!(d.update(&loading, &t), Some(InGameState::Loading));
+        for _ in 0..LOADING_EXIT_SAMPLES - 1 {
+            assert_eq!(d.update(&sample(60.0, 0), &t), None);
+        }
+        assert_eq!(d.update(&sample(60.0, 0), &t), Some(InGameState::Active));
+        // Heavy reads with frames on target are just streaming, not loading.
+        let streaming = Sample {
+            slow: false,
+            ..loading
+        };
+        assert_eq!(d.update(&streaming, &t), None);
+        assert_eq!(d.update(&streaming, &t), None);
+    }
+
+    #[test]
+    fn io_pressure_alone_marks_loading() {
+        let t = thresholds();
+        let mut d = InGameDetector::default();
+        let stalled = Sample {
+            slow: true,
+            io_pressure_pct: Some(35.0),
+            ..sample(20.0, 0)
+        };
+        assert_eq!(d.update(&stalled, &t), None);
+        assert_eq!(d.update(&stalled, &t), Some(InGameState::Loading));
+    }
+}
@@ -0,0 +1,133 @@
+// Touchscreen input decoding.
+//
+// Touchscreens are found by their evdev capabilities (multi-touch X axis)
+// rather than by name, since vendor driver names are all over the place.
+// Events are read raw from `/dev/input/eventN` and reduced to two
+// signals: a finger going down and a finger moving.
+
+use std::fs;
+use std::path::PathBuf;
+
+const EV_KEY: u16 = 0x01;
+const EV_ABS: u16 = 0x03;
+const BTN_TOUCH: u16 = 0x14a;
+const ABS_MT_POSITION_X: u16 = 0x35;
+const ABS_MT_POSITION_Y: u16 = 0x36;
+const ABS_MT_TRACKING_ID: u16 = 0x39;
+
+/// `struct input_event` on 64-bit kernels: 16-byte timeval, then type,
+/// code and value.
+pub const INPUT_EVENT_SIZE: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchKind {
+    Down,
+    Move,
+}
+
+/// `/dev/input/eventN` nodes whose device reports multi-touch positions.
+pub fn touchscreen_devices() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir("/sys/class/input") else {
+        return Vec::new();
+    };
+    let mut out: Vec<PathBuf> = entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_str()?.to_string();
+            if !name.starts_with("event") {
+                return None;
+            }
+            let caps = fs::read_to_string(e.path().join("device/capabilities/abs")).ok()?;
+            has_abs_bit(&caps, ABS_MT_POSITION_X).then(|| PathBuf::from("/dev/input").join(name))
+        })
+        .collect();
+    out.sort();
+    out
+}
+
+/// Test `bit` in a sysfs capability bitmap: space-separated hex words,
+/// most significant first, each `usize::BITS` wide.
+fn has_abs_bit(caps: &str, bit: u16) -> bool {
+    let word_bits = usize::BITS as usize;
+    let words: Vec<&str> = caps.split_whitespace().collect();
+    let idx = bit as usize / word_bits;
+    let Some(word) = words.len().checked_sub(idx + 1).map(|i| words[i]) else {
+        return false;
+    };
+    u64::from_str_radix(word, 16).is_ok_and(|w| (w >> (bit as usize % word_bits)) & 1 == 1)
+}
+
+/// `(type, code, value)` of one raw `input_event`.
+pub fn parse_event(raw: &[u8]) -> Option<(u16, u16, i32)> {
+    let raw = raw.get(16..INPUT_EVENT_SIZE)?;
+    Some((
+        u16::from_ne_bytes([raw[0], raw[1]]),
+        u16::from_ne_bytes([raw[2], raw[3]]),
+        i32::from_ne_bytes([raw[4], raw[5], raw[6], raw[7]]),
+    ))
+}
+
+/// Reduce an evdev stream to touch-down / move. Protocol A devices signal
+/// contact with `BTN_TOUCH`, protocol B ones with a new tracking id; either
+/// counts as down.
+#[derive(Debug, Default)]
+pub struct TouchDecoder {
+    touching: bool,
+}
+
+impl TouchDecoder {
+    pub fn feed(&mut self, ev_type: u16, code: u16, value: i32) -> Option<TouchKind> {
+        match (ev_type, code) {
+            (EV_KEY, BTN_TOUCH) => {
+                let was = self.touching;
+                self.touching = value != 0;
+                (!was && self.touching).then_some(TouchKind::Down)
+            }
+            (EV_ABS, ABS_MT_TRACKING_ID) if value >= 0 => {
+                self.touching = true;
+                Some(TouchKind::Down)
+            }
+            (EV_ABS, ABS_MT_POSITION_X | ABS_MT_POSITION_Y) if self.touching => {
+                Some(TouchKind::Move)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_mt_position_bit() {
+        // 64-bit words: bit 0x35 lives in the lowest word.
+        assert!(has_abs_bit("660800000000003", ABS_MT_POSITION_X));
+        assert!(has_abs_bit("1 660800000000003", ABS_MT_POSITION_X));
+        // Keyboard/sensor with only ABS_X/Y.
+        assert!(!has_abs_bit("3", ABS_MT_POSITION_X));
+        assert!(!has_abs_bit("", ABS_MT_POSITION_X));
+    }
+
+    #[test]
+    fn decodes_raw_event() {
+        let mut raw = [0u8; INPUT_EVENT_SIZE];
+        raw[16..18].copy_from_slice(&EV_ABS.to_ne_bytes());
+        raw[18..20].copy_from_slice(&ABS_MT_TRACKING_ID.to_ne_bytes());
+        raw[20..24].copy_from_slice(&(-1i32).to_ne_bytes());
+        assert_eq!(parse_event(&raw), Some((EV_ABS, ABS_MT_TRACKING_ID, -1)));
+        assert_eq!(parse_event(&raw[..20]), None);
+    }
+
+    #[test]
+    fn decoder_reports_down_then_moves() {
+        let mut d = TouchDecoder::default();
+        assert_eq!(d.feed(EV_ABS, ABS_MT_POSITION_X, 10), None);
+        assert_eq!(d.feed(EV_ABS, ABS_MT_TRACKING_ID, 7), Some(TouchKind::Down));
+        assert_eq!(d.feed(EV_KEY, BTN_TOUCH, 1), None);
+        assert_eq!(d.feed(EV_ABS, ABS_MT_POSITION_Y, 20), Some(TouchKind::Move));
+        assert_eq!(d.feed(EV_ABS, ABS_MT_TRACKING_ID, -1), None);
+        assert_eq!(d.feed(EV_KEY, BTN_TOUCH, 0), None);
+        assert_eq!(d.feed(EV_ABS, ABS_MT_POSITION_X, 30), None);
+    }
+}
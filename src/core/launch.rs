@@ -0,0 +1,324 @@
+// Game launch detection and launch-time records.
+//
+// The companion only reports a game once it has a focused window and a
+// PID, by which point a good part of the cold start (zygote fork, class
+// loading, the first asset reads) is already over. Two earlier signals
+// start a launch instead: a fresh process of a whitelisted package joining
+// the top-app cpuset, and the companion naming a whitelisted package as
+// focused before it has a PID.
+//
+// A launch is timed from the process's own start time, so detection
+// latency doesn't count, until the game has frames on screen and its
+// storage reads have gone quiet. Times are kept per game in `launch.json`
+// under the state dir.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+const TOP_APP_PROCS: &str = "/dev/cpuset/top-app/cgroup.procs";
+/// A process older than this when it's first seen is a resume, not a
+/// cold start.
+pub const COLD_START_MAX_AGE: Duration = Duration::from_secs(5);
+/// Consecutive quiet samples (≈500 ms ticks) with frames on screen that
+/// end a launch.
+const SETTLE_SAMPLES: u32 = 4;
+/// A launch that hasn't settled by then is dropped, not recorded.
+const MEASURE_MAX: Duration = Duration::from_secs(120);
+/// Launch times kept per game.
+const HISTORY: usize = 10;
+
+/// PIDs currently in the top-app cpuset, or `None` where it doesn't exist.
+pub fn top_app_pids() -> Option<HashSet<i32>> {
+    let procs = fs::read_to_string(TOP_APP_PROCS).ok()?;
+    Some(
+        procs
+            .lines()
+            .filter_map(|l| l.trim().parse().ok())
+            .collect(),
+    )
+}
+
+/// The package `pid` belongs to when it's one of `packages` and the
+/// process is young enough to be a cold start.
+pub fn cold_start_package(pid: i32, packages: &HashSet<String>) -> Option<String> {
+    let cmdline = fs::read(format!("/proc/{pid}/cmdline")).ok()?;
+    let argv0 = cmdline.split(|&b| b == 0).next()?;
+    let name = std::str::from_utf8(argv0).ok()?;
+    let pkg = name.split(':').next()?;
+    (packages.contains(pkg) && is_cold_start(pid)).then(|| pkg.to_string())
+}
+
+pub fn is_cold_start(pid: i32) -> bool {
+    process_age(pid).is_some_and(|age| age <= COLD_START_MAX_AGE)
+}
+
+/// Time since `pid` was forked.
+pub fn process_age(pid: i32) -> Option<Duration> {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    let start_ticks = parse_starttime(&stat)?;
+    let uptime: f64 = fs::read_to_string("/proc/uptime")
+        .ok()?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()?;
+    // SAFETY: sysconf has no preconditions.
+    let hz = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if hz <= 0 {
+        return None;
+    }
+    let started = start_ticks as f64 / hz as f64;
+    Some(Duration::from_secs_f64((uptime - started).max(0.0)))
+}
+
+/// `starttime` (field 22, clock ticks since boot) from `/proc/<pid>/stat`.
+/// `comm` may hold spaces and parentheses, so fields are counted from the
+/// last `)`.
+fn parse_starttime(stat: &str) -> Option<u64> {
+    let rest = &stat[stat.rfind(')')? + 1..];
+    rest.split_whitespace().nth(19)?.parse().ok()
+}
+
+/// One launch in flight, from detection until the game settles.
+#[derive(Debug)]
+pub struct Launch {
+    pub pkg: String,
+    pub pid: Option<i32>,
+    started: Instant,
+    detected: Instant,
+    /// A game session for `pkg` has been seen.
+    pub entered: bool,
+    quiet_since: Option<Instant>,
+    quiet: u32,
+}
+
+impl Launch {
+    pub fn new(pkg: &str, pid: Option<i32>) -> Self {
+        let now = Instant::now();
+        let started = pid
+            .and_then(process_age)
+            .and_then(|age| now.checked_sub(age))
+            .unwrap_or(now);
+        Self {
+            pkg: pkg.to_string(),
+            pid,
+            started,
+            detected: now,
+            entered: false,
+            quiet_since: None,
+            quiet: 0,
+        }
+    }
+
+    /// Adopt the game's PID once it's known, timing from its fork when
+    /// that was earlier. `false` when the process predates the launch:
+    /// focus came back to a running game rather than starting one.
+    pub fn set_pid(&mut self, pid: i32) -> bool {
+        let Some(started) = process_age(pid).and_then(|age| Instant::now().checked_sub(age)) else {
+            return true;
+        };
+        if started + COLD_START_MAX_AGE < self.detected {
+            return false;
+        }
+        self.pid = Some(pid);
+        self.started = self.started.min(started);
+        true
+    }
+
+    /// Feed one in-session sample. `frames` is whether the game is
+    /// presenting; `read_kbps` its storage read rate. Returns the launch
+    /// time once frames have been up with reads below `quiet_kbps` for
+    /// [`SETTLE_SAMPLES`] in a row.
+    pub fn update(
+        &mut self,
+        frames: bool,
+        read_kbps: Option<f64>,
+        quiet_kbps: f64,
+        now: Instant,
+    ) -> Option<Duration> {
+        self.entered = true;
+        if !frames || read_kbps.is_none_or(|r| r >= quiet_kbps) {
+            self.quiet_since = None;
+            self.quiet = 0;
+            return None;
+        }
+        let since = *self.quiet_since.get_or_insert(now);
+        self.quiet += 1;
+        (self.quiet >= SETTLE_SAMPLES).then(|| since.saturating_duration_since(self.started))
+    }
+
+    /// Past the measurement window.
+    pub fn expired(&self, now: Instant) -> bool {
+        now.duration_since(self.detected) >= MEASURE_MAX
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct LaunchRecord {
+    pub count: u32,
+    pub best_ms: u64,
+    /// Most recent last, at most [`HISTORY`] entries.
+    #[serde(default)]
+    pub recent_ms: Vec<u64>,
+}
+
+impl LaunchRecord {
+    fn push(&mut self, ms: u64) {
+        self.best_ms = if self.count == 0 {
+            ms
+        } else {
+            self.best_ms.min(ms)
+        };
+        self.count += 1;
+        self.recent_ms.push(ms);
+        if self.recent_ms.len() > HISTORY {
+            self.recent_ms.remove(0);
+        }
+    }
+
+    pub fn last_ms(&self) -> Option<u64> {
+        self.recent_ms.last().copied()
+    }
+
+    /// Mean of the recent launches.
+    pub fn avg_ms(&self) -> Option<u64> {
+        let n = self.recent_ms.len() as u64;
+        (n > 0).then(|| self.recent_ms.iter().sum::<u64>() / n)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LaunchStore {
+    #[serde(default)]
+    pub games: HashMap<String, LaunchRecord>,
+}
+
+impl LaunchStore {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content =
+            fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).context("Failed to parse launch.json")
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let json =
+            serde_json::to_string_pretty(self).context("Failed to serialize launch times")?;
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, json).context("Failed to write temporary file")?;
+        fs::rename(&temp_path, path).context("Failed to rename to final file")?;
+        Ok(())
+    }
+}
+
+/// Launch times for the stats API.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LaunchSummary {
+    pub pkg: String,
+    /// A launch of `pkg` is being timed right now.
+    pub launching: bool,
+    pub last_ms: Option<u64>,
+    pub best_ms: Option<u64>,
+    pub avg_ms: Option<u64>,
+    pub count: u32,
+}
+
+/// [`LaunchStore`] plus the file it lives in.
+pub struct LaunchLog {
+    path: PathBuf,
+    store: LaunchStore,
+}
+
+impl LaunchLog {
+    pub fn new(path: PathBuf) -> Self {
+        let store = LaunchStore::load(&path).unwrap_or_else(|e| {
+            warn!(target: "auriya::launch", "Ignoring unreadable {}: {e:#}", path.display());
+            LaunchStore::default()
+        });
+        Self { path, store }
+    }
+
+    pub fn record(&mut self, pkg: &str, took: Duration) {
+        let ms = u64::try_from(took.as_millis()).unwrap_or(u64::MAX);
+        self.store
+            .games
+            .entry(pkg.to_string())
+            .or_default()
+            .push(ms);
+        if let Err(e) = self.store.save(&self.path) {
+            warn!(target: "auriya::launch", "Failed to save launch times: {e:#}");
+        }
+    }
+
+    pub fn summary(&self, pkg: &str, launching: bool) -> LaunchSummary {
+        let rec = self.store.games.get(pkg);
+        LaunchSummary {
+            pkg: pkg.to_string(),
+            launching,
+            last_ms: rec.and_then(LaunchRecord::last_ms),
+            best_ms: rec.filter(|r| r.count > 0).map(|r| r.best_ms),
+            avg_ms: rec.and_then(LaunchRecord::avg_ms),
+            count: rec.map_or(0, |r| r.count),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_starttime_past_odd_comm() {
+        let stat = "4242 (Unity) Main)) S 1 4242 0 0 -1 4194624 100 0 0 0 5 3 0 0 \
+                    10 -10 40 0 987654 123456789 2000";
+        assert_eq!(parse_starttime(stat), Some(987_654));
+        assert_eq!(parse_starttime("4242 (x) S 1"), None);
+    }
+
+    #[test]
+    fn settles_after_quiet_frames_and_times_from_start() {
+        let mut l = Launch::new("com.game", None);
+        let t0 = l.started;
+        let tick = |n: u64| t0 + Duration::from_millis(500 * n);
+        // Loading: frames up but reads heavy.
+        assert_eq!(l.update(true, Some(20_000.0), 8192.0, tick(1)), None);
+        // No frames yet counts as unsettled too.
+        assert_eq!(l.update(false, Some(0.0), 8192.0, tick(2)), None);
+        for n in 3..6 {
+            assert_eq!(l.update(true, Some(100.0), 8192.0, tick(n)), None);
+        }
+        let took = l.update(true, Some(100.0), 8192.0, tick(6)).unwrap();
+        // Timed to the first quiet sample, not the end of the streak.
+        assert_eq!(took, Duration::from_millis(1500));
+        assert!(l.entered);
+    }
+
+    #[test]
+    fn record_keeps_best_and_recent_history() {
+        let mut r = LaunchRecord::default();
+        for ms in [9000, 7000, 8000] {
+            r.push(ms);
+        }
+        assert_eq!((r.count, r.best_ms), (3, 7000));
+        assert_eq!(r.last_ms(), Some(8000));
+        assert_eq!(r.avg_ms(), Some(8000));
+        for _ in 0..HISTORY {
+            r.push(6000);
+        }
+        assert_eq!(r.recent_ms.len(), HISTORY);
+        assert_eq!((r.count, r.best_ms, r.avg_ms()), (13, 6000, Some(6000)));
+    }
+}
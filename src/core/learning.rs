@@ -0,0 +1,481 @@
+// Per-game parameter learning.
+//
+// For a game's first `[learning] sessions` sessions the daemon records
+// what the game actually does: which frame rates it settles at while
+// nothing of ours caps it (no ceiling, no FAS target), how often
+// FAS sees a CPU or GPU bottleneck, and how hot it runs. Once the window
+// closes, those observations become a proposal for the game's
+// `target_fps`, `margin`, `mode` and `ceiling`.
+//
+// Everything lives in `learned.json` under the state dir, never in
+// `gamelist.toml`: the daemon only uses a learned value for a field the
+// user left unset, so hand-tuned entries always win.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::core::config::gamelist::{GameProfile, TargetFpsConfig};
+use crate::core::config::settings::LearningConfig;
+use crate::core::fas::bottleneck::BottleneckType;
+
+/// Refresh/frame-rate caps games commonly lock to. Observed rates are
+/// snapped to the nearest one within [`SNAP_TOLERANCE`].
+const STANDARD_RATES: &[u32] = &[24, 30, 40, 45, 48, 60, 72, 90, 120, 144, 165];
+const SNAP_TOLERANCE: f64 = 0.08;
+/// Share of samples a rate needs to count as a plateau.
+const PLATEAU_MIN_SHARE: f64 = 0.15;
+/// Share of hot samples above which the proposal turns conservative.
+const HOT_SHARE: f64 = 0.2;
+/// Share of CPU+GPU-bound ticks above which more headroom is proposed.
+const BOUND_SHARE: f64 = 0.3;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct GameObservations {
+    pub sessions: u32,
+    /// Rounded fps → samples.
+    #[serde(default)]
+    pub fps: BTreeMap<u32, u64>,
+    #[serde(default)]
+    pub cpu_bound: u64,
+    #[serde(default)]
+    pub gpu_bound: u64,
+    #[serde(default)]
+    pub balanced: u64,
+    #[serde(default)]
+    pub thermal_samples: u64,
+    #[serde(default)]
+    pub hot_samples: u64,
+    #[serde(default)]
+    pub max_temp_c: f32,
+    /// Written once the learning window closes.
+    #[serde(default)]
+    pub proposal: Option<Proposal>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Proposal {
+    pub target_fps: Vec<u32>,
+    pub margin: f64,
+    pub mode: String,
+    pub ceiling: String,
+    pub evidence: Vec<String>,
+}
+
+impl GameObservations {
+    pub fn record_fps(&mut self, fps: f64) {
+        if fps >= 1.0 {
+            *self.fps.entry(fps.round() as u32).or_default() += 1;
+        }
+    }
+
+    pub fn record_bottleneck(&mut self, b: BottleneckType) {
+        match b {
+            BottleneckType::Cpu => self.cpu_bound += 1,
+            BottleneckType::Gpu => self.gpu_bound += 1,
+            BottleneckType::Balanced => self.balanced += 1,
+            BottleneckType::Unknown => {}
+        }
+    }
+
+    pub fn record_temp(&mut self, temp_c: f32, hot_threshold: f32) {
+        self.thermal_samples += 1;
+        if temp_c > hot_threshold {
+            self.hot_samples += 1;
+        }
+        self.max_temp_c = self.max_temp_c.max(temp_c);
+    }
+
+    pub fn fps_samples(&self) -> u64 {
+        self.fps.values().sum()
+    }
+
+    /// Standard rates holding at least [`PLATEAU_MIN_SHARE`] of samples,
+    /// with their share, ascending.
+    pub fn plateaus(&self) -> Vec<(u32, f64)> {
+        let total = self.fps_samples();
+        if total == 0 {
+            return Vec::new();
+        }
+        let mut buckets: BTreeMap<u32, u64> = BTreeMap::new();
+        for (&fps, &n) in &self.fps {
+            let nearest = STANDARD_RATES
+                .iter()
+                .copied()
+                .min_by_key(|r| r.abs_diff(fps))
+                .filter(|&r| f64::from(r.abs_diff(fps)) <= f64::from(r) * SNAP_TOLERANCE);
+            if let Some(r) = nearest {
+                *buckets.entry(r).or_default() += n;
+            }
+        }
+        buckets
+            .into_iter()
+            .map(|(r, n)| (r, n as f64 / total as f64))
+            .filter(|&(_, share)| share >= PLATEAU_MIN_SHARE)
+            .collect()
+    }
+
+    /// Build a proposal from what has been observed. `None` until there
+    /// are `min_samples` frame-rate samples and at least one plateau.
+    pub fn propose(&self, min_samples: u64) -> Option<Proposal> {
+        let samples = self.fps_samples();
+        if samples < min_samples {
+            return None;
+        }
+        let plateaus = self.plateaus();
+        if plateaus.is_empty() {
+            return None;
+        }
+
+        let classified = self.cpu_bound + self.gpu_bound + self.balanced;
+        let share = |n: u64| {
+            if classified == 0 {
+                0.0
+            } else {
+                n as f64 / classified as f64
+            }
+        };
+        let bound = share(self.cpu_bound + self.gpu_bound);
+        let hot = if self.thermal_samples == 0 {
+            0.0
+        } else {
+            self.hot_samples as f64 / self.thermal_samples as f64
+        };
+
+        let (mode, ceiling) = if hot >= HOT_SHARE {
+            ("balance", "balance")
+        } else if bound >= BOUND_SHARE {
+            ("performance", "high")
+        } else {
+            ("fast", "balance")
+        };
+        let margin = if bound >= BOUND_SHARE { 2.0 } else { 1.0 };
+
+        let evidence = vec![
+            format!(
+                "fps plateaus: {} over {} samples in {} session(s)",
+                plateaus
+                    .iter()
+                    .map(|(r, s)| format!("{r} ({:.0}%)", s * 100.0))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                samples,
+                self.sessions,
+            ),
+            format!(
+                "bottleneck: cpu {:.0}% gpu {:.0}% balanced {:.0}% of {} classified ticks",
+                share(self.cpu_bound) * 100.0,
+                share(self.gpu_bound) * 100.0,
+                share(self.balanced) * 100.0,
+                classified,
+            ),
+            format!(
+                "thermal: max {:.1}°C, {:.0}% of samples over the FAS limit",
+                self.max_temp_c,
+                hot * 100.0,
+            ),
+        ];
+
+        Some(Proposal {
+            target_fps: plateaus.into_iter().map(|(r, _)| r).collect(),
+            margin,
+            mode: mode.to_string(),
+            ceiling: ceiling.to_string(),
+            evidence,
+        })
+    }
+}
+
+impl Proposal {
+    /// `profile` with every field the user left unset filled from this
+    /// proposal.
+    pub fn fill(&self, profile: &GameProfile) -> GameProfile {
+        let mut out = profile.clone();
+        if out.target_fps.is_none() {
+            out.target_fps = Some(match self.target_fps.as_slice() {
+                [single] => TargetFpsConfig::Single(*single),
+                many => TargetFpsConfig::Array(many.to_vec()),
+            });
+        }
+        if out.mode.is_none() {
+            out.mode = Some(self.mode.clone());
+        }
+        if out.ceiling.is_none() {
+            out.ceiling = Some(self.ceiling.clone());
+        }
+        if out.margin.is_none() {
+            out.margin = Some(self.margin);
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LearnedStore {
+    #[serde(default)]
+    pub games: HashMap<String, GameObservations>,
+}
+
+impl LearnedStore {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content =
+            std::fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).context("Failed to parse learned.json")
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let json =
+            serde_json::to_string_pretty(self).context("Failed to serialize learned state")?;
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, json).context("Failed to write temporary file")?;
+        std::fs::rename(&temp_path, path).context("Failed to rename to final file")?;
+        Ok(())
+    }
+}
+
+/// Session bookkeeping around a [`LearnedStore`]: counts sessions, routes
+/// samples to the game being learned and turns the observations into a
+/// proposal once the window is over.
+pub struct GameLearner {
+    path: PathBuf,
+    config: LearningConfig,
+    /// Shared with IPC so `GAME_SUGGEST` sees the running session.
+    store: Arc<RwLock<LearnedStore>>,
+    active: Option<String>,
+}
+
+impl GameLearner {
+    pub fn new(path: PathBuf, config: LearningConfig) -> Self {
+        let store = LearnedStore::load(&path).unwrap_or_else(|e| {
+            warn!(target: "auriya::learning", "Ignoring unreadable {}: {e:#}", path.display());
+            LearnedStore::default()
+        });
+        Self {
+            path,
+            config,
+            store: Arc::new(RwLock::new(store)),
+            active: None,
+        }
+    }
+
+    pub fn set_config(&mut self, config: LearningConfig) {
+        self.config = config;
+    }
+
+    /// Live view of the observations, including the unsaved session.
+    pub fn shared(&self) -> Arc<RwLock<LearnedStore>> {
+        self.store.clone()
+    }
+
+    /// Start a session for `pkg`. Games that already have a proposal are
+    /// left alone.
+    pub fn begin(&mut self, pkg: &str) {
+        self.end();
+        if !self.config.enabled {
+            return;
+        }
+        let Ok(mut store) = self.store.write() else {
+            return;
+        };
+        let obs = store.games.entry(pkg.to_string()).or_default();
+        if obs.proposal.is_some() {
+            return;
+        }
+        obs.sessions += 1;
+        debug!(target: "auriya::learning", "Learning {} (session {}/{})", pkg, obs.sessions, self.config.sessions);
+        self.active = Some(pkg.to_string());
+    }
+
+    /// Close the running session, proposing once the window is complete,
+    /// and persist.
+    pub fn end(&mut self) {
+        let Some(pkg) = self.active.take() else {
+            return;
+        };
+        let Ok(mut store) = self.store.write() else {
+            return;
+        };
+        if let Some(obs) = store.games.get_mut(&pkg)
+            && obs.sessions >= self.config.sessions
+            && let Some(p) = obs.propose(self.config.min_samples)
+        {
+            info!(
+                target: "auriya::learning",
+                "Learned {}: target_fps={:?} margin={} mode={} ceiling={}",
+                pkg, p.target_fps, p.margin, p.mode, p.ceiling
+            );
+            obs.proposal = Some(p);
+        }
+        if let Err(e) = store.save(&self.path) {
+            warn!(target: "auriya::learning", "Failed to save {}: {e:#}", self.path.display());
+        }
+    }
+
+    fn observe(&self, f: impl FnOnce(&mut GameObservations)) {
+        let Some(pkg) = self.active.as_ref() else {
+            return;
+        };
+        if let Ok(mut store) = self.store.write()
+            && let Some(obs) = store.games.get_mut(pkg)
+        {
+            f(obs);
+        }
+    }
+
+    pub fn observe_fps(&mut self, fps: f64) {
+        self.observe(|obs| obs.record_fps(fps));
+    }
+
+    pub fn observe_bottleneck(&mut self, b: BottleneckType) {
+        self.observe(|obs| obs.record_bottleneck(b));
+    }
+
+    pub fn observe_temp(&mut self, temp_c: f32, hot_threshold: f32) {
+        self.observe(|obs| obs.record_temp(temp_c, hot_threshold));
+    }
+
+    /// `profile` with the learned proposal filled into its unset fields,
+    /// when `auto_apply` is on and one exists.
+    pub fn apply(&self, profile: &GameProfile) -> Option<GameProfile> {
+        if !self.config.auto_apply {
+            return None;
+        }
+        let store = self.store.read().ok()?;
+        let proposal = store.games.get(&profile.package)?.proposal.as_ref()?;
+        Some(proposal.fill(profile))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observed(fps: &[(u32, u64)]) -> GameObservations {
+        GameObservations {
+            sessions: 3,
+            fps: fps.iter().copied().collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn snaps_samples_to_plateaus() {
+        let obs = observed(&[(59, 400), (60, 300), (61, 100), (30, 180), (47, 20)]);
+        let p = obs.plateaus();
+        assert_eq!(p.iter().map(|(r, _)| *r).collect::<Vec<_>>(), vec![30, 60]);
+        assert!((p[1].1 - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn proposal_reflects_heat_and_bottlenecks() {
+        let mut obs = observed(&[(60, 1000)]);
+        obs.cpu_bound = 40;
+        obs.balanced = 60;
+        let p = obs.propose(600).unwrap();
+        assert_eq!(p.target_fps, vec![60]);
+        assert_eq!(
+            (p.mode.as_str(), p.ceiling.as_str()),
+            ("performance", "high")
+        );
+        assert_eq!(p.margin, 2.0);
+
+        obs.thermal_samples = 100;
+        obs.hot_samples = 30;
+        let p = obs.propose(600).unwrap();
+        assert_eq!(
+            (p.mode.as_str(), p.ceiling.as_str()),
+            ("balance", "balance")
+        );
+        assert_eq!(p.evidence.len(), 3);
+
+        assert_eq!(observed(&[(60, 10)]).propose(600), None);
+    }
+
+    #[test]
+    fn fill_never_overrides_user_values() {
+        let proposal = Proposal {
+            target_fps: vec![30, 60],
+            margin: 2.0,
+            mode: "fast".into(),
+            ceiling: "high".into(),
+            evidence: Vec::new(),
+        };
+        let user = GameProfile {
+            package: "com.game".into(),
+            cpu_governor: "performance".into(),
+            enable_dnd: true,
+            target_fps: None,
+            refresh_rate: None,
+            mode: Some("performance".into()),
+            ceiling: None,
+            touch_boost: None,
+            idle_detect: None,
+            margin: None,
+            resolution: None,
+            oom_protect: None,
+            uclamp_min: None,
+            gpu_governor: None,
+        };
+        let filled = proposal.fill(&user);
+        assert_eq!(filled.mode.as_deref(), Some("performance"));
+        assert_eq!(filled.ceiling.as_deref(), Some("high"));
+        assert_eq!(filled.margin, Some(2.0));
+        assert!(matches!(filled.target_fps, Some(TargetFpsConfig::Array(ref a)) if a == &[30, 60]));
+    }
+
+    #[test]
+    fn learner_proposes_after_window_and_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("learned.json");
+        let config = LearningConfig {
+            sessions: 2,
+            min_samples: 10,
+            auto_apply: true,
+            ..Default::default()
+        };
+        let mut l = GameLearner::new(path.clone(), config.clone());
+        for _ in 0..2 {
+            l.begin("com.game");
+            for _ in 0..10 {
+                l.observe_fps(60.0);
+                l.observe_temp(40.0, 90.0);
+            }
+            l.end();
+        }
+        let l = GameLearner::new(path, config);
+        let profile = GameProfile {
+            package: "com.game".into(),
+            cpu_governor: String::new(),
+            enable_dnd: true,
+            target_fps: None,
+            refresh_rate: None,
+            mode: None,
+            ceiling: None,
+            touch_boost: None,
+            idle_detect: None,
+            margin: None,
+            resolution: None,
+            oom_protect: None,
+            uclamp_min: None,
+            gpu_governor: None,
+        };
+        let filled = l.apply(&profile).unwrap();
+        assert_eq!(filled.mode.as_deref(), Some("fast"));
+        assert!(matches!(
+            filled.target_fps,
+            Some(TargetFpsConfig::Single(60))
+        ));
+    }
+}
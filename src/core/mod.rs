@@ -1,3 +1,4 @@
+pub mod adaptive_refresh;
 pub mod cmd_writer;
 
 pub mod config;
@@ -6,8 +7,14 @@ pub mod dumpsys;
 pub mod ebpf;
 pub mod fas;
 pub mod fps_meter;
+pub mod ingame;
+pub mod input;
+pub mod launch;
+pub mod learning;
 pub mod pid_tracker;
+pub mod prewarm;
 pub mod profile;
+pub mod psi;
 pub mod scaling;
 pub mod stats;
 pub mod system_status;
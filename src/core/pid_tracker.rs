@@ -4,12 +4,16 @@
 //!
 //!   - [`PidTracker::is_alive`] is a cheap non-blocking probe the tick loop
 //!     uses to decide whether the currently-tracked game is still up.
-//!   - A background thread blocks until the process actually exits and then
-//!     pushes a [`DaemonEvent::PidExited`] so the daemon re-evaluates
+//!   - A background thread blocks until a tracked process actually exits and
+//!     then pushes a [`DaemonEvent::PidExited`] so the daemon re-evaluates
 //!     *instantly* instead of waiting for the next adaptive tick.
 //!
+//! A tracker watches a whole process set (a game's main process plus any
+//! child render processes): it stays alive while any member is, and fires
+//! one `PidExited` per member as each goes away.
+//!
 //! On kernels with `pidfd_open` (Linux ≥ 5.3) the thread blocks in
-//! `poll()` on the pidfd — zero wakeups until the process dies. On older
+//! `poll()` on the pidfds — zero wakeups until a process dies. On older
 //! kernels it degrades to a 150 ms `/proc/<pid>` poll. Either way an
 //! `eventfd` lets `Drop` interrupt the thread the moment we stop tracking.
 
@@ -19,11 +23,16 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread::JoinHandle;
 
-pub struct PidTracker {
+/// One tracked process. `pidfd` is `None` when `pidfd_open` is unsupported.
+struct Member {
     pid: i32,
-    /// Shared with the watcher thread; also used for the non-blocking
-    /// `is_alive` probe. `None` when `pidfd_open` is unsupported.
     pidfd: Option<Arc<OwnedFd>>,
+}
+
+pub struct PidTracker {
+    /// Shared with the watcher thread; also used for the non-blocking
+    /// `is_alive` probe.
+    members: Vec<Member>,
     /// Set on `Drop` to tell the watcher thread to exit without firing.
     stop: Arc<AtomicBool>,
     /// `eventfd` used to interrupt the thread's blocking `poll`.
@@ -32,10 +41,17 @@ pub struct PidTracker {
 }
 
 impl PidTracker {
-    /// Begin tracking `pid`. Spawns a watcher thread that fires
-    /// [`DaemonEvent::PidExited`] on `event_tx` when the process exits.
-    pub fn spawn(pid: i32, event_tx: EventSender) -> Self {
-        let pidfd = Self::open_pidfd(pid).map(Arc::new);
+    /// Begin tracking every PID in `pids`. Spawns a watcher thread that
+    /// fires [`DaemonEvent::PidExited`] on `event_tx` once per member as it
+    /// exits; the tracker counts as alive until all are gone.
+    pub fn spawn_set(pids: &[i32], event_tx: EventSender) -> Self {
+        let members: Vec<Member> = pids
+            .iter()
+            .map(|&pid| Member {
+                pid,
+                pidfd: Self::open_pidfd(pid).map(Arc::new),
+            })
+            .collect();
         let stop = Arc::new(AtomicBool::new(false));
         let wakeup = Self::make_eventfd().map(Arc::new);
 
@@ -44,20 +60,20 @@ impl PidTracker {
         // tick loop still notices the exit via `is_alive`, just one tick
         // later.
         let handle = match wakeup.clone() {
-            Some(wfd) => {
+            Some(wfd) if !members.is_empty() => {
                 let stop_t = stop.clone();
-                let pidfd_t = pidfd.clone();
+                let watched: Vec<(i32, Option<Arc<OwnedFd>>)> =
+                    members.iter().map(|m| (m.pid, m.pidfd.clone())).collect();
                 std::thread::Builder::new()
                     .name("auriya-pidtrack".into())
-                    .spawn(move || track_loop(pid, pidfd_t, wfd, stop_t, event_tx))
+                    .spawn(move || track_loop(watched, wfd, stop_t, event_tx))
                     .ok()
             }
-            None => None,
+            _ => None,
         };
 
         Self {
-            pid,
-            pidfd,
+            members,
             stop,
             wakeup,
             handle,
@@ -91,10 +107,24 @@ impl PidTracker {
     }
 
     /// Non-blocking liveness probe used by the tick loop's fast path.
+    /// True while any member of the set is still running.
     pub fn is_alive(&self) -> bool {
-        match self.pidfd.as_ref() {
+        self.members.iter().any(Self::member_is_alive)
+    }
+
+    /// Members that are still running.
+    pub fn alive_pids(&self) -> Vec<i32> {
+        self.members
+            .iter()
+            .filter(|m| Self::member_is_alive(m))
+            .map(|m| m.pid)
+            .collect()
+    }
+
+    fn member_is_alive(m: &Member) -> bool {
+        match m.pidfd.as_ref() {
             Some(fd) => Self::pidfd_is_alive(fd),
-            None => Self::fallback_is_alive(self.pid),
+            None => Self::fallback_is_alive(m.pid),
         }
     }
 
@@ -140,21 +170,22 @@ fn proc_exists(pid: i32) -> bool {
     std::path::Path::new(&format!("/proc/{pid}")).exists()
 }
 
-/// Block until the tracked process exits (or `Drop` interrupts us), then
-/// push a single [`DaemonEvent::PidExited`].
+/// Block until each tracked process exits (or `Drop` interrupts us),
+/// pushing one [`DaemonEvent::PidExited`] per exit.
 fn track_loop(
-    pid: i32,
-    pidfd: Option<Arc<OwnedFd>>,
+    mut watched: Vec<(i32, Option<Arc<OwnedFd>>)>,
     wakeup: Arc<OwnedFd>,
     stop: Arc<AtomicBool>,
     event_tx: EventSender,
 ) {
-    let exited = match pidfd {
-        Some(fd) => wait_pidfd(fd.as_raw_fd(), wakeup.as_raw_fd(), &stop),
-        None => wait_proc_poll(pid, wakeup.as_raw_fd(), &stop),
-    };
-
-    if exited && !stop.load(Ordering::Acquire) {
+    while !watched.is_empty() {
+        let Some(idx) = wait_any(&watched, wakeup.as_raw_fd(), &stop) else {
+            return;
+        };
+        let (pid, _) = watched.swap_remove(idx);
+        if stop.load(Ordering::Acquire) {
+            return;
+        }
         // `try_send` (not `blocking_send`): the `Drop` that joins this
         // thread may run on the same tokio worker that drains the channel,
         // so blocking here on a full buffer could deadlock. Dropping the
@@ -164,59 +195,64 @@ fn track_loop(
     }
 }
 
-/// Returns `true` if the process exited, `false` if interrupted/stopped.
-fn wait_pidfd(pidfd: RawFd, wakeup: RawFd, stop: &AtomicBool) -> bool {
+/// Index of a member that exited, or `None` if interrupted/stopped.
+/// Members with a pidfd are waited on directly; the rest are covered by
+/// a 150 ms `/proc` poll, so a set mixing both still wakes promptly.
+fn wait_any(
+    watched: &[(i32, Option<Arc<OwnedFd>>)],
+    wakeup: RawFd,
+    stop: &AtomicBool,
+) -> Option<usize> {
+    const POLL_INTERVAL_MS: libc::c_int = 150;
+    let needs_proc_poll = watched.iter().any(|(_, fd)| fd.is_none());
+    let timeout = if needs_proc_poll {
+        POLL_INTERVAL_MS
+    } else {
+        -1
+    };
     loop {
-        let mut pfds = [
-            libc::pollfd {
-                fd: pidfd,
-                events: libc::POLLIN,
-                revents: 0,
-            },
-            libc::pollfd {
-                fd: wakeup,
-                events: libc::POLLIN,
-                revents: 0,
-            },
-        ];
-        let ret = unsafe { libc::poll(pfds.as_mut_ptr(), 2, -1) };
+        let mut pfds: Vec<libc::pollfd> = Vec::with_capacity(watched.len() + 1);
+        pfds.push(libc::pollfd {
+            fd: wakeup,
+            events: libc::POLLIN,
+            revents: 0,
+        });
+        let mut slots = Vec::with_capacity(watched.len());
+        for (i, (_, fd)) in watched.iter().enumerate() {
+            if let Some(fd) = fd {
+                pfds.push(libc::pollfd {
+                    fd: fd.as_raw_fd(),
+                    events: libc::POLLIN,
+                    revents: 0,
+                });
+                slots.push(i);
+            }
+        }
+
+        let ret = unsafe { libc::poll(pfds.as_mut_ptr(), pfds.len() as libc::nfds_t, timeout) };
         if stop.load(Ordering::Acquire) {
-            return false;
+            return None;
         }
         if ret < 0 {
             if is_eintr() {
                 continue;
             }
-            return false;
-        }
-        if (pfds[1].revents & libc::POLLIN) != 0 {
-            return false; // woken to stop
+            return None;
         }
         if (pfds[0].revents & libc::POLLIN) != 0 {
-            return true; // process exited
+            return None; // woken to stop
         }
-    }
-}
-
-/// Fallback for kernels without `pidfd_open`: poll `/proc/<pid>` every
-/// 150 ms, interruptible via the wakeup eventfd.
-fn wait_proc_poll(pid: i32, wakeup: RawFd, stop: &AtomicBool) -> bool {
-    const POLL_INTERVAL_MS: libc::c_int = 150;
-    loop {
-        let mut pfd = libc::pollfd {
-            fd: wakeup,
-            events: libc::POLLIN,
-            revents: 0,
-        };
-        let ret = unsafe { libc::poll(&mut pfd, 1, POLL_INTERVAL_MS) };
-        if stop.load(Ordering::Acquire) {
-            return false;
-        }
-        if ret > 0 && (pfd.revents & libc::POLLIN) != 0 {
-            return false; // woken to stop
+        for (slot, pfd) in slots.iter().zip(&pfds[1..]) {
+            if (pfd.revents & libc::POLLIN) != 0 {
+                return Some(*slot); // process exited
+            }
         }
-        if !proc_exists(pid) {
-            return true; // process gone
+        if needs_proc_poll
+            && let Some(i) = watched
+                .iter()
+                .position(|(pid, fd)| fd.is_none() && !proc_exists(*pid))
+        {
+            return Some(i); // process gone
         }
     }
 }
@@ -244,7 +280,7 @@ mod tests {
             .expect("spawn sleep child");
         let pid = child.id() as i32;
 
-        let tracker = PidTracker::spawn(pid, tx);
+        let tracker = PidTracker::spawn_set(&[pid], tx);
         assert!(
             tracker.is_alive(),
             "child should be alive immediately after spawn"
@@ -281,7 +317,7 @@ mod tests {
             .spawn()
             .expect("spawn sleep child");
         let pid = child.id() as i32;
-        let _tracker = PidTracker::spawn(pid, tx);
+        let _tracker = PidTracker::spawn_set(&[pid], tx);
 
         // Give the watcher thread a moment to arm its blocking poll.
         std::thread::sleep(Duration::from_millis(50));
@@ -314,12 +350,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_stays_alive_until_last_member_exits() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let mut a = Command::new("sleep").arg("30").spawn().expect("spawn a");
+        let mut b = Command::new("sleep").arg("30").spawn().expect("spawn b");
+        let (pa, pb) = (a.id() as i32, b.id() as i32);
+
+        let tracker = PidTracker::spawn_set(&[pa, pb], tx);
+        a.kill().expect("kill a");
+        let _ = a.wait();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut got = None;
+        while Instant::now() < deadline {
+            if let Ok(ev) = rx.try_recv() {
+                got = Some(ev);
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(got, Some(DaemonEvent::PidExited(pa)));
+        assert!(tracker.is_alive(), "b is still running");
+        assert_eq!(tracker.alive_pids(), vec![pb]);
+
+        b.kill().expect("kill b");
+        let _ = b.wait();
+        assert!(!tracker.is_alive());
+    }
+
     #[test]
     fn dead_pid_is_not_alive() {
         // PID 0 is never a valid tracked target; open_pidfd returns None
         // and the /proc fallback reports not-alive for a nonexistent PID.
         let (tx, _rx) = tokio::sync::mpsc::channel(1);
-        let tracker = PidTracker::spawn(999_999_999, tx);
+        let tracker = PidTracker::spawn_set(&[999_999_999], tx);
         assert!(!tracker.is_alive());
     }
 }
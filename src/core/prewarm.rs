@@ -0,0 +1,356 @@
+// Page-cache prewarming of game assets.
+//
+// A cold start reads the APK and its splits, the compiled code next to
+// them, OBB expansion files and whatever the engine unpacked under the
+// app's data directories. Asking the kernel to read those ahead
+// (`posix_fadvise(WILLNEED)`) while the game is still forking, or earlier
+// while the phone sits idle on the charger, turns much of that into
+// page-cache hits.
+//
+// Each run has a byte budget covering what isn't cached yet, and stops as
+// soon as memory PSI (`some avg10`) passes `[prewarm] max_memory_psi`:
+// evicting other apps' pages to make room would cost more than it saves.
+// Before reading ahead, each file's residency is taken with `mincore`;
+// the share already cached when a launch starts is that launch's hit
+// rate. Runs happen on a worker thread, one at a time.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::{debug, info};
+
+const APP_ROOT: &str = "/data/app";
+const DATA_ROOT: &str = "/data/data";
+const MEDIA_ROOT: &str = "/data/media/0/Android";
+/// Per-app data subdirectories that hold nothing a launch reads.
+const SKIP_DIRS: [&str; 2] = ["cache", "code_cache"];
+const MAX_DEPTH: usize = 8;
+/// Bound on files considered per game, for apps with huge data dirs.
+const MAX_FILES: usize = 20_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrewarmKind {
+    /// A cold start is under way.
+    Launch,
+    /// Ahead of time, while charging with the screen off.
+    Idle,
+}
+
+/// Prewarm history of one game, for the IPC.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PrewarmRecord {
+    pub pkg: String,
+    /// Launches prewarmed.
+    pub launches: u32,
+    /// Share of the game's assets already cached when the latest launch
+    /// started, in percent.
+    pub last_hit_pct: Option<f32>,
+    /// Mean of `last_hit_pct` over every launch.
+    pub avg_hit_pct: Option<f32>,
+    pub idle_runs: u32,
+    /// Size of the game's asset set at the latest run.
+    pub asset_bytes: u64,
+    /// Bytes read ahead by the latest run.
+    pub warmed_bytes: u64,
+    #[serde(skip)]
+    hit_sum: f32,
+}
+
+/// One game's pass: bytes looked at, already cached, and read ahead.
+#[derive(Debug, Default, PartialEq)]
+struct Pass {
+    total: u64,
+    resident: u64,
+    fetched: u64,
+    /// Memory pressure cut the pass short.
+    pressured: bool,
+}
+
+impl Pass {
+    fn hit_pct(&self) -> Option<f32> {
+        (self.total > 0).then(|| self.resident as f32 / self.total as f32 * 100.0)
+    }
+}
+
+#[derive(Default)]
+pub struct Prewarmer {
+    busy: Arc<AtomicBool>,
+    records: Arc<Mutex<BTreeMap<String, PrewarmRecord>>>,
+    last_idle: Option<Instant>,
+}
+
+impl Prewarmer {
+    /// Start prewarming `pkgs`, in order, on a worker thread with
+    /// `budget` bytes to read ahead between them. `false` when a run is
+    /// already going or memory is already under pressure.
+    pub fn start(&self, kind: PrewarmKind, pkgs: Vec<String>, budget: u64, max_psi: f32) -> bool {
+        if pkgs.is_empty() || under_pressure(max_psi) {
+            return false;
+        }
+        if self.busy.swap(true, Ordering::AcqRel) {
+            debug!(target: "auriya::prewarm", "Prewarm already running, skipping {:?}", kind);
+            return false;
+        }
+        let busy = Arc::clone(&self.busy);
+        let records = Arc::clone(&self.records);
+        let spawned = std::thread::Builder::new()
+            .name("auriya-prewarm".into())
+            .spawn(move || {
+                let mut remaining = budget;
+                for pkg in pkgs {
+                    let pass = warm(&asset_files(&pkg), &mut remaining, max_psi);
+                    info!(
+                        target: "auriya::prewarm",
+                        "Prewarmed {} ({:?}): {} MiB read ahead, {:.0}% of {} MiB already cached{}",
+                        pkg,
+                        kind,
+                        pass.fetched >> 20,
+                        pass.hit_pct().unwrap_or(0.0),
+                        pass.total >> 20,
+                        if pass.pressured { ", stopped on memory pressure" } else { "" }
+                    );
+                    if let Ok(mut r) = records.lock() {
+                        record(r.entry(pkg.clone()).or_default(), &pkg, kind, &pass);
+                    }
+                    if pass.pressured || remaining == 0 {
+                        break;
+                    }
+                }
+                busy.store(false, Ordering::Release);
+            });
+        if spawned.is_err() {
+            self.busy.store(false, Ordering::Release);
+            return false;
+        }
+        true
+    }
+
+    /// Whether an idle run is allowed again, `interval` after the last.
+    pub fn idle_due(&self, interval: Duration) -> bool {
+        self.last_idle.is_none_or(|t| t.elapsed() >= interval)
+    }
+
+    pub fn mark_idle_run(&mut self) {
+        self.last_idle = Some(Instant::now());
+    }
+
+    pub fn records(&self) -> Vec<PrewarmRecord> {
+        self.records
+            .lock()
+            .map(|r| r.values().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+fn record(rec: &mut PrewarmRecord, pkg: &str, kind: PrewarmKind, pass: &Pass) {
+    rec.pkg = pkg.to_string();
+    rec.asset_bytes = pass.total;
+    rec.warmed_bytes = pass.fetched;
+    match kind {
+        PrewarmKind::Idle => rec.idle_runs += 1,
+        PrewarmKind::Launch => {
+            let Some(hit) = pass.hit_pct() else {
+                return;
+            };
+            rec.launches += 1;
+            rec.hit_sum += hit;
+            rec.last_hit_pct = Some(hit);
+            rec.avg_hit_pct = Some(rec.hit_sum / rec.launches as f32);
+        }
+    }
+}
+
+fn under_pressure(max_psi: f32) -> bool {
+    crate::core::psi::memory_some_avg10().is_some_and(|p| p > max_psi)
+}
+
+/// Measure and read ahead `files` until `remaining` runs out or memory
+/// pressure rises; residency is still measured past the budget so the
+/// hit rate covers the whole asset set.
+fn warm(files: &[PathBuf], remaining: &mut u64, max_psi: f32) -> Pass {
+    let mut pass = Pass::default();
+    for path in files {
+        let Ok(file) = File::open(path) else {
+            continue;
+        };
+        let Ok(len) = file.metadata().map(|m| m.len()) else {
+            continue;
+        };
+        let resident = resident_bytes(&file, len).unwrap_or(0);
+        pass.total += len;
+        pass.resident += resident;
+        let missing = len - resident;
+        if missing == 0 || *remaining == 0 || pass.pressured {
+            continue;
+        }
+        if under_pressure(max_psi) {
+            pass.pressured = true;
+            continue;
+        }
+        let advise = if missing <= *remaining {
+            len
+        } else {
+            resident + *remaining
+        };
+        if will_need(&file, advise) {
+            let fetched = missing.min(*remaining);
+            pass.fetched += fetched;
+            *remaining -= fetched;
+        }
+    }
+    pass
+}
+
+/// Bytes of `file` in the page cache.
+fn resident_bytes(file: &File, len: u64) -> Option<u64> {
+    if len == 0 {
+        return Some(0);
+    }
+    let size = usize::try_from(len).ok()?;
+    // SAFETY: sysconf has no preconditions.
+    let page = usize::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).ok()?;
+    // SAFETY: a fresh read-only shared mapping of an open file; it's only
+    // handed to mincore and unmapped before returning.
+    let addr = unsafe {
+        libc::mmap(
+            std::ptr::null_mut(),
+            size,
+            libc::PROT_READ,
+            libc::MAP_SHARED,
+            file.as_raw_fd(),
+            0,
+        )
+    };
+    if addr == libc::MAP_FAILED {
+        return None;
+    }
+    let mut vec = vec![0u8; size.div_ceil(page)];
+    // SAFETY: `vec` holds one byte per page of the mapping.
+    let r = unsafe { libc::mincore(addr, size, vec.as_mut_ptr().cast()) };
+    // SAFETY: unmaps exactly the mapping made above.
+    unsafe { libc::munmap(addr, size) };
+    if r != 0 {
+        return None;
+    }
+    let pages = vec.iter().filter(|&&b| b & 1 == 1).count() as u64;
+    Some((pages * page as u64).min(len))
+}
+
+fn will_need(file: &File, len: u64) -> bool {
+    let Ok(len) = libc::off_t::try_from(len) else {
+        return false;
+    };
+    // SAFETY: advisory only; the fd is open for the duration of the call.
+    unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, len, libc::POSIX_FADV_WILLNEED) == 0 }
+}
+
+/// Everything a launch of `pkg` is likely to read, most important first:
+/// the install dir (APKs, splits, oat, native libs), OBBs, then the
+/// internal and external data dirs.
+fn asset_files(pkg: &str) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    if let Some(dir) = install_dir(Path::new(APP_ROOT), pkg) {
+        collect(&dir, 0, &mut files);
+    }
+    for dir in [
+        PathBuf::from(MEDIA_ROOT).join("obb").join(pkg),
+        PathBuf::from(DATA_ROOT).join(pkg),
+        PathBuf::from(MEDIA_ROOT)
+            .join("data")
+            .join(pkg)
+            .join("files"),
+    ] {
+        collect(&dir, 0, &mut files);
+    }
+    files
+}
+
+/// `<root>/<pkg>-<suffix>`, or since Android 11
+/// `<root>/~~<random>/<pkg>-<suffix>`.
+fn install_dir(root: &Path, pkg: &str) -> Option<PathBuf> {
+    let prefix = format!("{pkg}-");
+    for entry in fs::read_dir(root).ok()?.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with(&prefix) {
+            return Some(entry.path());
+        }
+        if name.starts_with("~~")
+            && let Ok(inner) = fs::read_dir(entry.path())
+            && let Some(dir) = inner
+                .flatten()
+                .find(|e| e.file_name().to_string_lossy().starts_with(&prefix))
+        {
+            return Some(dir.path());
+        }
+    }
+    None
+}
+
+fn collect(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if out.len() >= MAX_FILES {
+            return;
+        }
+        let Ok(ft) = entry.file_type() else {
+            continue;
+        };
+        if ft.is_file() {
+            out.push(entry.path());
+        } else if ft.is_dir() && !(depth == 0 && SKIP_DIRS.iter().any(|s| entry.file_name() == *s))
+        {
+            collect(&entry.path(), depth + 1, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_install_dir_in_both_layouts() {
+        let root = std::env::temp_dir().join(format!("auriya-prewarm-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("com.old-1")).unwrap();
+        fs::create_dir_all(root.join("~~abc==/com.game-xyz==")).unwrap();
+        fs::create_dir_all(root.join("~~def==/com.gamer-q==")).unwrap();
+        assert_eq!(install_dir(&root, "com.old"), Some(root.join("com.old-1")));
+        assert_eq!(
+            install_dir(&root, "com.game"),
+            Some(root.join("~~abc==/com.game-xyz=="))
+        );
+        assert_eq!(install_dir(&root, "com.none"), None);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn warms_within_budget_and_measures_everything() {
+        let root = std::env::temp_dir().join(format!("auriya-warm-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("cache")).unwrap();
+        fs::write(root.join("a.bin"), vec![1u8; 8192]).unwrap();
+        fs::write(root.join("cache/skip.bin"), vec![1u8; 8192]).unwrap();
+        let mut files = Vec::new();
+        collect(&root, 0, &mut files);
+        assert_eq!(files, [root.join("a.bin")]);
+
+        let mut remaining = 0;
+        let pass = warm(&files, &mut remaining, f32::MAX);
+        assert_eq!(pass.total, 8192);
+        assert_eq!(pass.fetched, 0);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::core::cmd_writer::{self, DndFilter};
 use crate::core::tweaks::{
     cpu, gpu, init, memory, paths, sched, storage, touchpanel,
-    vendor::{detect as soc, mtk, snapdragon},
+    vendor::{detect as soc, exynos, mtk, snapdragon, tensor, unisoc},
 };
 use anyhow::Result;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -20,12 +20,24 @@ pub fn set_companion_alive(alive: bool) {
 }
 
 #[inline]
-fn warn_on_err<E: std::fmt::Display>(result: Result<(), E>, context: &str) {
+pub(crate) fn warn_on_err<E: std::fmt::Display>(result: Result<(), E>, context: &str) {
     if let Err(e) = result {
         tracing::warn!(target: "auriya::profile", "Failed to {}: {}", context, e);
     }
 }
 
+/// Prioritise every PID of the game. Child processes come and go, so one
+/// that has already exited is logged and skipped. CPU placement is per
+/// thread and belongs to `ThreadPlacer`.
+fn prioritize_game_pids(pids: &[i32]) {
+    for &game_pid in pids {
+        warn_on_err(
+            cpu::set_process_priority(game_pid),
+            &format!("set priority for pid {game_pid}"),
+        );
+    }
+}
+
 /// Request the companion service to apply the given DnD filter. The
 /// daemon cannot call NotificationManager itself — it would need to be
 /// an app rather than a root binary — so we hand the work off through
@@ -99,17 +111,13 @@ impl std::str::FromStr for ProfileMode {
     }
 }
 
-pub fn apply_performance_with_config(
-    governor: &str,
-    enable_dnd: bool,
-    pid: Option<i32>,
-) -> Result<()> {
+pub fn apply_performance_with_config(governor: &str, enable_dnd: bool, pids: &[i32]) -> Result<()> {
     debug!(
         target: "auriya::profile",
-        "Applying PERFORMANCE profile (governor: {}, suppress_notifs: {}, pid: {:?})",
+        "Applying PERFORMANCE profile (governor: {}, suppress_notifs: {}, pids: {:?})",
         governor,
         enable_dnd,
-        pid
+        pids
     );
 
     paths::set_governor_cached(governor);
@@ -125,7 +133,18 @@ pub fn apply_performance_with_config(
         soc::SocType::Snapdragon => {
             let _ = snapdragon::apply_performance();
         }
-        _ => tracing::debug!(target: "auriya::profile", "No vendor tweaks for: {}", soc_type),
+        soc::SocType::Tensor => {
+            let _ = tensor::apply_performance();
+        }
+        soc::SocType::Exynos => {
+            let _ = exynos::apply_performance();
+        }
+        soc::SocType::Unisoc => {
+            let _ = unisoc::apply_performance();
+        }
+        soc::SocType::Unknown => {
+            tracing::debug!(target: "auriya::profile", "No vendor tweaks for: {}", soc_type)
+        }
     }
 
     gpu::set_performance_mode()?;
@@ -137,13 +156,9 @@ pub fn apply_performance_with_config(
     warn_on_err(init::apply_general_tweaks(), "apply general tweaks");
     warn_on_err(sched::apply_performance_sched(), "apply scheduler tweaks");
     warn_on_err(storage::lock_storage_freq(), "lock storage freq");
-    warn_on_err(memory::drop_caches(), "drop caches");
     warn_on_err(memory::adjust_for_gaming(), "apply gaming memory settings");
 
-    if let Some(game_pid) = pid {
-        cpu::set_game_affinity_dynamic(game_pid, "performance")?;
-        cpu::set_process_priority(game_pid)?;
-    }
+    prioritize_game_pids(pids);
 
     // DnD is handled by the daemon lifecycle, not here — see request_dnd.
     let _ = enable_dnd;
@@ -152,12 +167,12 @@ pub fn apply_performance_with_config(
 }
 
 pub fn apply_performance() -> Result<()> {
-    apply_performance_with_config("performance", true, None)
+    apply_performance_with_config("performance", true, &[])
 }
 
 pub fn apply_fast() -> Result<()> {
     debug!(target: "auriya::profile", "Applying FAST profile (zero margin boost)");
-    apply_performance_with_config("performance", true, None)
+    apply_performance_with_config("performance", true, &[])
 }
 
 pub fn apply_balance(governor: &str) -> Result<()> {
@@ -183,7 +198,16 @@ pub fn apply_balance_with_dnd(governor: &str, enable_dnd: bool) -> Result<()> {
         soc::SocType::Snapdragon => {
             let _ = snapdragon::apply_normal();
         }
-        _ => {}
+        soc::SocType::Tensor => {
+            let _ = tensor::apply_normal();
+        }
+        soc::SocType::Exynos => {
+            let _ = exynos::apply_normal();
+        }
+        soc::SocType::Unisoc => {
+            let _ = unisoc::apply_normal();
+        }
+        soc::SocType::Unknown => {}
     }
     gpu::set_balanced_mode()?;
     warn_on_err(
@@ -203,37 +227,53 @@ pub fn apply_balance_with_dnd(governor: &str, enable_dnd: bool) -> Result<()> {
     Ok(())
 }
 
+/// Raise the GPU floor one level (GPU only, CPU untouched); repeated
+/// boosts climb towards the top of the frequency table.
 pub fn apply_gpu_boost() -> Result<()> {
-    debug!(target: "auriya::profile", "Applying GPU BOOST (GPU only, CPU untouched)");
-    gpu::set_performance_mode()?;
+    let level = gpu::step_up();
+    debug!(
+        target: "auriya::profile",
+        "Applying GPU BOOST (floor level {}/{})",
+        level,
+        gpu::GPU_LEVELS
+    );
     Ok(())
 }
 
-pub fn apply_cpu_boost(governor: &str, pid: Option<i32>) -> Result<()> {
+/// Drop the GPU floor one level; returns the level left. A reduce after
+/// a run of GPU boosts walks back down rather than falling off the top.
+pub fn reduce_gpu_boost() -> usize {
+    let level = gpu::step_down();
     debug!(
         target: "auriya::profile",
-        "Applying CPU BOOST (governor: {}, pid: {:?})",
+        "Reducing GPU BOOST (floor level {}/{})",
+        level,
+        gpu::GPU_LEVELS
+    );
+    level
+}
+
+pub fn apply_cpu_boost(governor: &str, pids: &[i32]) -> Result<()> {
+    debug!(
+        target: "auriya::profile",
+        "Applying CPU BOOST (governor: {}, pids: {:?})",
         governor,
-        pid
+        pids
     );
 
     paths::set_governor_cached(governor);
     cpu::enable_boost()?;
     paths::online_all_cores_cached();
     warn_on_err(sched::apply_performance_sched(), "apply scheduler tweaks");
-    gpu::set_balanced_mode()?;
 
-    if let Some(game_pid) = pid {
-        cpu::set_game_affinity_dynamic(game_pid, "performance")?;
-        cpu::set_process_priority(game_pid)?;
-    }
+    prioritize_game_pids(pids);
 
     Ok(())
 }
 
 pub fn apply_ceiling(
     controller: &mut crate::core::tweaks::ceiling::CeilingController,
-    level: crate::core::tweaks::ceiling::CeilingLevel,
+    level: &crate::core::tweaks::ceiling::CeilingLevel,
     config: &crate::core::tweaks::ceiling::CeilingConfig,
 ) {
     if let Err(e) = controller.apply(level, config) {
@@ -253,7 +293,6 @@ pub fn apply_powersave_with_dnd(enable_dnd: bool) -> Result<()> {
     );
 
     paths::set_governor_cached("powersave");
-    warn_on_err(memory::set_swappiness(60), "set powersave swappiness");
 
     // DnD is owned by the daemon lifecycle (Daemon::sync_dnd).
     let _ = enable_dnd;
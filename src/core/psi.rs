@@ -0,0 +1,240 @@
+// Pressure stall information from `/proc/pressure`.
+//
+// Each resource file has a `some` line (at least one task stalled) and,
+// for memory and io, a `full` line (every non-idle task stalled):
+//
+//   some avg10=1.23 avg60=0.80 avg300=0.12 total=123456
+//   full avg10=0.00 avg60=0.00 avg300=0.00 total=4567
+//
+// The kernel's `avg10` trails by seconds, so [`PsiMonitor`] turns the
+// cumulative `total` (µs stalled) into a share of the time since the last
+// sample; `avg10` only stands in for the first one. Kernels without
+// CONFIG_PSI (or with `psi=0`) lack the files; every reader returns `None`
+// there and callers treat that as "no pressure".
+//
+// Triggers (see [`Trigger`]) make the kernel wake a `poll()` when a stall
+// threshold is crossed within a window, so reacting to a pressure spike
+// costs nothing while there is none.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::os::fd::AsRawFd;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::time::Instant;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resource {
+    Cpu,
+    Memory,
+    Io,
+}
+
+impl Resource {
+    fn path(self) -> &'static str {
+        match self {
+            Self::Cpu => "/proc/pressure/cpu",
+            Self::Memory => "/proc/pressure/memory",
+            Self::Io => "/proc/pressure/io",
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Memory => "memory",
+            Self::Io => "io",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PsiLine {
+    /// Percent of the last 10 s stalled.
+    pub avg10: f32,
+    /// Cumulative stall time in µs.
+    pub total_us: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Pressure {
+    pub some: PsiLine,
+    pub full: Option<PsiLine>,
+}
+
+pub fn read(resource: Resource) -> Option<Pressure> {
+    parse(&fs::read_to_string(resource.path()).ok()?)
+}
+
+/// Read a pressure file at `path`, e.g. a cgroup's `cpu.pressure`.
+pub fn read_path(path: &Path) -> Option<Pressure> {
+    parse(&fs::read_to_string(path).ok()?)
+}
+
+/// `some avg10` of memory pressure, in percent.
+pub fn memory_some_avg10() -> Option<f32> {
+    read(Resource::Memory).map(|p| p.some.avg10)
+}
+
+fn parse(content: &str) -> Option<Pressure> {
+    let line = |kind: &str| -> Option<PsiLine> {
+        let l = content
+            .lines()
+            .find(|l| l.split_whitespace().next() == Some(kind))?;
+        let field = |name: &str| {
+            l.split_whitespace()
+                .find_map(|f| f.strip_prefix(name)?.strip_prefix('='))
+        };
+        Some(PsiLine {
+            avg10: field("avg10")?.parse().ok()?,
+            total_us: field("total")?.parse().ok()?,
+        })
+    };
+    Some(Pressure {
+        some: line("some")?,
+        full: line("full"),
+    })
+}
+
+/// Stall shares of one resource, in percent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct PsiLevels {
+    pub some: f32,
+    /// Absent for `cpu` on most kernels.
+    pub full: Option<f32>,
+}
+
+/// Latest pressure per resource; `None` where the kernel has no PSI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct PsiSnapshot {
+    pub cpu: Option<PsiLevels>,
+    pub memory: Option<PsiLevels>,
+    pub io: Option<PsiLevels>,
+}
+
+impl PsiSnapshot {
+    pub fn get(&self, resource: Resource) -> Option<PsiLevels> {
+        match resource {
+            Resource::Cpu => self.cpu,
+            Resource::Memory => self.memory,
+            Resource::Io => self.io,
+        }
+    }
+}
+
+/// Turns cumulative stall counters into the share of time stalled since
+/// the previous sample.
+#[derive(Debug, Default)]
+pub struct StallRate {
+    last: Option<(Instant, Pressure)>,
+}
+
+impl StallRate {
+    /// Share stalled since the last call, or the kernel's `avg10` on the
+    /// first.
+    pub fn update(&mut self, now: Instant, p: Pressure) -> PsiLevels {
+        let levels = match self.last {
+            Some((at, prev)) if now > at => {
+                let wall_us = now.duration_since(at).as_micros() as f64;
+                let share = |cur: PsiLine, prev: PsiLine| {
+                    (cur.total_us.saturating_sub(prev.total_us) as f64 / wall_us * 100.0).min(100.0)
+                        as f32
+                };
+                PsiLevels {
+                    some: share(p.some, prev.some),
+                    full: p.full.zip(prev.full).map(|(c, o)| share(c, o)),
+                }
+            }
+            _ => PsiLevels {
+                some: p.some.avg10,
+                full: p.full.map(|f| f.avg10),
+            },
+        };
+        self.last = Some((now, p));
+        levels
+    }
+}
+
+/// System-wide PSI sampled once per tick.
+#[derive(Debug, Default)]
+pub struct PsiMonitor {
+    cpu: StallRate,
+    memory: StallRate,
+    io: StallRate,
+    latest: PsiSnapshot,
+}
+
+impl PsiMonitor {
+    pub fn sample(&mut self) -> PsiSnapshot {
+        let now = Instant::now();
+        let one = |rate: &mut StallRate, r: Resource| read(r).map(|p| rate.update(now, p));
+        self.latest = PsiSnapshot {
+            cpu: one(&mut self.cpu, Resource::Cpu),
+            memory: one(&mut self.memory, Resource::Memory),
+            io: one(&mut self.io, Resource::Io),
+        };
+        self.latest
+    }
+
+    pub fn latest(&self) -> PsiSnapshot {
+        self.latest
+    }
+}
+
+/// A registered PSI trigger: the fd polls `POLLPRI` once `stall_us` of
+/// `some` stall accumulate within `window_us`. Dropping it unregisters.
+pub struct Trigger {
+    pub resource: Resource,
+    file: File,
+}
+
+impl Trigger {
+    pub fn register(resource: Resource, stall_us: u64, window_us: u64) -> std::io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(resource.path())?;
+        file.write_all(format!("some {stall_us} {window_us}\0").as_bytes())?;
+        Ok(Self { resource, file })
+    }
+
+    pub fn pollfd(&self) -> libc::pollfd {
+        libc::pollfd {
+            fd: self.file.as_raw_fd(),
+            events: libc::POLLPRI,
+            revents: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_lines_and_rates() {
+        let psi = "some avg10=12.50 avg60=3.10 avg300=0.90 total=1000000\n\
+                   full avg10=4.00 avg60=1.00 avg300=0.20 total=400000\n";
+        let p = parse(psi).unwrap();
+        assert_eq!(p.some.avg10, 12.5);
+        assert_eq!(p.full.map(|f| f.total_us), Some(400_000));
+        assert_eq!(parse("some avg10=1.00 total=5\n").unwrap().full, None);
+        assert_eq!(parse("some total=1\n"), None);
+
+        let mut rate = StallRate::default();
+        let t0 = Instant::now();
+        // First sample: avg10 stands in.
+        assert_eq!(rate.update(t0, p).some, 12.5);
+        // 250 ms stalled over the next 500 ms.
+        let mut next = p;
+        next.some.total_us += 250_000;
+        next.full.as_mut().unwrap().total_us += 50_000;
+        let levels = rate.update(t0 + Duration::from_millis(500), next);
+        assert_eq!(levels.some, 50.0);
+        assert_eq!(levels.full, Some(10.0));
+    }
+}
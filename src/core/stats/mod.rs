@@ -12,7 +12,9 @@
 // This module is in `core` and must not depend on `daemon` — the builder takes
 // core snapshot types, and the IPC handler feeds them in from `CurrentState`.
 
+use crate::core::launch::LaunchSummary;
 use crate::core::profile::ProfileMode;
+use crate::core::psi::PsiSnapshot;
 use crate::core::telemetry::battery::BatterySnapshot;
 use crate::core::telemetry::cpu::{ClusterType, CpuSnapshot};
 use crate::core::telemetry::gpu::GpuSnapshot;
@@ -161,6 +163,9 @@ pub struct StatsSnapshot {
     pub cpu: Option<CpuCard>,
     pub gpu: Option<GpuCard>,
     pub session: SessionCard,
+    pub launch: Option<LaunchSummary>,
+    /// Stall shares per resource; `None` on kernels without PSI.
+    pub psi: Option<PsiSnapshot>,
 }
 
 fn cluster_str(c: &ClusterType) -> &'static str {
@@ -185,6 +190,8 @@ impl StatsSnapshot {
         pkg: Option<&str>,
         profile: ProfileMode,
         active: bool,
+        launch: Option<&LaunchSummary>,
+        psi: Option<PsiSnapshot>,
     ) -> Self {
         StatsSnapshot {
             fps,
@@ -224,6 +231,8 @@ impl StatsSnapshot {
                 profile: profile.to_string().to_lowercase(),
                 active,
             },
+            launch: launch.cloned(),
+            psi,
         }
     }
 }
@@ -19,6 +19,12 @@ pub mod watcher;
 
 pub use watcher::SystemStatusCache;
 
+/// `Settings.Global.ZEN_MODE_*` as reported in `zen_mode`. Anything
+/// above these (no interruptions, alarms only) is stricter than the
+/// priority filter a game session asks for.
+pub const ZEN_MODE_OFF: u8 = 0;
+pub const ZEN_MODE_IMPORTANT_INTERRUPTIONS: u8 = 1;
+
 /// Default location of the status file written by the companion service.
 pub const STATUS_FILE: &str = "/data/adb/.config/auriya/system_status";
 
@@ -65,6 +65,22 @@ impl SystemStatusCache {
             .and_then(|g| g.as_ref().and_then(|s| s.focused_pid))
     }
 
+    /// Convenience: return the focused app's UID from the latest snapshot.
+    pub fn focused_uid(&self) -> Option<i32> {
+        self.inner
+            .read()
+            .ok()
+            .and_then(|g| g.as_ref().and_then(|s| s.focused_uid))
+    }
+
+    /// Convenience: the user's current `zen_mode`, if reported.
+    pub fn zen_mode(&self) -> Option<u8> {
+        self.inner
+            .read()
+            .ok()
+            .and_then(|g| g.as_ref().and_then(|s| s.zen_mode))
+    }
+
     /// Convenience: derive the (`screen_awake`, `battery_saver`) pair
     /// the tick loop needs. Defaults to `(true, false)` when the
     /// respective field is unset — the daemon falls back to "best case"
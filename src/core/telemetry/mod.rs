@@ -26,7 +26,7 @@ impl TelemetryHub {
         Self {
             cpu_collector: cpu::CpuCollector::default(),
             gpu_collector,
-            thermal_collector: thermal::ThermalCollector,
+            thermal_collector: thermal::ThermalCollector::default(),
             has_gpu,
         }
     }
@@ -1,5 +1,374 @@
-use anyhow::Result;
+// Thermal zone discovery and role mapping.
+//
+// Every `/sys/class/thermal/thermal_zone*` is discovered together with its
+// `type`, and `[thermal]` in settings.toml decides which zones feed which
+// decision:
+//
+//   cpu / gpu  — HUD readings (`ThermalCollector`)
+//   fas        — FAS `Reduce` above the active mode's `thermal_threshold`
+//   ceiling    — forces the Low ceiling above `ceiling_threshold` (opt-in)
+//   safety     — forces FAS `Reduce` above `safety_threshold`
+//
+// Each role is a list of selectors matched case-insensitively against the
+// zone type: an exact name (`cpu-1-0`), a glob (`cpuss-*`, `gpu?`), or the
+// token `virtual_skin`, which expands to the weighted average configured in
+// `[thermal.virtual_skin]`. A role reads the hottest of everything it selects.
+// When the `fas` selectors match no zone at all (vendor type names vary
+// wildly), FAS falls back to the hottest zone that isn't a PMIC or battery
+// sensor rather than reading nothing.
+//
+// The mapping is process-wide (installed by the daemon from settings and on
+// reload) so FAS, the ceiling logic and the HUD always agree on what "hot"
+// means. They also share one [`sweep`] per tick, which reads only the zones
+// some role selects.
+
 use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+
+use crate::core::config::settings::ThermalConfig;
+
+const THERMAL_ROOT: &str = "/sys/class/thermal";
+/// How long a sweep is reused; shorter than the FAS poll, so FAS, the
+/// ceiling check and the HUD share one read per tick.
+const SWEEP_TTL: Duration = Duration::from_millis(250);
+/// Zone types that aren't die temperatures: PMIC, battery, charger and
+/// current-limit sensors, which run hot without the SoC being hot.
+const NOT_DIE: &[&str] = &["pm*", "*pmic*", "*bat*", "*bcl*", "*charg*", "*usb*"];
+
+/// Selector token that expands to the weighted virtual-skin composite.
+pub const VIRTUAL_SKIN: &str = "virtual_skin";
+
+/// A discovered thermal zone and (optionally) its live reading.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThermalZone {
+    pub index: u32,
+    pub kind: String,
+    pub temp_c: Option<f32>,
+}
+
+/// Resolved role → selector mapping. Built from `[thermal]`.
+#[derive(Debug, Clone)]
+pub struct ThermalMap {
+    pub cpu: Vec<String>,
+    pub gpu: Vec<String>,
+    pub fas: Vec<String>,
+    pub ceiling: Vec<String>,
+    pub safety: Vec<String>,
+    /// `(selector, weight)` pairs for the virtual-skin composite.
+    pub virtual_skin: Vec<(String, f32)>,
+    pub safety_threshold: f32,
+    pub ceiling_threshold: Option<f32>,
+}
+
+impl Default for ThermalMap {
+    fn default() -> Self {
+        Self::from_config(&ThermalConfig::default())
+    }
+}
+
+impl ThermalMap {
+    pub fn from_config(cfg: &ThermalConfig) -> Self {
+        let lower = |v: &[String]| v.iter().map(|s| s.to_lowercase()).collect::<Vec<_>>();
+        let mut virtual_skin: Vec<(String, f32)> = cfg
+            .virtual_skin
+            .iter()
+            .filter(|(_, w)| **w > 0.0)
+            .map(|(k, w)| (k.to_lowercase(), *w as f32))
+            .collect();
+        // HashMap order is random; keep the composite deterministic.
+        virtual_skin.sort_by(|a, b| a.0.cmp(&b.0));
+        Self {
+            cpu: lower(&cfg.cpu),
+            gpu: lower(&cfg.gpu),
+            fas: lower(&cfg.fas),
+            ceiling: lower(&cfg.ceiling),
+            safety: lower(&cfg.safety),
+            virtual_skin,
+            safety_threshold: cfg.safety_threshold as f32,
+            ceiling_threshold: cfg.ceiling_threshold.map(|t| t as f32),
+        }
+    }
+
+    /// Roles (`cpu`, `fas`, ...) a zone type participates in. Used by
+    /// `THERMAL_ZONES` so users can see what their selectors picked up.
+    pub fn roles_for(&self, kind: &str) -> Vec<&'static str> {
+        let kind = kind.to_lowercase();
+        let skin = self.virtual_skin.iter().any(|(p, _)| glob_match(p, &kind));
+        let hit = |sel: &[String]| {
+            sel.iter().any(|p| {
+                if p == VIRTUAL_SKIN {
+                    skin
+                } else {
+                    glob_match(p, &kind)
+                }
+            })
+        };
+        let mut roles = Vec::new();
+        for (name, sel) in [
+            ("cpu", &self.cpu),
+            ("gpu", &self.gpu),
+            ("fas", &self.fas),
+            ("ceiling", &self.ceiling),
+            ("safety", &self.safety),
+        ] {
+            if hit(sel) {
+                roles.push(name);
+            }
+        }
+        if skin {
+            roles.push("skin");
+        }
+        roles
+    }
+
+    /// Whether any role reads zones of type `kind`.
+    fn selects(&self, kind: &str) -> bool {
+        !self.roles_for(kind).is_empty()
+    }
+
+    /// Whether `selectors` match the type of any of `zones`.
+    fn matches_any(&self, zones: &[ThermalZone], selectors: &[String]) -> bool {
+        zones.iter().any(|z| {
+            let kind = z.kind.to_lowercase();
+            selectors.iter().any(|p| {
+                if p == VIRTUAL_SKIN {
+                    self.virtual_skin.iter().any(|(v, _)| glob_match(v, &kind))
+                } else {
+                    glob_match(p, &kind)
+                }
+            })
+        })
+    }
+
+    /// The `fas` reading; the hottest die zone when the selectors match
+    /// nothing on this device.
+    fn fas(&self, zones: &[ThermalZone]) -> Option<f32> {
+        if self.matches_any(zones, &self.fas) {
+            return self.resolve(zones, &self.fas);
+        }
+        static WARNED: AtomicBool = AtomicBool::new(false);
+        if !zones.is_empty() && !WARNED.swap(true, Ordering::Relaxed) {
+            warn!(
+                target: "auriya::thermal",
+                "No thermal zone matches [thermal] fas {:?}; using the hottest non-PMIC zone",
+                self.fas
+            );
+        }
+        hottest_die(zones)
+    }
+
+    /// Hottest reading among the zones `selectors` pick out.
+    pub fn resolve(&self, zones: &[ThermalZone], selectors: &[String]) -> Option<f32> {
+        let mut max: Option<f32> = None;
+        for sel in selectors {
+            let v = if sel == VIRTUAL_SKIN {
+                self.virtual_skin(zones)
+            } else {
+                hottest(zones, sel)
+            };
+            if let Some(v) = v {
+                max = Some(max.map_or(v, |m| m.max(v)));
+            }
+        }
+        max
+    }
+
+    /// Weighted average of the virtual-skin inputs that currently have a
+    /// reading. Missing sensors drop out instead of dragging the average
+    /// toward zero.
+    pub fn virtual_skin(&self, zones: &[ThermalZone]) -> Option<f32> {
+        let (mut sum, mut weight) = (0.0f32, 0.0f32);
+        for (sel, w) in &self.virtual_skin {
+            if let Some(t) = hottest(zones, sel) {
+                sum += t * w;
+                weight += w;
+            }
+        }
+        (weight > 0.0).then(|| sum / weight)
+    }
+
+    pub fn readings(&self, zones: &[ThermalZone]) -> ThermalReadings {
+        ThermalReadings {
+            cpu: self.resolve(zones, &self.cpu),
+            gpu: self.resolve(zones, &self.gpu),
+            fas: self.fas(zones),
+            ceiling: self.resolve(zones, &self.ceiling),
+            safety: self.resolve(zones, &self.safety),
+            skin: self.virtual_skin(zones),
+        }
+    }
+}
+
+/// Per-role temperatures (°C) for one sweep over the zones.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ThermalReadings {
+    pub cpu: Option<f32>,
+    pub gpu: Option<f32>,
+    pub fas: Option<f32>,
+    pub ceiling: Option<f32>,
+    pub safety: Option<f32>,
+    pub skin: Option<f32>,
+}
+
+fn hottest(zones: &[ThermalZone], pattern: &str) -> Option<f32> {
+    zones
+        .iter()
+        .filter(|z| glob_match(pattern, &z.kind.to_lowercase()))
+        .filter_map(|z| z.temp_c)
+        .reduce(f32::max)
+}
+
+fn is_die(kind: &str) -> bool {
+    let kind = kind.to_lowercase();
+    !NOT_DIE.iter().any(|p| glob_match(p, &kind))
+}
+
+fn hottest_die(zones: &[ThermalZone]) -> Option<f32> {
+    zones
+        .iter()
+        .filter(|z| is_die(&z.kind))
+        .filter_map(|z| z.temp_c)
+        .reduce(f32::max)
+}
+
+/// Minimal glob: `*` matches any run, `?` any single char. Callers
+/// lowercase both sides.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let p = pattern.as_bytes();
+    let n = name.as_bytes();
+    let (mut pi, mut ni) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == b'?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == b'*' {
+            star = Some((pi, ni));
+            pi += 1;
+        } else if let Some((sp, sn)) = star {
+            pi = sp + 1;
+            ni = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == b'*')
+}
+
+fn map_lock() -> &'static RwLock<ThermalMap> {
+    static MAP: OnceLock<RwLock<ThermalMap>> = OnceLock::new();
+    MAP.get_or_init(|| RwLock::new(ThermalMap::default()))
+}
+
+/// Install the mapping from settings. Called at daemon start and on reload.
+pub fn set_map(map: ThermalMap) {
+    if let Ok(mut g) = map_lock().write() {
+        *g = map;
+    }
+    if let Ok(mut s) = sweep_cache().lock() {
+        *s = None;
+    }
+}
+
+pub fn current_map() -> ThermalMap {
+    map_lock().read().map(|g| g.clone()).unwrap_or_default()
+}
+
+/// Zone index/type pairs. Types are fixed after boot, so this is scanned once.
+fn zone_table() -> &'static [(u32, String)] {
+    static ZONES: OnceLock<Vec<(u32, String)>> = OnceLock::new();
+    ZONES.get_or_init(|| scan_zone_table(Path::new(THERMAL_ROOT)))
+}
+
+fn scan_zone_table(root: &Path) -> Vec<(u32, String)> {
+    let mut out = Vec::new();
+    let Ok(entries) = fs::read_dir(root) else {
+        return out;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(index) = name
+            .to_str()
+            .and_then(|n| n.strip_prefix("thermal_zone"))
+            .and_then(|n| n.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let kind = fs::read_to_string(entry.path().join("type"))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default();
+        out.push((index, kind));
+    }
+    out.sort_by_key(|(i, _)| *i);
+    out
+}
+
+fn read_zone_temp(root: &Path, index: u32) -> Option<f32> {
+    let raw = fs::read_to_string(root.join(format!("thermal_zone{index}/temp"))).ok()?;
+    let celsius = raw.trim().parse::<i32>().ok()? as f32 / 1000.0;
+    (celsius > 0.0 && celsius < 150.0).then_some(celsius)
+}
+
+/// Every thermal zone with a live reading. Zones reporting nonsense (or
+/// nothing) keep `temp_c = None` so they still show up in listings.
+pub fn discover_zones() -> Vec<ThermalZone> {
+    let root = Path::new(THERMAL_ROOT);
+    zone_table()
+        .iter()
+        .map(|(index, kind)| ThermalZone {
+            index: *index,
+            kind: kind.clone(),
+            temp_c: read_zone_temp(root, *index),
+        })
+        .collect()
+}
+
+/// The last sweep and when it was taken.
+type Sweep = Option<(Instant, Arc<[ThermalZone]>)>;
+
+fn sweep_cache() -> &'static Mutex<Sweep> {
+    static SWEEP: OnceLock<Mutex<Sweep>> = OnceLock::new();
+    SWEEP.get_or_init(|| Mutex::new(None))
+}
+
+/// The zones the current mapping reads, with live readings; reused for
+/// [`SWEEP_TTL`]. Zones no role selects keep `temp_c = None`, except that
+/// every die zone is read while `fas` falls back to them.
+pub fn sweep() -> Arc<[ThermalZone]> {
+    let Ok(mut cache) = sweep_cache().lock() else {
+        return discover_zones().into();
+    };
+    if let Some((at, zones)) = cache.as_ref()
+        && at.elapsed() < SWEEP_TTL
+    {
+        return zones.clone();
+    }
+    let map = current_map();
+    let root = Path::new(THERMAL_ROOT);
+    let mut zones: Vec<ThermalZone> = zone_table()
+        .iter()
+        .map(|(index, kind)| ThermalZone {
+            index: *index,
+            kind: kind.clone(),
+            temp_c: None,
+        })
+        .collect();
+    let fas_fallback = !map.matches_any(&zones, &map.fas);
+    for z in &mut zones {
+        if map.selects(&z.kind) || (fas_fallback && is_die(&z.kind)) {
+            z.temp_c = read_zone_temp(root, z.index);
+        }
+    }
+    let zones: Arc<[ThermalZone]> = zones.into();
+    *cache = Some((Instant::now(), zones.clone()));
+    zones
+}
 
 pub struct ThermalMonitor;
 
@@ -14,21 +383,108 @@ impl ThermalMonitor {
         Self
     }
 
-    pub fn get_max_temp(&self) -> Result<f32> {
-        let mut max = 0.0f32;
+    /// This tick's [`sweep`], resolved through the current mapping.
+    pub fn readings(&self) -> ThermalReadings {
+        current_map().readings(&sweep())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        for i in 0..20 {
-            let path = format!("/sys/class/thermal/thermal_zone{}/temp", i);
-            if let Ok(s) = fs::read_to_string(&path)
-                && let Ok(millicelsius) = s.trim().parse::<i32>()
-            {
-                let celsius = millicelsius as f32 / 1000.0;
-                if celsius > 0.0 && celsius < 150.0 && celsius > max {
-                    max = celsius;
-                }
-            }
+    fn zone(index: u32, kind: &str, temp: f32) -> ThermalZone {
+        ThermalZone {
+            index,
+            kind: kind.to_string(),
+            temp_c: Some(temp),
+        }
+    }
+
+    #[test]
+    fn glob_matches_names_and_wildcards() {
+        assert!(glob_match("cpu-1-0", "cpu-1-0"));
+        assert!(glob_match("cpu*", "cpu-1-0"));
+        assert!(glob_match("*therm", "xo-therm"));
+        assert!(glob_match("gpu?", "gpu1"));
+        assert!(glob_match("*", "pmic"));
+        assert!(!glob_match("cpu*", "pm8350-cpu"));
+        assert!(!glob_match("gpu?", "gpu10"));
+    }
+
+    #[test]
+    fn hot_pmic_does_not_reach_fas() {
+        let zones = [
+            zone(0, "cpu-0-0", 55.0),
+            zone(1, "gpuss-0", 60.0),
+            zone(42, "pm8350b-ibat", 98.0),
+        ];
+        let map = ThermalMap::default();
+        let r = map.readings(&zones);
+        assert_eq!(r.fas, Some(60.0));
+        assert_eq!(r.cpu, Some(55.0));
+        assert_eq!(r.safety, Some(98.0));
+    }
+
+    #[test]
+    fn unmatched_fas_falls_back_to_hottest_die_zone() {
+        let zones = [
+            zone(0, "mtktscpu", 71.0),
+            zone(1, "soc_max", 74.0),
+            zone(2, "pmic", 90.0),
+            zone(3, "battery", 45.0),
+        ];
+        let map = ThermalMap::default();
+        assert_eq!(map.readings(&zones).fas, Some(74.0));
+
+        // A matching zone without a reading is not a fallback case.
+        let zones = [
+            ThermalZone {
+                index: 0,
+                kind: "cpu-0-0".into(),
+                temp_c: None,
+            },
+            zone(1, "soc_max", 74.0),
+        ];
+        assert_eq!(map.readings(&zones).fas, None);
+    }
+
+    #[test]
+    fn virtual_skin_is_weighted_and_skips_missing() {
+        let cfg = ThermalConfig {
+            fas: vec![VIRTUAL_SKIN.to_string()],
+            virtual_skin: [
+                ("xo-therm".to_string(), 3.0),
+                ("quiet-therm".to_string(), 1.0),
+                ("absent".to_string(), 5.0),
+            ]
+            .into_iter()
+            .collect(),
+            ..ThermalConfig::default()
+        };
+        let map = ThermalMap::from_config(&cfg);
+        let zones = [zone(3, "xo-therm", 40.0), zone(4, "quiet-therm", 44.0)];
+        assert_eq!(map.readings(&zones).fas, Some(41.0));
+        assert_eq!(map.roles_for("xo-therm"), vec!["fas", "safety", "skin"]);
+    }
+
+    #[test]
+    fn zone_table_scans_past_twenty() {
+        let dir = std::env::temp_dir().join(format!("auriya-thermal-{}", std::process::id()));
+        for (i, kind) in [(2, "cpu-0-0"), (27, "gpuss-1")] {
+            let z = dir.join(format!("thermal_zone{i}"));
+            fs::create_dir_all(&z).unwrap();
+            fs::write(z.join("type"), format!("{kind}\n")).unwrap();
+            fs::write(z.join("temp"), "47500\n").unwrap();
         }
+        fs::create_dir_all(dir.join("cooling_device0")).unwrap();
 
-        Ok(max)
+        let table = scan_zone_table(&dir);
+        assert_eq!(
+            table,
+            vec![(2, "cpu-0-0".to_string()), (27, "gpuss-1".to_string())]
+        );
+        assert_eq!(read_zone_temp(&dir, 27), Some(47.5));
+        fs::remove_dir_all(&dir).ok();
     }
 }
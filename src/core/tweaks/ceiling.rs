@@ -1,4 +1,5 @@
 use anyhow::Result;
+use std::collections::HashMap;
 use std::ffi::CString;
 use std::fs;
 use std::os::unix::fs::PermissionsExt;
@@ -8,11 +9,18 @@ use std::ptr;
 use libc::{MNT_DETACH, MS_BIND, MS_REC, mount, umount2};
 use tracing::{debug, info, warn};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use crate::core::config::settings::CustomCeiling;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CeilingLevel {
     Low,
+    /// Every cluster capped at its energy-model efficient frequency, all
+    /// cores online.
+    Efficient,
     Balance,
     High,
+    /// A `[ceiling.levels.<name>]` definition.
+    Custom(String),
 }
 
 impl std::str::FromStr for CeilingLevel {
@@ -20,6 +28,7 @@ impl std::str::FromStr for CeilingLevel {
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "low" => Ok(Self::Low),
+            "efficient" => Ok(Self::Efficient),
             "balance" => Ok(Self::Balance),
             "high" => Ok(Self::High),
             _ => Err(()),
@@ -27,12 +36,22 @@ impl std::str::FromStr for CeilingLevel {
     }
 }
 
+impl CeilingLevel {
+    /// Whether the level holds clocks or cores below what the hardware
+    /// offers; balance and high leave them alone.
+    pub fn is_capping(&self) -> bool {
+        !matches!(self, Self::Balance | Self::High)
+    }
+}
+
 impl std::fmt::Display for CeilingLevel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Low => write!(f, "low"),
+            Self::Efficient => write!(f, "efficient"),
             Self::Balance => write!(f, "balance"),
             Self::High => write!(f, "high"),
+            Self::Custom(name) => f.write_str(name),
         }
     }
 }
@@ -44,6 +63,8 @@ pub struct CoreLayout {
     pub prime_ids: Vec<usize>,
     pub little_freqs_khz: Vec<u64>,
     pub big_freqs_khz: Vec<u64>,
+    /// Energy-model efficient frequency per core, where known.
+    pub efficient_khz: Vec<(usize, u64)>,
 }
 
 impl CoreLayout {
@@ -81,6 +102,11 @@ impl CoreLayout {
 
         let little_freqs_khz = read_available_freqs(&little_ids);
         let big_freqs_khz = read_available_freqs(&big_ids);
+        let em = crate::core::tweaks::energy_model::table();
+        let efficient_khz = cores_for_classify
+            .iter()
+            .filter_map(|&c| em.efficient_for(c).map(|f| (c, f)))
+            .collect();
 
         Self {
             all_core_ids: cores_for_classify,
@@ -89,8 +115,18 @@ impl CoreLayout {
             prime_ids,
             little_freqs_khz,
             big_freqs_khz,
+            efficient_khz,
         }
     }
+
+    /// Efficient frequency of the cluster holding the first of `ids`.
+    pub fn efficient_for(&self, ids: &[usize]) -> Option<u64> {
+        let first = ids.first()?;
+        self.efficient_khz
+            .iter()
+            .find(|(c, _)| c == first)
+            .map(|&(_, f)| f)
+    }
 }
 
 fn read_available_freqs(core_ids: &[usize]) -> Vec<u64> {
@@ -195,11 +231,12 @@ fn cleanup_stale_mounts() {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CeilingConfig {
     pub default: CeilingLevel,
     pub low_freq_little_khz: Option<u64>,
     pub low_freq_big_khz: Option<u64>,
+    pub levels: HashMap<String, CustomCeiling>,
 }
 
 impl Default for CeilingConfig {
@@ -208,6 +245,61 @@ impl Default for CeilingConfig {
             default: CeilingLevel::Balance,
             low_freq_little_khz: None,
             low_freq_big_khz: None,
+            levels: HashMap::new(),
+        }
+    }
+}
+
+impl CeilingConfig {
+    /// Build from `[ceiling]`. User levels named like a built-in one would
+    /// never be reached and are dropped; an unknown `default` falls back
+    /// to balance. Both are warned about.
+    pub fn from_settings(c: &crate::core::config::settings::CeilingConfig) -> Self {
+        let levels = c
+            .levels
+            .iter()
+            .filter(|(name, _)| {
+                let builtin = name.parse::<CeilingLevel>().is_ok();
+                if builtin {
+                    warn!(target: "auriya::ceiling", "[ceiling.levels.{}] ignored: {:?} is a built-in level", name, name);
+                }
+                !builtin
+            })
+            .map(|(name, def)| (name.clone(), def.clone()))
+            .collect();
+        let mut config = Self {
+            low_freq_little_khz: c.low_freq_little_khz,
+            low_freq_big_khz: c.low_freq_big_khz,
+            levels,
+            ..Self::default()
+        };
+        match config.level(&c.default) {
+            Some(level) => config.default = level,
+            None => {
+                warn!(target: "auriya::ceiling", "Unknown [ceiling] default {:?}, using balance", c.default)
+            }
+        }
+        config
+    }
+
+    /// A built-in level, or a `[ceiling.levels]` one by exact name.
+    pub fn level(&self, name: &str) -> Option<CeilingLevel> {
+        name.parse().ok().or_else(|| {
+            self.levels
+                .contains_key(name)
+                .then(|| CeilingLevel::Custom(name.to_string()))
+        })
+    }
+
+    /// Warn about every game whose `ceiling` names no known level; those
+    /// games run at the default level instead.
+    pub fn check_gamelist(&self, gamelist: &crate::core::config::GameList) {
+        for game in &gamelist.game {
+            if let Some(name) = game.ceiling.as_deref()
+                && self.level(name).is_none()
+            {
+                warn!(target: "auriya::ceiling", "{}: unknown ceiling {:?}, using the default ({})", game.package, name, self.default);
+            }
         }
     }
 }
@@ -225,6 +317,8 @@ pub struct CeilingController {
     pub layout: CoreLayout,
     mounts: Vec<MountEntry>,
     current_level: Option<CeilingLevel>,
+    /// A custom level capped the GPU.
+    gpu_capped: bool,
 }
 
 impl Default for CeilingController {
@@ -254,11 +348,12 @@ impl CeilingController {
             layout,
             mounts: Vec::new(),
             current_level: None,
+            gpu_capped: false,
         }
     }
 
-    pub fn apply(&mut self, level: CeilingLevel, config: &CeilingConfig) -> Result<()> {
-        if self.current_level == Some(level) {
+    pub fn apply(&mut self, level: &CeilingLevel, config: &CeilingConfig) -> Result<()> {
+        if self.current_level.as_ref() == Some(level) {
             return Ok(());
         }
 
@@ -266,13 +361,76 @@ impl CeilingController {
 
         match level {
             CeilingLevel::Low => self.apply_low(config),
+            CeilingLevel::Efficient => self.apply_efficient(),
             CeilingLevel::Balance => {
                 self.online_all();
                 self.current_level = Some(CeilingLevel::Balance);
                 Ok(())
             }
             CeilingLevel::High => self.apply_high(config),
+            CeilingLevel::Custom(name) => match config.levels.get(name) {
+                Some(def) => self.apply_custom(name, def),
+                None => {
+                    warn!(target: "auriya::ceiling", "Unknown ceiling level {:?}, treating as balance", name);
+                    self.online_all();
+                    self.current_level = Some(level.clone());
+                    Ok(())
+                }
+            },
+        }
+    }
+
+    /// Offline the listed cores, then freeze each cluster's max/min at the
+    /// level's values. Percentages resolve per core against its
+    /// `cpuinfo_max_freq`.
+    fn apply_custom(&mut self, name: &str, def: &CustomCeiling) -> Result<()> {
+        self.online_all();
+
+        for &core in &def.offline {
+            let path = format!("/sys/devices/system/cpu/cpu{}/online", core);
+            if Path::new(&path).exists() {
+                let _ = fs::write(&path, "0");
+                debug!(target: "auriya::ceiling", "{}: offline core {}", name, core);
+            }
+        }
+
+        let clusters = [
+            (self.layout.little_ids.clone(), &def.little),
+            (self.layout.big_ids.clone(), &def.big),
+            (self.layout.prime_ids.clone(), &def.prime),
+        ];
+        for (ids, limit) in clusters {
+            for core in ids.into_iter().filter(|c| !def.offline.contains(c)) {
+                let dir = format!("/sys/devices/system/cpu/cpu{}/cpufreq", core);
+                let Some(hw_max) = fs::read_to_string(format!("{dir}/cpuinfo_max_freq"))
+                    .ok()
+                    .and_then(|s| s.trim().parse::<u64>().ok())
+                else {
+                    continue;
+                };
+                let max = limit.max.as_ref().and_then(|v| v.resolve(hw_max));
+                let min = limit
+                    .min
+                    .as_ref()
+                    .and_then(|v| v.resolve(hw_max))
+                    .map(|m| max.map_or(m, |max| m.min(max)));
+                if let Some(max) = max {
+                    self.freeze_freq(&format!("{dir}/scaling_max_freq"), max);
+                }
+                if let Some(min) = min {
+                    self.freeze_freq(&format!("{dir}/scaling_min_freq"), min);
+                }
+            }
+        }
+
+        if def.gpu_max.is_some() {
+            crate::core::tweaks::gpu::set_ceiling(def.gpu_max.as_ref());
+            self.gpu_capped = true;
         }
+
+        self.current_level = Some(CeilingLevel::Custom(name.to_string()));
+        debug!(target: "auriya::ceiling", "Applied custom ceiling {}", name);
+        Ok(())
     }
 
     fn apply_low(&mut self, config: &CeilingConfig) -> Result<()> {
@@ -281,9 +439,11 @@ impl CeilingController {
         let little_ids = self.layout.little_ids.clone();
         let little_freq = config
             .low_freq_little_khz
+            .or_else(|| self.layout.efficient_for(&little_ids))
             .or_else(|| self.layout.little_freqs_khz.first().copied());
         let big_freq = config
             .low_freq_big_khz
+            .or_else(|| self.layout.efficient_for(&big_ids))
             .or_else(|| self.layout.big_freqs_khz.first().copied());
 
         for &core in prime_ids {
@@ -328,6 +488,28 @@ impl CeilingController {
         Ok(())
     }
 
+    /// Cap every core at its cluster's efficient frequency. Clusters the
+    /// energy model doesn't cover are left alone.
+    fn apply_efficient(&mut self) -> Result<()> {
+        self.online_all();
+
+        let caps: Vec<(usize, u64)> = self.layout.efficient_khz.clone();
+        if caps.is_empty() {
+            debug!(target: "auriya::ceiling", "No energy model, EFFICIENT behaves as BALANCE");
+        }
+        for (core, freq) in caps {
+            let p = format!(
+                "/sys/devices/system/cpu/cpu{}/cpufreq/scaling_max_freq",
+                core
+            );
+            self.freeze_freq(&p, freq);
+        }
+
+        self.current_level = Some(CeilingLevel::Efficient);
+        debug!(target: "auriya::ceiling", "Applied EFFICIENT ceiling");
+        Ok(())
+    }
+
     fn apply_high(&mut self, _config: &CeilingConfig) -> Result<()> {
         self.online_all();
 
@@ -386,6 +568,9 @@ impl CeilingController {
     }
 
     pub fn restore(&mut self) {
+        if std::mem::take(&mut self.gpu_capped) {
+            crate::core::tweaks::gpu::set_ceiling(None);
+        }
         for entry in self.mounts.drain(..) {
             // Unmount FIRST so the following chmod/write hit the real sysfs
             // node, not the cache file shadowing it. Doing it the other way
@@ -467,6 +652,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn custom_levels_parse_and_resolve() {
+        assert_eq!("LOW".parse(), Ok(CeilingLevel::Low));
+        assert_eq!("efficient".parse(), Ok(CeilingLevel::Efficient));
+        assert!("hgih".parse::<CeilingLevel>().is_err());
+
+        let settings: crate::core::config::settings::CeilingConfig = toml::from_str(
+            r#"
+            default = "my-cool-30fps"
+            [levels.my-cool-30fps]
+            offline = [7]
+            little = { max = "60%" }
+            big = { max = 1400000, min = "25%" }
+            gpu_max = "70%"
+            [levels.low]
+            offline = [4, 5, 6, 7]
+            "#,
+        )
+        .unwrap();
+        let cfg = CeilingConfig::from_settings(&settings);
+        assert_eq!(cfg.default, CeilingLevel::Custom("my-cool-30fps".into()));
+        assert_eq!(cfg.level("high"), Some(CeilingLevel::High));
+        assert_eq!(cfg.level("my-cool-30fp"), None);
+        assert!(!cfg.levels.contains_key("low"));
+        let def = &cfg.levels["my-cool-30fps"];
+        assert_eq!(def.offline, vec![7]);
+        let resolve = |v: &Option<crate::core::config::settings::FreqValue>| {
+            v.as_ref().and_then(|v| v.resolve(2_000_000))
+        };
+        assert_eq!(resolve(&def.little.max), Some(1_200_000));
+        assert_eq!(resolve(&def.big.max), Some(1_400_000));
+        assert_eq!(resolve(&def.big.min), Some(500_000));
+        assert_eq!(resolve(&def.prime.max), None);
+        assert_eq!(resolve(&def.gpu_max), Some(1_400_000));
+    }
+
     /// Real-device test (root only): drive the ceiling through
     /// balance → sleep(Low) → balance → in-game(High) → balance, capturing
     /// each cluster's scaling_{min,max}_freq and permissions, and asserting
@@ -514,16 +735,16 @@ mod tests {
             ))
         };
 
-        ctrl.apply(CeilingLevel::Balance, &cfg).unwrap();
+        ctrl.apply(&CeilingLevel::Balance, &cfg).unwrap();
         snapshot("BALANCE (baseline / free)", &cores);
 
         // SLEEP / screen-off → Low ceiling caps little + big max.
-        ctrl.apply(CeilingLevel::Low, &cfg).unwrap();
+        ctrl.apply(&CeilingLevel::Low, &cfg).unwrap();
         snapshot("LOW (sleep / screen-off)", &cores);
 
         // Release → every online core's max must be back at the hw limit
         // and writable (not the stuck-low 0o444 we used to leave behind).
-        ctrl.apply(CeilingLevel::Balance, &cfg).unwrap();
+        ctrl.apply(&CeilingLevel::Balance, &cfg).unwrap();
         snapshot("BALANCE after LOW (restore)", &cores);
         for &(name, core) in &cores {
             if !online(core) {
@@ -543,7 +764,7 @@ mod tests {
         }
 
         // IN-GAME → High ceiling locks min = max on every core.
-        ctrl.apply(CeilingLevel::High, &cfg).unwrap();
+        ctrl.apply(&CeilingLevel::High, &cfg).unwrap();
         snapshot("HIGH (in-game)", &cores);
         for &(name, core) in &cores {
             let min = read_u64(&format!(
@@ -553,7 +774,7 @@ mod tests {
         }
 
         // Release → min must drop back to the hw minimum, writable.
-        ctrl.apply(CeilingLevel::Balance, &cfg).unwrap();
+        ctrl.apply(&CeilingLevel::Balance, &cfg).unwrap();
         snapshot("BALANCE after HIGH (restore)", &cores);
         for &(name, core) in &cores {
             let p = format!("/sys/devices/system/cpu/cpu{core}/cpufreq/scaling_min_freq");
@@ -0,0 +1,471 @@
+// uclamp and cpuset control through Android's scheduling cgroups.
+//
+// GKI kernels dropped schedtune (`/dev/stune`); boosting a task group is
+// done with utilization clamps instead. Android mounts the cpu controller
+// as cgroup v1 at `/dev/cpuctl` (`top-app`, `foreground`, `background`,
+// each with `cpu.uclamp.min`/`cpu.uclamp.max`); some kernels have it on
+// the v2 hierarchy under `/sys/fs/cgroup` instead. Core placement per group
+// is the v1 cpuset at `/dev/cpuset`.
+//
+// Per profile, [`CgroupController::apply`] clamps the role groups and
+// narrows the foreground/background cpusets. With a dedicated game group
+// configured, [`CgroupController::place_game`] creates it next to top-app
+// (same shares and cores, its own `uclamp.min`) and moves the game in;
+// each process goes back to the group it came from on release. On v2 the
+// game's processes already have their own groups, so the clamp is set
+// there instead.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::{debug, info};
+
+use crate::core::config::settings::{GroupPolicy, UclampRange};
+use crate::core::tweaks::memory::{CGROUP_V2_ROOT, parse_cgroup_v2_path};
+use crate::core::tweaks::saved::SavedNodes;
+
+const CPUCTL_ROOT: &str = "/dev/cpuctl";
+const CPUSET_ROOT: &str = "/dev/cpuset";
+const STUNE_TOP_APP: &str = "/dev/stune/top-app";
+const TOP_APP: &str = "top-app";
+/// cpuctl nodes a dedicated game group inherits from top-app.
+const CPUCTL_INHERITED: [&str; 3] = [
+    "cpu.shares",
+    "cpu.uclamp.max",
+    "cpu.uclamp.latency_sensitive",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UclampBackend {
+    /// cgroup v1 cpu controller at `/dev/cpuctl`.
+    CpuCtl,
+    /// cpu controller on the cgroup v2 hierarchy.
+    CgroupV2,
+}
+
+impl UclampBackend {
+    fn detect() -> Option<Self> {
+        if Path::new(CPUCTL_ROOT)
+            .join(TOP_APP)
+            .join("cpu.uclamp.min")
+            .exists()
+        {
+            Some(Self::CpuCtl)
+        } else if fs::read_to_string(Path::new(CGROUP_V2_ROOT).join("cgroup.controllers"))
+            .is_ok_and(|c| c.split_whitespace().any(|c| c == "cpu"))
+        {
+            Some(Self::CgroupV2)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CpuCtl => "cpuctl",
+            Self::CgroupV2 => "cgroup_v2",
+        }
+    }
+
+    fn root(self) -> &'static str {
+        match self {
+            Self::CpuCtl => CPUCTL_ROOT,
+            Self::CgroupV2 => CGROUP_V2_ROOT,
+        }
+    }
+}
+
+/// Scheduling group mechanisms the kernel offers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SchedGroups {
+    pub uclamp: Option<UclampBackend>,
+    /// cgroup v1 cpuset with Android's role groups.
+    pub cpuset: bool,
+    /// Legacy schedtune (pre-GKI kernels).
+    pub schedtune: bool,
+}
+
+impl SchedGroups {
+    pub fn detect() -> Self {
+        Self {
+            uclamp: UclampBackend::detect(),
+            cpuset: Path::new(CPUSET_ROOT).join(TOP_APP).join("cpus").exists(),
+            schedtune: Path::new(STUNE_TOP_APP).exists(),
+        }
+    }
+}
+
+impl std::fmt::Display for SchedGroups {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "UCLAMP={} CPUSET={} SCHEDTUNE={}",
+            self.uclamp.map(UclampBackend::as_str).unwrap_or("none"),
+            if self.cpuset { "v1" } else { "none" },
+            if self.schedtune { "yes" } else { "no" },
+        )
+    }
+}
+
+/// `cpu.uclamp.*` value for a percentage; 100 is written as `max`.
+fn uclamp_value(pct: f32) -> String {
+    if pct >= 100.0 {
+        "max".to_string()
+    } else {
+        format!("{:.2}", pct.max(0.0))
+    }
+}
+
+/// Compact cpu list for a cpuset, e.g. `[0, 1, 2, 3, 6]` → `0-3,6`.
+pub fn cpulist(ids: &[usize]) -> String {
+    let mut ids = ids.to_vec();
+    ids.sort_unstable();
+    ids.dedup();
+    let mut out: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < ids.len() {
+        let start = ids[i];
+        while i + 1 < ids.len() && ids[i + 1] == ids[i] + 1 {
+            i += 1;
+        }
+        out.push(if ids[i] == start {
+            start.to_string()
+        } else {
+            format!("{}-{}", start, ids[i])
+        });
+        i += 1;
+    }
+    out.join(",")
+}
+
+/// A process's path in the v1 hierarchy holding `controller`.
+pub(crate) fn parse_cgroup_v1_path<'a>(content: &'a str, controller: &str) -> Option<&'a str> {
+    content.lines().find_map(|l| {
+        let mut parts = l.splitn(3, ':');
+        let _id = parts.next()?;
+        let controllers = parts.next()?;
+        let path = parts.next()?;
+        controllers
+            .split(',')
+            .any(|c| c == controller)
+            .then_some(path)
+    })
+}
+
+/// Node → value for `policy`, with `top_app_min` replacing top-app's
+/// `min`. `little` stands in for the little cluster in cpu lists.
+fn plan_nodes(
+    groups: &SchedGroups,
+    policy: &GroupPolicy,
+    top_app_min: Option<f32>,
+    little: &str,
+) -> Vec<(PathBuf, String)> {
+    let mut nodes = Vec::new();
+    if let Some(backend) = groups.uclamp {
+        let top_app = UclampRange {
+            min: top_app_min.or(policy.top_app.and_then(|r| r.min)),
+            max: policy.top_app.and_then(|r| r.max),
+        };
+        for (role, range) in [
+            (TOP_APP, Some(top_app)),
+            ("foreground", policy.foreground),
+            ("background", policy.background),
+        ] {
+            let Some(range) = range else {
+                continue;
+            };
+            let dir = Path::new(backend.root()).join(role);
+            if let Some(min) = range.min {
+                nodes.push((dir.join("cpu.uclamp.min"), uclamp_value(min)));
+            }
+            if let Some(max) = range.max {
+                nodes.push((dir.join("cpu.uclamp.max"), uclamp_value(max)));
+            }
+        }
+    }
+    if groups.cpuset {
+        for (role, cpus) in [
+            ("foreground", &policy.foreground_cpus),
+            ("background", &policy.background_cpus),
+        ] {
+            let Some(cpus) = cpus.as_deref().map(str::trim).filter(|c| !c.is_empty()) else {
+                continue;
+            };
+            let cpus = if cpus == "little" { little } else { cpus };
+            if !cpus.is_empty() {
+                nodes.push((
+                    Path::new(CPUSET_ROOT).join(role).join("cpus"),
+                    cpus.to_string(),
+                ));
+            }
+        }
+    }
+    nodes
+}
+
+/// The game's own group in one hierarchy.
+#[derive(Debug)]
+struct GameGroup {
+    dir: PathBuf,
+    /// Created by us, so removed again on release.
+    created: bool,
+    /// pid → group it was moved out of, relative to the hierarchy root.
+    origin: HashMap<i32, String>,
+}
+
+/// Role-group clamps and cpusets per profile, and the game's boosted
+/// group; see the module docs.
+#[derive(Debug, Default)]
+pub struct CgroupController {
+    groups: SchedGroups,
+    little_cpus: String,
+    saved: SavedNodes,
+    /// Policy last applied, with the top-app `min` override.
+    applied: Option<(GroupPolicy, Option<f32>)>,
+    /// Game group per hierarchy (cpuctl, cpuset), when placed.
+    game: Vec<GameGroup>,
+    /// v2 per-process `cpu.uclamp.min` nodes set for the game.
+    game_v2: Vec<PathBuf>,
+    game_group: Option<String>,
+}
+
+impl CgroupController {
+    pub fn new(little_cpus: String) -> Self {
+        let groups = SchedGroups::detect();
+        info!(target: "auriya::cgroups", "Scheduling groups: {}", groups);
+        Self {
+            groups,
+            little_cpus,
+            ..Self::default()
+        }
+    }
+
+    pub fn groups(&self) -> SchedGroups {
+        self.groups
+    }
+
+    /// Name of the dedicated group the game is in, if any.
+    pub fn game_group(&self) -> Option<&str> {
+        self.game_group.as_deref()
+    }
+
+    /// Clamp the role groups and set their cpusets for `policy`. Nodes the
+    /// previous policy set and this one doesn't are put back. Cheap to call
+    /// every tick.
+    pub fn apply(&mut self, policy: &GroupPolicy, top_app_min: Option<f32>) {
+        if self
+            .applied
+            .as_ref()
+            .is_some_and(|(p, m)| p == policy && *m == top_app_min)
+        {
+            return;
+        }
+        self.applied = Some((policy.clone(), top_app_min));
+        let nodes = plan_nodes(&self.groups, policy, top_app_min, &self.little_cpus);
+
+        let game_v2 = &self.game_v2;
+        self.saved.restore_unless(|n| {
+            nodes.iter().any(|(p, _)| p == n) || game_v2.iter().any(|p| p == n)
+        });
+        for (node, value) in nodes {
+            self.write_saved(node, &value);
+        }
+    }
+
+    fn write_saved(&mut self, node: PathBuf, value: &str) {
+        if let Some(old) = self.saved.write(&node, value) {
+            debug!(target: "auriya::cgroups", "{} {} -> {}", node.display(), old, value);
+        }
+    }
+
+    /// Put the game's processes in the dedicated group `name` with
+    /// `uclamp_min`. Repeated during the session, since Android moves
+    /// processes between role groups on every state change.
+    pub fn place_game(&mut self, pids: &[i32], name: &str, uclamp_min: Option<f32>) {
+        if self.game_group.as_deref() != Some(name) {
+            self.release_game();
+            self.game_group = Some(name.to_string());
+            if self.groups.uclamp == Some(UclampBackend::CpuCtl) {
+                let top_app = Path::new(CPUCTL_ROOT).join(TOP_APP);
+                if let Some(g) = Self::create_group(CPUCTL_ROOT, name) {
+                    for node in CPUCTL_INHERITED {
+                        if let Ok(v) = fs::read_to_string(top_app.join(node)) {
+                            let _ = fs::write(g.dir.join(node), v.trim());
+                        }
+                    }
+                    self.game.push(g);
+                }
+            }
+            if self.groups.cpuset {
+                let top_app = Path::new(CPUSET_ROOT).join(TOP_APP);
+                if let Some(g) = Self::create_group(CPUSET_ROOT, name) {
+                    // A cpuset takes no tasks until both are set.
+                    for node in ["cpus", "mems"] {
+                        if let Ok(v) = fs::read_to_string(top_app.join(node)) {
+                            let _ = fs::write(g.dir.join(node), v.trim());
+                        }
+                    }
+                    self.game.push(g);
+                }
+            }
+            info!(target: "auriya::cgroups", "Game moved to its own group {}", name);
+        }
+
+        let value = uclamp_min.map(uclamp_value);
+        for g in &mut self.game {
+            if g.dir.starts_with(CPUCTL_ROOT)
+                && let Some(v) = &value
+            {
+                let _ = fs::write(g.dir.join("cpu.uclamp.min"), v);
+            }
+            let controller = if g.dir.starts_with(CPUCTL_ROOT) {
+                "cpu"
+            } else {
+                "cpuset"
+            };
+            let ours = format!("/{name}");
+            for &pid in pids {
+                let Ok(cgroup) = fs::read_to_string(format!("/proc/{pid}/cgroup")) else {
+                    continue;
+                };
+                match parse_cgroup_v1_path(&cgroup, controller) {
+                    Some(p) if p == ours => {}
+                    Some(p) => {
+                        g.origin.insert(pid, p.to_string());
+                        let _ = fs::write(g.dir.join("cgroup.procs"), pid.to_string());
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        if self.groups.uclamp == Some(UclampBackend::CgroupV2)
+            && let Some(v) = &value
+        {
+            for &pid in pids {
+                let Some(node) = fs::read_to_string(format!("/proc/{pid}/cgroup"))
+                    .ok()
+                    .and_then(|c| parse_cgroup_v2_path(&c).map(str::to_string))
+                    .map(|p| {
+                        Path::new(CGROUP_V2_ROOT)
+                            .join(p.trim_start_matches('/'))
+                            .join("cpu.uclamp.min")
+                    })
+                else {
+                    continue;
+                };
+                if !self.game_v2.contains(&node) {
+                    self.game_v2.push(node.clone());
+                }
+                self.write_saved(node, v);
+            }
+        }
+    }
+
+    fn create_group(root: &str, name: &str) -> Option<GameGroup> {
+        let dir = Path::new(root).join(name);
+        let created = !dir.exists();
+        if created && let Err(e) = fs::create_dir(&dir) {
+            debug!(target: "auriya::cgroups", "Cannot create {}: {}", dir.display(), e);
+            return None;
+        }
+        Some(GameGroup {
+            dir,
+            created,
+            origin: HashMap::new(),
+        })
+    }
+
+    /// Move the game's processes back where they came from and remove the
+    /// groups created for it.
+    pub fn release_game(&mut self) {
+        if self.game_group.take().is_none() {
+            return;
+        }
+        for g in self.game.drain(..) {
+            let root = if g.dir.starts_with(CPUCTL_ROOT) {
+                CPUCTL_ROOT
+            } else {
+                CPUSET_ROOT
+            };
+            let procs = fs::read_to_string(g.dir.join("cgroup.procs")).unwrap_or_default();
+            for pid in procs.lines().filter_map(|l| l.trim().parse::<i32>().ok()) {
+                let origin = g.origin.get(&pid).map(String::as_str).unwrap_or("/");
+                let target = Path::new(root)
+                    .join(origin.trim_start_matches('/'))
+                    .join("cgroup.procs");
+                let _ = fs::write(target, pid.to_string());
+            }
+            if g.created {
+                let _ = fs::remove_dir(&g.dir);
+            }
+        }
+        for node in std::mem::take(&mut self.game_v2) {
+            self.saved.restore(&node);
+        }
+        debug!(target: "auriya::cgroups", "Game group released");
+    }
+
+    /// Release the game group and put every role group back.
+    pub fn restore(&mut self) {
+        self.release_game();
+        self.applied = None;
+        self.saved.restore_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_values_and_cpu_lists() {
+        assert_eq!(uclamp_value(20.0), "20.00");
+        assert_eq!(uclamp_value(100.0), "max");
+        assert_eq!(cpulist(&[3, 0, 1, 2, 6]), "0-3,6");
+        assert_eq!(cpulist(&[4]), "4");
+        assert_eq!(cpulist(&[]), "");
+
+        let cgroup = "5:cpu,cpuacct:/top-app\n3:cpuset:/foreground\n0::/uid_10234/pid_4242\n";
+        assert_eq!(parse_cgroup_v1_path(cgroup, "cpu"), Some("/top-app"));
+        assert_eq!(parse_cgroup_v1_path(cgroup, "cpuset"), Some("/foreground"));
+        assert_eq!(parse_cgroup_v1_path(cgroup, "memory"), None);
+    }
+
+    #[test]
+    fn plans_nodes_for_policy() {
+        let groups = SchedGroups {
+            uclamp: Some(UclampBackend::CpuCtl),
+            cpuset: true,
+            schedtune: false,
+        };
+        let policy = GroupPolicy {
+            top_app: Some(UclampRange {
+                min: Some(10.0),
+                max: None,
+            }),
+            background: Some(UclampRange {
+                min: None,
+                max: Some(50.0),
+            }),
+            background_cpus: Some("little".into()),
+            ..GroupPolicy::default()
+        };
+        let nodes = plan_nodes(&groups, &policy, Some(30.0), "0-3");
+        let value = |p: &str| {
+            nodes
+                .iter()
+                .find(|(n, _)| n == Path::new(p))
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(value("/dev/cpuctl/top-app/cpu.uclamp.min"), Some("30.00"));
+        assert_eq!(
+            value("/dev/cpuctl/background/cpu.uclamp.max"),
+            Some("50.00")
+        );
+        assert_eq!(value("/dev/cpuset/background/cpus"), Some("0-3"));
+        assert_eq!(nodes.len(), 3);
+        assert!(plan_nodes(&SchedGroups::default(), &policy, None, "0-3").is_empty());
+    }
+}
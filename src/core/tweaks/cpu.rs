@@ -3,9 +3,8 @@ use std::fs;
 use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
-use tracing::{debug, info, warn};
+use tracing::{debug, info};
 
-static LAST_TASKSET_WARN_MS: AtomicU64 = AtomicU64::new(0);
 static LAST_RENICE_WARN_MS: AtomicU64 = AtomicU64::new(0);
 const WARN_DEBOUNCE_MS: u64 = 30000;
 
@@ -146,33 +145,9 @@ fn classify_from_freqs(core_freqs: &[(usize, u32)]) -> (u64, u64, u64) {
     (little_mask, big_mask, prime_mask)
 }
 
-pub fn get_affinity_mask_for_profile(profile: &str) -> u64 {
-    match get_online_cores() {
-        Ok(cores) => {
-            let (little, big, prime) = classify_cores(&cores);
-            match profile {
-                "performance" => big | prime,
-                "balance" => big,
-                "powersave" => little,
-                _ => big | prime,
-            }
-        }
-        Err(_) => 0xffff_ffff_ffff_ffff,
-    }
-}
-
-pub fn set_game_affinity_dynamic(pid: i32, profile: &str) -> Result<()> {
-    let mask = get_affinity_mask_for_profile(profile);
-    if mask == 0xffff_ffff_ffff_ffff {
-        debug!(target: "auriya:cpu", "Skipping affinity: invalid mask (get_online_cores failed)");
-        return Ok(());
-    }
-
-    if mask == 0 {
-        debug!(target: "auriya:cpu", "Skipping affinity: mask is zero for profile={}", profile);
-        return Ok(());
-    }
-
+/// `sched_setaffinity` on a single task. Affinity is per-thread on Linux:
+/// passing a PID only moves that process's main thread.
+pub fn set_thread_affinity(tid: i32, mask: u64) -> std::io::Result<()> {
     let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
 
     for i in 0..64 {
@@ -182,28 +157,35 @@ pub fn set_game_affinity_dynamic(pid: i32, profile: &str) -> Result<()> {
     }
 
     let result =
-        unsafe { libc::sched_setaffinity(pid, std::mem::size_of::<libc::cpu_set_t>(), &cpu_set) };
-
+        unsafe { libc::sched_setaffinity(tid, std::mem::size_of::<libc::cpu_set_t>(), &cpu_set) };
     if result == 0 {
-        info!(
-            target: "auriya:cpu",
-            "Set CPU affinity pid={} mask={:x} profile={}",
-            pid, mask, profile
-        );
+        Ok(())
     } else {
-        let now = now_ms();
-        let last = LAST_TASKSET_WARN_MS.load(Ordering::Relaxed);
-        if now.saturating_sub(last) > WARN_DEBOUNCE_MS {
-            warn!(
-                target: "auriya:cpu",
-                "sched_setaffinity failed: errno={}",
-                std::io::Error::last_os_error()
-            );
-            LAST_TASKSET_WARN_MS.store(now, Ordering::Relaxed);
-        }
+        Err(std::io::Error::last_os_error())
     }
+}
 
-    Ok(())
+/// Inverse of [`parse_online_cores`]: `0b1111_0001` → `"0,4-7"`.
+pub fn mask_to_cpu_list(mask: u64) -> String {
+    let mut parts = Vec::new();
+    let mut i = 0;
+    while i < 64 {
+        if (mask >> i) & 1 == 0 {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i + 1 < 64 && (mask >> (i + 1)) & 1 == 1 {
+            i += 1;
+        }
+        parts.push(if start == i {
+            start.to_string()
+        } else {
+            format!("{start}-{i}")
+        });
+        i += 1;
+    }
+    parts.join(",")
 }
 
 pub fn set_process_priority(pid: i32) -> Result<()> {
@@ -218,18 +200,13 @@ pub fn set_process_priority(pid: i32) -> Result<()> {
         }
     }
 
-    let oom_path = format!("/proc/{}/oom_score_adj", pid);
-    if Path::new(&oom_path).exists() {
-        let _ = fs::write(oom_path, "-800");
-    }
-
     info!(target: "auriya:cpu", "Process priority set for PID {}", pid);
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::classify_from_freqs;
+    use super::{classify_from_freqs, mask_to_cpu_list, parse_online_cores};
 
     /// Helper: turn a bitmask into a sorted Vec of core ids for assertions.
     fn ids(mask: u64) -> Vec<usize> {
@@ -306,4 +283,15 @@ mod tests {
         assert_eq!(ids(big), vec![4, 5, 6]);
         assert_eq!(ids(prime), vec![7]);
     }
+
+    #[test]
+    fn cpu_list_round_trips_through_mask() {
+        assert_eq!(mask_to_cpu_list(0b1111_0001), "0,4-7");
+        assert_eq!(mask_to_cpu_list(0b1000_0000), "7");
+        assert_eq!(mask_to_cpu_list(0), "");
+        let mask = parse_online_cores("0-3,6")
+            .iter()
+            .fold(0u64, |m, &c| m | (1 << c));
+        assert_eq!(mask_to_cpu_list(mask), "0-3,6");
+    }
 }
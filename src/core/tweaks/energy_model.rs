@@ -0,0 +1,369 @@
+// Power-efficient CPU frequency per cluster, from the kernel energy model.
+//
+// Each performance domain (one per cpufreq policy) in
+// `/sys/kernel/debug/energy_model/<pd>/` lists its OPPs as `ps:<freq>`
+// (`cs:<freq>` before 5.10) directories holding `frequency`, `power` and
+// `cost`. `cost` is energy per unit of work, so it falls with frequency
+// while the voltage sits at its floor and climbs once the voltage has to
+// rise. The efficient frequency is the knee of that curve: the fastest OPP
+// whose cost is still within `KNEE_TOLERANCE` of the cheapest one. That is
+// what the low and efficient ceilings cap a cluster at.
+//
+// debugfs is rarely mounted on user builds after boot, so the first
+// derivation mounts it for as long as it takes to read the model, and the
+// table is cached in the state dir, keyed by a fingerprint of the cpufreq
+// policies so a kernel with different OPPs derives it afresh.
+
+use std::collections::BTreeMap;
+use std::ffi::CString;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::ptr;
+use std::sync::OnceLock;
+
+use libc::{MNT_DETACH, mount, umount2};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+const DEBUGFS_DIR: &str = "sys/kernel/debug";
+const EM_DIR: &str = "sys/kernel/debug/energy_model";
+const CPUFREQ_DIR: &str = "sys/devices/system/cpu/cpufreq";
+/// How much costlier than the cheapest OPP the knee may be.
+const KNEE_TOLERANCE: f64 = 1.10;
+
+/// One performance domain's efficient frequency.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClusterEfficiency {
+    pub cpus: Vec<usize>,
+    pub efficient_khz: u64,
+    pub max_khz: u64,
+}
+
+/// Where a [`EfficiencyTable`] came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmSource {
+    /// Derived from debugfs on this boot.
+    Debugfs,
+    /// Loaded from the state-dir cache.
+    Cache,
+    /// No energy model available.
+    #[default]
+    None,
+}
+
+impl std::fmt::Display for EmSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Debugfs => "debugfs",
+            Self::Cache => "cache",
+            Self::None => "none",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EfficiencyTable {
+    /// cpufreq layout the table was derived for.
+    pub fingerprint: String,
+    #[serde(skip)]
+    pub source: EmSource,
+    pub clusters: Vec<ClusterEfficiency>,
+}
+
+impl EfficiencyTable {
+    /// Efficient frequency of the cluster holding `cpu`.
+    pub fn efficient_for(&self, cpu: usize) -> Option<u64> {
+        self.clusters
+            .iter()
+            .find(|c| c.cpus.contains(&cpu))
+            .map(|c| c.efficient_khz)
+    }
+
+    /// Read the cache at `cache` if it matches this device, else derive
+    /// from debugfs (mounting it if needed) and refresh the cache.
+    pub fn load_or_derive(root: &Path, cache: &Path) -> Self {
+        let fingerprint = fingerprint(root);
+        if let Some(mut table) = fs::read_to_string(cache)
+            .ok()
+            .and_then(|s| serde_json::from_str::<Self>(&s).ok())
+            .filter(|t| t.fingerprint == fingerprint && !t.clusters.is_empty())
+        {
+            table.source = EmSource::Cache;
+            return table;
+        }
+        let clusters = {
+            let _debugfs = DebugfsMount::ensure(root);
+            derive(root)
+        };
+        if clusters.is_empty() {
+            return Self {
+                fingerprint,
+                ..Self::default()
+            };
+        }
+        let table = Self {
+            fingerprint,
+            source: EmSource::Debugfs,
+            clusters,
+        };
+        match serde_json::to_string_pretty(&table) {
+            Ok(json) => {
+                if let Err(e) = fs::write(cache, json) {
+                    warn!(target: "auriya::ceiling", "Failed to cache energy model at {}: {e}", cache.display());
+                }
+            }
+            Err(e) => warn!(target: "auriya::ceiling", "Failed to serialise energy model: {e}"),
+        }
+        table
+    }
+}
+
+static TABLE: OnceLock<EfficiencyTable> = OnceLock::new();
+
+/// This device's table, derived or loaded on first use.
+pub fn table() -> &'static EfficiencyTable {
+    TABLE.get_or_init(|| {
+        let table = EfficiencyTable::load_or_derive(
+            Path::new("/"),
+            &crate::core::config::energy_model_path(),
+        );
+        for c in &table.clusters {
+            info!(
+                target: "auriya::ceiling",
+                "Efficient freq cpus={:?}: {} kHz (max {}, from {})",
+                c.cpus, c.efficient_khz, c.max_khz, table.source
+            );
+        }
+        table
+    })
+}
+
+/// debugfs mounted just for one derivation; unmounted on drop.
+struct DebugfsMount(Option<CString>);
+
+impl DebugfsMount {
+    /// Mount debugfs if the live tree has it unmounted (an empty mount
+    /// point). Fake roots are left alone.
+    fn ensure(root: &Path) -> Self {
+        let dir = root.join(DEBUGFS_DIR);
+        let unmounted = fs::read_dir(&dir).is_ok_and(|mut e| e.next().is_none());
+        if root != Path::new("/") || !unmounted {
+            return Self(None);
+        }
+        let Ok(dir) = CString::new(dir.as_os_str().as_bytes()) else {
+            return Self(None);
+        };
+        // SAFETY: source, target and fstype are NUL-terminated strings that
+        // outlive the call; debugfs takes no mount data, so null is valid.
+        let ok = unsafe {
+            mount(
+                c"debugfs".as_ptr(),
+                dir.as_ptr(),
+                c"debugfs".as_ptr(),
+                0,
+                ptr::null(),
+            ) == 0
+        };
+        if !ok {
+            debug!(
+                target: "auriya::ceiling",
+                "Failed to mount debugfs: {}",
+                std::io::Error::last_os_error()
+            );
+            return Self(None);
+        }
+        info!(target: "auriya::ceiling", "Mounted debugfs to read the energy model");
+        Self(Some(dir))
+    }
+}
+
+impl Drop for DebugfsMount {
+    fn drop(&mut self) {
+        if let Some(dir) = &self.0 {
+            // SAFETY: `dir` is the NUL-terminated path we mounted on, owned
+            // by `self` for the duration of the call.
+            unsafe {
+                umount2(dir.as_ptr(), MNT_DETACH);
+            }
+        }
+    }
+}
+
+/// `policyN:min-max` for every cpufreq policy.
+fn fingerprint(root: &Path) -> String {
+    let Ok(entries) = fs::read_dir(root.join(CPUFREQ_DIR)) else {
+        return String::new();
+    };
+    let mut policies: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with("policy"))
+        .map(|e| {
+            let read = |n: &str| {
+                fs::read_to_string(e.path().join(n))
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_default()
+            };
+            format!(
+                "{}:{}-{}",
+                e.file_name().to_string_lossy(),
+                read("cpuinfo_min_freq"),
+                read("cpuinfo_max_freq")
+            )
+        })
+        .collect();
+    policies.sort();
+    policies.join(",")
+}
+
+/// `0-3,6` → `[0, 1, 2, 3, 6]`.
+fn parse_cpulist(s: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for part in s.trim().split(',').filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((a, b)) => {
+                if let (Ok(a), Ok(b)) = (a.trim().parse::<usize>(), b.trim().parse::<usize>()) {
+                    cpus.extend(a..=b);
+                }
+            }
+            None => cpus.extend(part.trim().parse::<usize>().ok()),
+        }
+    }
+    cpus
+}
+
+fn derive(root: &Path) -> Vec<ClusterEfficiency> {
+    let Ok(entries) = fs::read_dir(root.join(EM_DIR)) else {
+        debug!(target: "auriya::ceiling", "No energy model in debugfs");
+        return Vec::new();
+    };
+    let mut clusters = Vec::new();
+    for pd in entries.flatten().map(|e| e.path()) {
+        let cpus = parse_cpulist(&fs::read_to_string(pd.join("cpus")).unwrap_or_default());
+        if cpus.is_empty() {
+            continue;
+        }
+        // freq → (power, cost)
+        let mut states: BTreeMap<u64, (u64, Option<u64>)> = BTreeMap::new();
+        for ps in fs::read_dir(&pd).into_iter().flatten().flatten() {
+            let name = ps.file_name().to_string_lossy().into_owned();
+            if !(name.starts_with("ps:") || name.starts_with("cs:")) {
+                continue;
+            }
+            let read = |n: &str| {
+                fs::read_to_string(ps.path().join(n))
+                    .ok()
+                    .and_then(|s| s.trim().parse::<u64>().ok())
+            };
+            if let (Some(freq), Some(power)) = (read("frequency"), read("power")) {
+                states.insert(freq, (power, read("cost")));
+            }
+        }
+        let Some(&max_khz) = states.keys().next_back() else {
+            continue;
+        };
+        // Without a `cost` node, work it out the way the kernel does.
+        let curve: Vec<(u64, f64)> = states
+            .iter()
+            .filter(|(f, _)| **f > 0)
+            .map(|(&f, &(power, cost))| {
+                let cost = cost
+                    .map(|c| c as f64)
+                    .unwrap_or(power as f64 * max_khz as f64 / f as f64);
+                (f, cost)
+            })
+            .collect();
+        if let Some(efficient_khz) = knee(&curve) {
+            clusters.push(ClusterEfficiency {
+                cpus,
+                efficient_khz,
+                max_khz,
+            });
+        }
+    }
+    clusters.sort_by_key(|c| c.cpus.first().copied());
+    clusters
+}
+
+/// Fastest frequency whose cost stays within [`KNEE_TOLERANCE`] of the
+/// minimum; `curve` is `(freq, cost)` ascending by frequency.
+fn knee(curve: &[(u64, f64)]) -> Option<u64> {
+    let min = curve
+        .iter()
+        .map(|&(_, c)| c)
+        .min_by(|a, b| a.total_cmp(b))?;
+    curve
+        .iter()
+        .filter(|&&(_, c)| c <= min * KNEE_TOLERANCE)
+        .map(|&(f, _)| f)
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tweaks::test_util::write;
+
+    #[test]
+    fn derives_knee_per_domain_and_caches_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        // (freq, power, cost): cost bottoms out at 1.2 GHz, 1.5 GHz is
+        // within 10% of it, 1.8 GHz is not.
+        let little = [
+            (300000, 10, 60),
+            (900000, 40, 80),
+            (1200000, 30, 45),
+            (1500000, 40, 48),
+            (1800000, 70, 70),
+        ];
+        for (f, p, c) in little {
+            write(
+                root,
+                &format!("{EM_DIR}/cpu0/ps:{f}/frequency"),
+                &f.to_string(),
+            );
+            write(root, &format!("{EM_DIR}/cpu0/ps:{f}/power"), &p.to_string());
+            write(root, &format!("{EM_DIR}/cpu0/ps:{f}/cost"), &c.to_string());
+        }
+        write(root, &format!("{EM_DIR}/cpu0/cpus"), "0-3\n");
+        // No cost nodes: derived as power * max / freq.
+        for (f, p) in [(800000, 100), (1600000, 215), (2400000, 600)] {
+            write(
+                root,
+                &format!("{EM_DIR}/cpu4/cs:{f}/frequency"),
+                &f.to_string(),
+            );
+            write(root, &format!("{EM_DIR}/cpu4/cs:{f}/power"), &p.to_string());
+        }
+        write(root, &format!("{EM_DIR}/cpu4/cpus"), "4-6,7\n");
+        write(
+            root,
+            &format!("{CPUFREQ_DIR}/policy0/cpuinfo_max_freq"),
+            "1800000",
+        );
+        let cache = root.join("energy_model.json");
+
+        let table = EfficiencyTable::load_or_derive(root, &cache);
+        assert_eq!(table.source, EmSource::Debugfs);
+        assert_eq!(table.efficient_for(2), Some(1500000));
+        assert_eq!(table.efficient_for(7), Some(1600000));
+        assert_eq!(table.clusters[1].cpus, vec![4, 5, 6, 7]);
+        assert_eq!(table.clusters[1].max_khz, 2400000);
+
+        // debugfs gone: the cache answers for the same cpufreq layout only.
+        fs::remove_dir_all(root.join(EM_DIR)).unwrap();
+        let cached = EfficiencyTable::load_or_derive(root, &cache);
+        assert_eq!(cached.source, EmSource::Cache);
+        assert_eq!(cached.clusters, table.clusters);
+
+        write(
+            root,
+            &format!("{CPUFREQ_DIR}/policy0/cpuinfo_max_freq"),
+            "2000000",
+        );
+        let stale = EfficiencyTable::load_or_derive(root, &cache);
+        assert_eq!(stale.source, EmSource::None);
+        assert!(stale.clusters.is_empty());
+    }
+}
@@ -0,0 +1,559 @@
+// Background app freezing for game sessions.
+//
+// While a game has focus, app processes Android already ranks as
+// background or cached (`oom_score_adj` at or above `[freezer] min_adj`)
+// are frozen so they stop competing for CPU, memory bandwidth and
+// storage. Two freezer layouts are handled:
+//
+// - cgroup v2 per-process groups (Android 12+): each app process lives in
+//   `/sys/fs/cgroup/uid_<uid>/pid_<pid>` and is frozen through that
+//   group's `cgroup.freeze`.
+// - Android 11's `/dev/freezer` hierarchy: a process is frozen by moving
+//   it into `frozen/` and thawed by moving it back to the root.
+//
+// ActivityManager's own cached-app freezer drives the same files, so only
+// processes found running are frozen here, and only those are thawed
+// again. The frozen set is mirrored to the state dir, so whatever a
+// daemon that died mid-session (SIGKILL, panic) left frozen is thawed on
+// the next start.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::core::dumpsys::activity::{FIRST_APPLICATION_UID, parse_status_uid};
+use crate::core::tweaks::cgroups::parse_cgroup_v1_path;
+use crate::core::tweaks::memory::{CGROUP_V2_ROOT, parse_cgroup_v2_path};
+
+const FREEZER_ROOT: &str = "dev/freezer";
+const COMPANION_PACKAGE: &str = "dev.auriya.app";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FreezerBackend {
+    /// `cgroup.freeze` in each process's own cgroup v2 group.
+    CgroupV2,
+    /// Android 11's `/dev/freezer/frozen` group.
+    FrozenGroup,
+}
+
+impl FreezerBackend {
+    fn detect_at(root: &Path) -> Option<Self> {
+        let v2 = cgroup_v2_root(root);
+        if root.join(FREEZER_ROOT).join("frozen/cgroup.procs").exists() {
+            Some(Self::FrozenGroup)
+        } else if v2.join("cgroup.freeze").exists() || v2.join("cgroup.controllers").exists() {
+            Some(Self::CgroupV2)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CgroupV2 => "cgroup_v2",
+            Self::FrozenGroup => "dev_freezer",
+        }
+    }
+
+    /// Freeze `pid`. `Ok(false)` when something else already froze it.
+    fn freeze(self, root: &Path, pid: i32) -> Result<bool, String> {
+        match self {
+            Self::CgroupV2 => {
+                let file = per_process_freeze_file(root, pid)?;
+                if fs::read_to_string(&file).is_ok_and(|s| s.trim() == "1") {
+                    return Ok(false);
+                }
+                fs::write(&file, "1").map_err(|e| format!("{}: {e}", file.display()))?;
+                Ok(true)
+            }
+            Self::FrozenGroup => {
+                let cgroup = read_cgroup(root, pid)?;
+                if parse_cgroup_v1_path(&cgroup, "freezer") == Some("/frozen") {
+                    return Ok(false);
+                }
+                let procs = root.join(FREEZER_ROOT).join("frozen/cgroup.procs");
+                fs::write(&procs, pid.to_string())
+                    .map_err(|e| format!("{}: {e}", procs.display()))?;
+                Ok(true)
+            }
+        }
+    }
+
+    fn thaw(self, root: &Path, pid: i32) {
+        match self {
+            Self::CgroupV2 => {
+                if let Ok(file) = per_process_freeze_file(root, pid) {
+                    let _ = fs::write(file, "0");
+                }
+            }
+            Self::FrozenGroup => {
+                let procs = root.join(FREEZER_ROOT).join("cgroup.procs");
+                let _ = fs::write(procs, pid.to_string());
+            }
+        }
+    }
+}
+
+fn cgroup_v2_root(root: &Path) -> PathBuf {
+    root.join(CGROUP_V2_ROOT.trim_start_matches('/'))
+}
+
+fn proc_file(root: &Path, pid: i32, name: &str) -> PathBuf {
+    root.join("proc").join(pid.to_string()).join(name)
+}
+
+fn read_cgroup(root: &Path, pid: i32) -> Result<String, String> {
+    let file = proc_file(root, pid, "cgroup");
+    fs::read_to_string(&file).map_err(|e| format!("{}: {e}", file.display()))
+}
+
+/// `cgroup.freeze` of `pid`'s own group. Freezing a shared group would
+/// take unrelated processes with it, so anything else is refused.
+fn per_process_freeze_file(root: &Path, pid: i32) -> Result<PathBuf, String> {
+    let cgroup = read_cgroup(root, pid)?;
+    let path = parse_cgroup_v2_path(&cgroup).ok_or("no cgroup v2 group")?;
+    if !is_per_process_group(path, pid) {
+        return Err(format!("shared cgroup {path}"));
+    }
+    Ok(cgroup_v2_root(root)
+        .join(path.trim_start_matches('/'))
+        .join("cgroup.freeze"))
+}
+
+fn is_per_process_group(path: &str, pid: i32) -> bool {
+    path.rsplit('/').next() == Some(format!("pid_{pid}").as_str())
+}
+
+/// Package an app process runs for: argv0 up to any `:<process>` suffix.
+/// `None` for native binaries and processes without a cmdline.
+fn package_of(cmdline: &[u8]) -> Option<&str> {
+    let argv0 = cmdline.split(|&b| b == 0).next()?;
+    let pkg = std::str::from_utf8(argv0).ok()?.split(':').next()?;
+    (pkg.contains('.') && !pkg.contains('/')).then_some(pkg)
+}
+
+/// Package of the running process `pid`, if it is an app.
+fn running_package(root: &Path, pid: i32) -> Option<String> {
+    let cmdline = fs::read(proc_file(root, pid, "cmdline")).ok()?;
+    package_of(&cmdline).map(str::to_string)
+}
+
+/// App processes ranked at or above `min_adj`, as (pid, package).
+fn background_apps(root: &Path, min_adj: i32) -> Vec<(i32, String)> {
+    let Ok(entries) = fs::read_dir(root.join("proc")) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|n| n.parse::<i32>().ok())
+        else {
+            continue;
+        };
+        if oom_score_adj(root, pid).is_none_or(|adj| adj < min_adj) {
+            continue;
+        }
+        let is_app = fs::read_to_string(proc_file(root, pid, "status"))
+            .ok()
+            .and_then(|s| parse_status_uid(&s))
+            .is_some_and(|uid| uid >= FIRST_APPLICATION_UID);
+        if !is_app {
+            continue;
+        }
+        if let Some(pkg) = running_package(root, pid) {
+            out.push((pid, pkg));
+        }
+    }
+    out
+}
+
+fn oom_score_adj(root: &Path, pid: i32) -> Option<i32> {
+    fs::read_to_string(proc_file(root, pid, "oom_score_adj"))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Package of the current keyboard, which must keep answering while a
+/// game has a text field up. Spawns `settings`, so it is only run off the
+/// tick thread (see [`AppFreezer::refresh_ime`]).
+fn default_ime_package() -> Option<String> {
+    let out = std::process::Command::new("settings")
+        .args(["get", "secure", "default_input_method"])
+        .output()
+        .ok()?;
+    let ime = String::from_utf8_lossy(&out.stdout);
+    let pkg = ime.trim().split('/').next()?;
+    (!pkg.is_empty() && pkg != "null").then(|| pkg.to_string())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrozenApp {
+    pub pid: i32,
+    pub pkg: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FreezeFailure {
+    pub pid: i32,
+    pub pkg: String,
+    pub error: String,
+}
+
+/// Freezes background apps for a game session and thaws exactly what it
+/// froze. [`AppFreezer::freeze_background`] is repeated during the session
+/// to catch apps that drop to the background later and to let go of ones
+/// Android promotes again (a notification, music starting).
+#[derive(Debug, Default)]
+pub struct AppFreezer {
+    /// Filesystem root; `/` outside tests.
+    root: PathBuf,
+    /// Where the frozen set is mirrored.
+    state: PathBuf,
+    backend: Option<FreezerBackend>,
+    active: bool,
+    /// Keyboard package as last read in the background.
+    ime: Arc<Mutex<Option<String>>>,
+    /// pid → package, for processes frozen by us.
+    frozen: BTreeMap<i32, String>,
+    /// Processes that failed to freeze this session; not retried.
+    failed: BTreeMap<i32, (String, String)>,
+}
+
+impl AppFreezer {
+    pub fn new() -> Self {
+        Self::new_at(Path::new("/"), crate::core::config::frozen_apps_path())
+    }
+
+    /// Freezer over the tree at `root`, mirroring to `state`. Thaws
+    /// whatever a previous daemon left frozen there.
+    fn new_at(root: &Path, state: PathBuf) -> Self {
+        let freezer = Self {
+            root: root.to_path_buf(),
+            state,
+            backend: FreezerBackend::detect_at(root),
+            ..Self::default()
+        };
+        if freezer.backend.is_some() {
+            freezer.thaw_stale();
+            freezer.refresh_ime();
+        }
+        freezer
+    }
+
+    /// Thaw the processes the last daemon recorded as frozen, where the
+    /// same package still runs under that PID.
+    fn thaw_stale(&self) {
+        let (Some(backend), Ok(json)) = (self.backend, fs::read_to_string(&self.state)) else {
+            return;
+        };
+        let stale: Vec<FrozenApp> = serde_json::from_str(&json).unwrap_or_default();
+        let mut n = 0;
+        for app in stale {
+            if running_package(&self.root, app.pid).as_deref() == Some(app.pkg.as_str()) {
+                backend.thaw(&self.root, app.pid);
+                n += 1;
+            }
+        }
+        let _ = fs::remove_file(&self.state);
+        if n > 0 {
+            info!(target: "auriya::freezer", "Thawed {} processes left frozen by the last run", n);
+        }
+    }
+
+    /// Mirror the frozen set to the state dir; nothing on disk when empty.
+    fn persist(&self) {
+        if self.frozen.is_empty() {
+            let _ = fs::remove_file(&self.state);
+            return;
+        }
+        let result = serde_json::to_string(&self.frozen())
+            .map_err(|e| e.to_string())
+            .and_then(|json| fs::write(&self.state, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            warn!(target: "auriya::freezer", "Failed to record frozen apps at {}: {}", self.state.display(), e);
+        }
+    }
+
+    /// Re-read the keyboard package on a helper thread; sessions use
+    /// whatever was read last, so a keyboard switch counts from the
+    /// session after. A failed read keeps the previous package.
+    fn refresh_ime(&self) {
+        let ime = Arc::clone(&self.ime);
+        std::thread::spawn(move || {
+            if let Some(pkg) = default_ime_package()
+                && let Ok(mut ime) = ime.lock()
+            {
+                *ime = Some(pkg);
+            }
+        });
+    }
+
+    pub fn backend(&self) -> Option<FreezerBackend> {
+        self.backend
+    }
+
+    /// A session freeze is in force.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn frozen(&self) -> Vec<FrozenApp> {
+        self.frozen
+            .iter()
+            .map(|(&pid, pkg)| FrozenApp {
+                pid,
+                pkg: pkg.clone(),
+            })
+            .collect()
+    }
+
+    pub fn failures(&self) -> Vec<FreezeFailure> {
+        self.failed
+            .iter()
+            .map(|(&pid, (pkg, error))| FreezeFailure {
+                pid,
+                pkg: pkg.clone(),
+                error: error.clone(),
+            })
+            .collect()
+    }
+
+    /// Freeze every app process at or above `min_adj` except the game's
+    /// own (`keep_pids`), packages in `allowlist`, the keyboard and the
+    /// companion. Processes already frozen by us that have since been
+    /// promoted below `min_adj`, or allowlisted, are thawed.
+    pub fn freeze_background(&mut self, keep_pids: &[i32], allowlist: &[String], min_adj: i32) {
+        let Some(backend) = self.backend else {
+            return;
+        };
+        if !self.active {
+            self.active = true;
+            self.failed.clear();
+            self.refresh_ime();
+        }
+        let ime = self.ime.lock().ok().and_then(|i| i.clone());
+        let exempt = |pkg: &str| {
+            pkg == COMPANION_PACKAGE
+                || allowlist.iter().any(|a| a == pkg)
+                || ime.as_deref() == Some(pkg)
+        };
+
+        let released: Vec<i32> = self
+            .frozen
+            .iter()
+            .filter(|&(&pid, pkg)| {
+                exempt(pkg) || oom_score_adj(&self.root, pid).is_none_or(|adj| adj < min_adj)
+            })
+            .map(|(&pid, _)| pid)
+            .collect();
+        let changed = !released.is_empty();
+        for pid in released {
+            if let Some(pkg) = self.frozen.remove(&pid) {
+                backend.thaw(&self.root, pid);
+                debug!(target: "auriya::freezer", "Thawed {} (PID {}): promoted or exempt", pkg, pid);
+            }
+        }
+
+        let mut newly = 0;
+        for (pid, pkg) in background_apps(&self.root, min_adj) {
+            if keep_pids.contains(&pid)
+                || exempt(&pkg)
+                || self.frozen.contains_key(&pid)
+                || self.failed.contains_key(&pid)
+            {
+                continue;
+            }
+            match backend.freeze(&self.root, pid) {
+                Ok(true) => {
+                    self.frozen.insert(pid, pkg);
+                    newly += 1;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    debug!(target: "auriya::freezer", "Failed to freeze {} (PID {}): {}", pkg, pid, e);
+                    self.failed.insert(pid, (pkg, e));
+                }
+            }
+        }
+        if newly > 0 {
+            info!(target: "auriya::freezer", "Froze {} background processes ({} total)", newly, self.frozen.len());
+        }
+        if changed || newly > 0 {
+            self.persist();
+        }
+    }
+
+    /// Thaw everything frozen by us and end the session freeze.
+    pub fn thaw_all(&mut self) {
+        if !self.active {
+            return;
+        }
+        self.active = false;
+        let Some(backend) = self.backend else {
+            return;
+        };
+        let n = self.frozen.len();
+        for (pid, _) in std::mem::take(&mut self.frozen) {
+            backend.thaw(&self.root, pid);
+        }
+        self.persist();
+        if n > 0 {
+            info!(target: "auriya::freezer", "Thawed {} background processes", n);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tweaks::test_util::{read, write};
+
+    #[test]
+    fn reads_package_and_per_process_group() {
+        assert_eq!(
+            package_of(b"com.spotify.music\0"),
+            Some("com.spotify.music")
+        );
+        assert_eq!(
+            package_of(b"com.discord:voice\0--flag\0"),
+            Some("com.discord")
+        );
+        assert_eq!(package_of(b"/system/bin/surfaceflinger\0"), None);
+        assert_eq!(package_of(b""), None);
+
+        assert!(is_per_process_group("/uid_10234/pid_4242", 4242));
+        assert!(!is_per_process_group("/uid_10234/pid_42421", 4242));
+        assert!(!is_per_process_group("/uid_10234", 4242));
+    }
+
+    /// App process `pid` of `pkg` with `adj`, in `cgroup`.
+    fn process(root: &Path, pid: i32, pkg: &str, uid: u32, adj: i32, cgroup: &str) {
+        write(root, &format!("proc/{pid}/cmdline"), &format!("{pkg}\0"));
+        write(
+            root,
+            &format!("proc/{pid}/status"),
+            &format!("Uid:\t{uid}\t{uid}\t{uid}\t{uid}\n"),
+        );
+        write(
+            root,
+            &format!("proc/{pid}/oom_score_adj"),
+            &format!("{adj}\n"),
+        );
+        write(root, &format!("proc/{pid}/cgroup"), cgroup);
+    }
+
+    fn v2_process(root: &Path, pid: i32, pkg: &str, uid: u32, adj: i32) {
+        let group = format!("uid_{uid}/pid_{pid}");
+        process(root, pid, pkg, uid, adj, &format!("0::/{group}\n"));
+        write(root, &format!("sys/fs/cgroup/{group}/cgroup.freeze"), "0\n");
+    }
+
+    fn frozen(root: &Path, uid: u32, pid: i32) -> bool {
+        read(
+            root,
+            &format!("sys/fs/cgroup/uid_{uid}/pid_{pid}/cgroup.freeze"),
+        ) == "1"
+    }
+
+    #[test]
+    fn freezes_background_and_thaws_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let state = root.join("frozen.json");
+        write(root, "sys/fs/cgroup/cgroup.controllers", "cpu memory\n");
+        v2_process(root, 100, "com.bg.app", 10100, 900);
+        v2_process(root, 101, "com.spotify.music", 10101, 900);
+        v2_process(root, 102, "com.ime.keyboard", 10102, 900);
+        v2_process(root, 103, COMPANION_PACKAGE, 10103, 900);
+        v2_process(root, 104, "com.game", 10104, 900);
+        v2_process(root, 105, "com.visible", 10105, 0);
+        v2_process(root, 106, "com.android.system", 1000, 900);
+
+        let mut freezer = AppFreezer::new_at(root, state.clone());
+        assert_eq!(freezer.backend(), Some(FreezerBackend::CgroupV2));
+        freezer.ime = Arc::new(Mutex::new(Some("com.ime.keyboard".into())));
+        let allowlist = vec!["com.spotify.music".to_string()];
+        freezer.freeze_background(&[104], &allowlist, 700);
+
+        assert!(frozen(root, 10100, 100));
+        for (uid, pid) in [
+            (10101, 101),
+            (10102, 102),
+            (10103, 103),
+            (10104, 104),
+            (10105, 105),
+            (1000, 106),
+        ] {
+            assert!(!frozen(root, uid, pid), "pid {pid} frozen");
+        }
+        assert!(fs::read_to_string(&state).unwrap().contains("com.bg.app"));
+
+        // Promoted above min_adj: let go mid-session.
+        write(root, "proc/100/oom_score_adj", "200\n");
+        freezer.freeze_background(&[104], &allowlist, 700);
+        assert!(!frozen(root, 10100, 100));
+        assert!(!state.exists());
+
+        write(root, "proc/100/oom_score_adj", "900\n");
+        freezer.freeze_background(&[104], &allowlist, 700);
+        assert!(frozen(root, 10100, 100));
+        freezer.thaw_all();
+        assert!(!frozen(root, 10100, 100));
+        assert!(!state.exists());
+
+        // A daemon killed mid-session: the next one thaws what it left,
+        // but not a PID since reused by another package.
+        freezer.freeze_background(&[104], &allowlist, 700);
+        drop(freezer);
+        fs::write(
+            &state,
+            r#"[{"pid":100,"pkg":"com.bg.app"},{"pid":105,"pkg":"com.gone"}]"#,
+        )
+        .unwrap();
+        write(root, "sys/fs/cgroup/uid_10105/pid_105/cgroup.freeze", "1\n");
+        let _restarted = AppFreezer::new_at(root, state.clone());
+        assert!(!frozen(root, 10100, 100));
+        assert!(frozen(root, 10105, 105));
+        assert!(!state.exists());
+    }
+
+    #[test]
+    fn dev_freezer_moves_processes_and_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "dev/freezer/frozen/cgroup.procs", "");
+        write(root, "dev/freezer/cgroup.procs", "");
+        process(root, 200, "com.bg.app", 10200, 900, "4:freezer:/\n0::/\n");
+        process(
+            root,
+            201,
+            "com.frozen.by.android",
+            10201,
+            900,
+            "4:freezer:/frozen\n",
+        );
+
+        let mut freezer = AppFreezer::new_at(root, root.join("frozen.json"));
+        assert_eq!(freezer.backend(), Some(FreezerBackend::FrozenGroup));
+        freezer.freeze_background(&[], &[], 700);
+        assert_eq!(read(root, "dev/freezer/frozen/cgroup.procs"), "200");
+        assert_eq!(
+            freezer.frozen(),
+            vec![FrozenApp {
+                pid: 200,
+                pkg: "com.bg.app".into()
+            }]
+        );
+
+        freezer.thaw_all();
+        assert_eq!(read(root, "dev/freezer/cgroup.procs"), "200");
+    }
+}
@@ -1,132 +1,603 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
 use tracing::debug;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum GpuVendor {
-    Adreno,
-    Mali,
-    Unknown,
-}
+use crate::core::config::settings::FreqValue;
+use crate::core::tweaks::saved::SavedNodes;
 
-pub fn detect_vendor() -> GpuVendor {
-    if Path::new("/sys/class/kgsl/kgsl-3d0").exists() {
-        GpuVendor::Adreno
-    } else if Path::new("/proc/gpufreq/gpufreq_opp_freq").exists()
-        || Path::new("/proc/gpufreqv2/fix_target_opp_index").exists()
-    {
-        GpuVendor::Mali
-    } else {
-        GpuVendor::Unknown
-    }
+/// Floor steps between the device's own minimum (level 0) and the top
+/// of the frequency table.
+pub const GPU_LEVELS: usize = 4;
+
+static ACTUATOR: LazyLock<Mutex<GpuActuator>> = LazyLock::new(|| Mutex::new(GpuActuator::detect()));
+
+fn with_actuator<T>(f: impl FnOnce(&mut GpuActuator) -> T) -> Option<T> {
+    ACTUATOR.lock().ok().map(|mut a| f(&mut a))
 }
 
+/// Keep the GPU clocks and power rails up; the floor itself is left to
+/// the FAS boost steps.
 pub fn set_performance_mode() -> Result<()> {
-    match detect_vendor() {
-        GpuVendor::Adreno => set_adreno_performance()?,
-        GpuVendor::Mali => set_mali_performance()?,
-        GpuVendor::Unknown => {
-            debug!("GPU vendor unknown, skipping GPU tweak");
-            return Ok(());
-        }
+    if with_actuator(GpuActuator::hold_power) != Some(true) {
+        debug!("GPU vendor unknown, skipping GPU tweak");
+        return Ok(());
     }
 
     debug!("GPU performance mode enabled");
     Ok(())
 }
 
-fn set_adreno_performance() -> Result<()> {
-    let base = "/sys/class/kgsl/kgsl-3d0";
+/// Drop the floor and put back the power nodes performance mode changed.
+pub fn set_balanced_mode() -> Result<()> {
+    with_actuator(|a| {
+        a.release();
+        a.release_power();
+    });
 
-    let max_freq_path = format!("{}/devfreq/max_freq", base);
-    if let Ok(max_freq) = fs::read_to_string(&max_freq_path) {
-        let max_freq = max_freq.trim();
+    debug!("GPU set to balanced mode");
+    Ok(())
+}
 
-        let min_freq_path = format!("{}/devfreq/min_freq", base);
-        fs::write(&min_freq_path, max_freq).context("Cannot lock GPU to max freq")?;
+/// Raise the GPU floor one level; returns the new level.
+pub fn step_up() -> usize {
+    with_actuator(GpuActuator::raise).unwrap_or(0)
+}
 
-        debug!("Adreno GPU locked at max freq: {} Hz", max_freq);
-    }
+/// Lower the GPU floor one level; returns the new level.
+pub fn step_down() -> usize {
+    with_actuator(GpuActuator::lower).unwrap_or(0)
+}
 
-    let _ = fs::write(format!("{}/force_clk_on", base), "1");
-    let _ = fs::write(format!("{}/force_bus_on", base), "1");
-    let _ = fs::write(format!("{}/bus_split", base), "0");
+/// Switch the GPU devfreq governor, or put the original back on `None`.
+pub fn set_governor(governor: Option<&str>) {
+    with_actuator(|a| a.set_governor(governor));
+}
 
-    Ok(())
+/// Cap the GPU for a ceiling level; `None` lifts it.
+pub fn set_ceiling(max: Option<&FreqValue>) {
+    with_actuator(|a| a.set_cap(max));
 }
 
-fn set_mali_performance() -> Result<()> {
-    if Path::new("/proc/gpufreqv2/fix_target_opp_index").exists() {
-        let _ = fs::write("/proc/gpufreqv2/fix_target_opp_index", "0"); // 0 usually means max freq index
-    } else if Path::new("/proc/gpufreq/gpufreq_opp_freq").exists() {
-    }
+/// Put back the original limits and governor.
+pub fn restore() {
+    with_actuator(GpuActuator::restore);
+}
 
-    // Exynos/Tensor Mali: always_on eliminates GPU wake-up latency
-    set_mali_power_policy("always_on");
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Backend {
+    /// A min/max frequency node pair: devfreq's `min_freq`/`max_freq`
+    /// (with a `governor` beside them), the Mali platform device's
+    /// `scaling_*` nodes (Tensor) or Samsung's `/sys/kernel/gpu/gpu_*_clock`.
+    Limits { min: PathBuf, max: PathBuf },
+    /// MediaTek gpufreq OPP lock. gpufreqv2 takes an OPP index (0 is the
+    /// fastest, -1 unlocks), gpufreq v1 a frequency (0 unlocks).
+    MtkOpp { node: PathBuf, by_index: bool },
+}
 
-    Ok(())
+/// Steps the GPU minimum frequency through [`GPU_LEVELS`] floors taken
+/// from the device's frequency table. Level 0 and
+/// [`GpuActuator::restore`] put back whatever the kernel had.
+#[derive(Debug, Default)]
+pub struct GpuActuator {
+    backend: Option<Backend>,
+    /// Frequency table, ascending.
+    freqs: Vec<u64>,
+    level: usize,
+    /// Ceiling-imposed top frequency, snapped to the table.
+    cap: Option<u64>,
+    governor: Option<String>,
+    /// Clock, bus and power-policy nodes performance mode holds, with the
+    /// value it holds them at.
+    power: Vec<(PathBuf, &'static str)>,
+    saved: SavedNodes,
 }
 
-pub fn set_balanced_mode() -> Result<()> {
-    match detect_vendor() {
-        GpuVendor::Adreno => {
-            let base = "/sys/class/kgsl/kgsl-3d0";
+impl GpuActuator {
+    pub fn detect() -> Self {
+        let actuator = Self::detect_at(Path::new("/"));
+        if let Some(backend) = &actuator.backend {
+            debug!(
+                "GPU actuator: {:?}, {} frequencies",
+                backend,
+                actuator.freqs.len()
+            );
+        }
+        actuator
+    }
 
-            let _ = fs::write(format!("{}/devfreq/governor", base), "msm-adreno-tz");
+    fn detect_at(root: &Path) -> Self {
+        Self {
+            power: power_nodes(root),
+            ..Self::detect_limits(root)
+        }
+    }
 
-            let min_freq_path = format!("{}/devfreq/min_freq", base);
-            if let Ok(avail) = fs::read_to_string(format!("{}/devfreq/available_frequencies", base))
-                && let Some(min) = avail.split_whitespace().next()
-            {
-                let _ = fs::write(&min_freq_path, min);
+    fn detect_limits(root: &Path) -> Self {
+        let mut devfreq = vec![root.join("sys/class/kgsl/kgsl-3d0/devfreq")];
+        let mut mali = Vec::new();
+        for (dir, nested) in [("sys/class/devfreq", false), ("sys/devices/platform", true)] {
+            let Ok(entries) = fs::read_dir(root.join(dir)) else {
+                continue;
+            };
+            let mut found: Vec<PathBuf> = entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| {
+                    let name = p.to_string_lossy().to_lowercase();
+                    if nested {
+                        name.ends_with(".mali")
+                    } else {
+                        ["kgsl", "mali", "gpu"].iter().any(|k| name.contains(k))
+                    }
+                })
+                .collect();
+            found.sort();
+            for p in found {
+                if nested {
+                    if let Ok(sub) = fs::read_dir(p.join("devfreq")) {
+                        devfreq.extend(sub.flatten().map(|e| e.path()));
+                    }
+                    mali.push(p);
+                } else {
+                    devfreq.push(p);
+                }
             }
-
-            let _ = fs::write(format!("{}/force_clk_on", base), "0");
-            let _ = fs::write(format!("{}/force_bus_on", base), "0");
-            let _ = fs::write(format!("{}/bus_split", base), "1");
         }
-        GpuVendor::Mali => {
-            if Path::new("/proc/gpufreqv2/fix_target_opp_index").exists() {
-                let _ = fs::write("/proc/gpufreqv2/fix_target_opp_index", "-1"); // -1 unlocks
+        let mut limits: Vec<(PathBuf, &str, &str, &str)> = devfreq
+            .into_iter()
+            .map(|dir| (dir, "min_freq", "max_freq", "available_frequencies"))
+            .collect();
+        limits.extend(mali.into_iter().map(|dir| {
+            (
+                dir,
+                "scaling_min_freq",
+                "scaling_max_freq",
+                "available_frequencies",
+            )
+        }));
+        let kernel_gpu = root.join("sys/kernel/gpu");
+        for table in ["gpu_available_frequencies", "gpu_freq_table"] {
+            limits.push((kernel_gpu.clone(), "gpu_min_clock", "gpu_max_clock", table));
+        }
+        for (dir, min, max, table) in limits {
+            let freqs = frequency_table(&fs::read_to_string(dir.join(table)).unwrap_or_default());
+            if !freqs.is_empty() && dir.join(min).exists() && dir.join(max).exists() {
+                return Self {
+                    backend: Some(Backend::Limits {
+                        min: dir.join(min),
+                        max: dir.join(max),
+                    }),
+                    freqs,
+                    ..Self::default()
+                };
             }
-            // coarse_demand lets GPU idle between frames
-            set_mali_power_policy("coarse_demand");
         }
-        GpuVendor::Unknown => {
-            debug!("GPU vendor unknown, skipping restore");
+
+        for (node, table, by_index) in [
+            (
+                "proc/gpufreqv2/fix_target_opp_index",
+                "proc/gpufreqv2/gpu_working_opp_table",
+                true,
+            ),
+            (
+                "proc/gpufreq/gpufreq_opp_freq",
+                "proc/gpufreq/gpufreq_opp_dump",
+                false,
+            ),
+        ] {
+            let node = root.join(node);
+            let freqs = parse_opp_table(&fs::read_to_string(root.join(table)).unwrap_or_default());
+            if node.exists() && !freqs.is_empty() {
+                return Self {
+                    backend: Some(Backend::MtkOpp { node, by_index }),
+                    freqs,
+                    ..Self::default()
+                };
+            }
         }
+        Self::default()
     }
 
-    debug!("GPU set to balanced mode");
-    Ok(())
-}
+    pub fn raise(&mut self) -> usize {
+        self.set_level(self.level + 1);
+        self.level
+    }
 
-fn set_mali_power_policy(policy: &str) {
-    // Try both common Mali power_policy paths
-    let candidates = [
-        "/sys/devices/platform/13000000.mali/power_policy",
-        "/sys/devices/platform/1c000000.mali/power_policy",
-        "/sys/class/misc/mali0/device/power_policy",
-    ];
+    pub fn lower(&mut self) -> usize {
+        self.set_level(self.level.saturating_sub(1));
+        self.level
+    }
+
+    /// Back to level 0: the original limits, governor untouched.
+    pub fn release(&mut self) {
+        self.set_level(0);
+    }
+
+    fn set_level(&mut self, level: usize) {
+        let level = level.min(GPU_LEVELS);
+        let Some(backend) = self.backend.clone() else {
+            return;
+        };
+        if level == self.level {
+            return;
+        }
+        self.level = level;
+        self.write_limits(&backend);
+    }
+
+    fn write_limits(&mut self, backend: &Backend) {
+        let level = self.level;
+        if level == 0 {
+            match backend {
+                Backend::Limits { min, max } => {
+                    // Min first so the restored max never sits under it.
+                    self.saved.restore(min);
+                    match self.cap {
+                        Some(cap) => self.write_saved(max.clone(), &cap.to_string()),
+                        None => self.saved.restore(max),
+                    }
+                }
+                Backend::MtkOpp { node, .. } => self.saved.restore(node),
+            }
+            debug!("GPU floor released");
+            return;
+        }
+        let Some(&top) = self.freqs.last() else {
+            return;
+        };
+        let top = self.cap.unwrap_or(top);
+        let idx = (self.freqs.len() - 1) * level / GPU_LEVELS;
+        // Under a cap the floor can't climb past it.
+        let idx = self.freqs[..=idx]
+            .iter()
+            .rposition(|&f| f <= top)
+            .unwrap_or(0);
+        let floor = self.freqs[idx];
+        match backend {
+            Backend::Limits { min, max } => {
+                // Max, min, max: whichever way the window moves, one of
+                // the max writes lands while min is compatible with it.
+                self.write_saved(max.clone(), &top.to_string());
+                self.write_saved(min.clone(), &floor.to_string());
+                self.write_saved(max.clone(), &top.to_string());
+            }
+            Backend::MtkOpp { node, by_index } => {
+                let value = if *by_index {
+                    (self.freqs.len() - 1 - idx).to_string()
+                } else {
+                    floor.to_string()
+                };
+                self.write_saved(node.clone(), &value);
+            }
+        }
+        debug!("GPU floor level {}/{}: {}", level, GPU_LEVELS, floor);
+    }
+
+    /// Cap the GPU at `max`, resolved against the top of the frequency
+    /// table and snapped down onto it; `None` lifts the cap. The OPP
+    /// backend has no separate max, so there the cap only bounds the
+    /// floor.
+    pub fn set_cap(&mut self, max: Option<&FreqValue>) {
+        let Some(backend) = self.backend.clone() else {
+            return;
+        };
+        let top = self.freqs.last().copied().unwrap_or(0);
+        let cap = max.and_then(|m| m.resolve(top)).map(|v| {
+            self.freqs
+                .iter()
+                .rev()
+                .find(|&&f| f <= v)
+                .or(self.freqs.first())
+                .copied()
+                .unwrap_or(v)
+        });
+        if cap == self.cap {
+            return;
+        }
+        self.cap = cap;
+        self.write_limits(&backend);
+        debug!("GPU cap: {:?}", cap);
+    }
 
-    for path in &candidates {
-        if Path::new(path).exists() {
-            let _ = fs::write(path, policy);
-            debug!("Mali power_policy set to {} via {}", policy, path);
+    pub fn set_governor(&mut self, governor: Option<&str>) {
+        let Some(Backend::Limits { min, .. }) = &self.backend else {
+            return;
+        };
+        let Some(dir) = min.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        let node = dir.join("governor");
+        if self.governor.as_deref() == governor || !node.exists() {
             return;
         }
+        match governor {
+            Some(g) => {
+                let available =
+                    fs::read_to_string(dir.join("available_governors")).unwrap_or_default();
+                if !available.is_empty() && !available.split_whitespace().any(|a| a == g) {
+                    debug!("GPU governor {} not offered ({})", g, available.trim());
+                } else {
+                    self.write_saved(node, g);
+                    debug!("GPU governor set to {}", g);
+                }
+            }
+            None => self.saved.restore(&node),
+        }
+        self.governor = governor.map(str::to_string);
     }
 
-    // Broader glob-style scan for Mali power_policy
-    if let Ok(entries) = fs::read_dir("/sys/devices/platform") {
-        for entry in entries.flatten() {
-            let mali = entry.path().join("power_policy");
-            if mali.exists() {
-                let _ = fs::write(&mali, policy);
-                debug!("Mali power_policy set to {} via {}", policy, mali.display());
-                return;
+    /// Hold the GPU power nodes at their performance values; `false` when
+    /// the GPU has none.
+    pub fn hold_power(&mut self) -> bool {
+        for (node, value) in &self.power {
+            let Ok(text) = fs::read_to_string(node) else {
+                continue;
+            };
+            let current = selected(&text);
+            if current != *value && fs::write(node, value).is_ok() {
+                self.saved.record(node.clone(), current);
+                debug!("GPU {} set to {}", node.display(), value);
             }
         }
+        !self.power.is_empty()
+    }
+
+    /// Put back what [`GpuActuator::hold_power`] replaced.
+    pub fn release_power(&mut self) {
+        for (node, _) in &self.power {
+            self.saved.restore(node);
+        }
+    }
+
+    pub fn restore(&mut self) {
+        self.set_governor(None);
+        self.set_cap(None);
+        self.release();
+        self.release_power();
+    }
+
+    fn write_saved(&mut self, node: PathBuf, value: &str) {
+        self.saved.write(&node, value);
+    }
+}
+
+/// Adreno's always-on clock and bus nodes, or else the Mali power policy.
+fn power_nodes(root: &Path) -> Vec<(PathBuf, &'static str)> {
+    let kgsl = root.join("sys/class/kgsl/kgsl-3d0");
+    if kgsl.exists() {
+        return [
+            ("force_clk_on", "1"),
+            ("force_bus_on", "1"),
+            ("bus_split", "0"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (kgsl.join(name), value))
+        .filter(|(node, _)| node.exists())
+        .collect();
+    }
+    // always_on eliminates the Mali wake-up latency between frames.
+    mali_power_policy(root)
+        .map(|node| vec![(node, "always_on")])
+        .unwrap_or_default()
+}
+
+fn mali_power_policy(root: &Path) -> Option<PathBuf> {
+    let candidates = [
+        "sys/devices/platform/13000000.mali/power_policy",
+        "sys/devices/platform/1c000000.mali/power_policy",
+        "sys/class/misc/mali0/device/power_policy",
+    ];
+    if let Some(node) = candidates.iter().map(|c| root.join(c)).find(|n| n.exists()) {
+        return Some(node);
+    }
+    let mut found: Vec<PathBuf> = fs::read_dir(root.join("sys/devices/platform"))
+        .ok()?
+        .flatten()
+        .map(|e| e.path().join("power_policy"))
+        .filter(|n| n.exists())
+        .collect();
+    found.sort();
+    found.into_iter().next()
+}
+
+/// The active choice of a `[selected] other` node, or the whole value.
+fn selected(text: &str) -> String {
+    text.split_whitespace()
+        .find_map(|w| w.strip_prefix('[')?.strip_suffix(']'))
+        .map(str::to_string)
+        .unwrap_or_else(|| text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn frequency_table(text: &str) -> Vec<u64> {
+    let mut freqs: Vec<u64> = text
+        .split_whitespace()
+        .filter_map(|x| x.parse().ok())
+        .collect();
+    freqs.sort_unstable();
+    freqs.dedup();
+    freqs
+}
+
+/// Frequencies from a gpufreq OPP dump, one `[n] freq = 880000, volt
+/// = ...` (v1) or `[n] freq: 880000, ...` (v2) line per OPP.
+fn parse_opp_table(text: &str) -> Vec<u64> {
+    let freqs: Vec<u64> = text
+        .lines()
+        .filter_map(|line| {
+            let rest = &line[line.find("freq")? + 4..];
+            let digits: String = rest
+                .trim_start_matches(|c: char| !c.is_ascii_digit())
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect();
+            digits.parse().ok()
+        })
+        .collect();
+    frequency_table(
+        &freqs
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tweaks::test_util::{read, write};
+
+    #[test]
+    fn steps_devfreq_floor_and_restores_limits_and_governor() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let df = "sys/class/kgsl/kgsl-3d0/devfreq";
+        write(
+            root,
+            &format!("{df}/available_frequencies"),
+            "900000000 680000000 545000000 443000000 315000000\n",
+        );
+        write(root, &format!("{df}/min_freq"), "315000000\n");
+        write(root, &format!("{df}/max_freq"), "680000000\n");
+        write(root, &format!("{df}/governor"), "msm-adreno-tz\n");
+        write(
+            root,
+            &format!("{df}/available_governors"),
+            "msm-adreno-tz performance powersave\n",
+        );
+
+        let mut gpu = GpuActuator::detect_at(root);
+        assert_eq!(gpu.raise(), 1);
+        assert_eq!(read(root, &format!("{df}/min_freq")), "443000000");
+        assert_eq!(read(root, &format!("{df}/max_freq")), "900000000");
+        gpu.raise();
+        assert_eq!(read(root, &format!("{df}/min_freq")), "545000000");
+        assert_eq!(gpu.lower(), 1);
+        assert_eq!(read(root, &format!("{df}/min_freq")), "443000000");
+        gpu.set_level(GPU_LEVELS);
+        assert_eq!(read(root, &format!("{df}/min_freq")), "900000000");
+        assert_eq!(gpu.raise(), GPU_LEVELS);
+
+        // 70% of 900 MHz snaps down to 545 MHz and drags the floor along.
+        gpu.set_cap(Some(&FreqValue::Spec("70%".into())));
+        assert_eq!(read(root, &format!("{df}/max_freq")), "545000000");
+        assert_eq!(read(root, &format!("{df}/min_freq")), "545000000");
+        gpu.release();
+        assert_eq!(read(root, &format!("{df}/min_freq")), "315000000");
+        assert_eq!(read(root, &format!("{df}/max_freq")), "545000000");
+        gpu.set_cap(None);
+        assert_eq!(read(root, &format!("{df}/max_freq")), "680000000");
+        gpu.set_level(GPU_LEVELS);
+
+        gpu.set_governor(Some("nonexistent"));
+        assert_eq!(read(root, &format!("{df}/governor")), "msm-adreno-tz");
+        gpu.set_governor(Some("performance"));
+        assert_eq!(read(root, &format!("{df}/governor")), "performance");
+
+        gpu.restore();
+        assert_eq!(gpu.level, 0);
+        assert_eq!(read(root, &format!("{df}/min_freq")), "315000000");
+        assert_eq!(read(root, &format!("{df}/max_freq")), "680000000");
+        assert_eq!(read(root, &format!("{df}/governor")), "msm-adreno-tz");
+    }
+
+    #[test]
+    fn drives_mali_scaling_and_samsung_gpu_nodes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let mali = "sys/devices/platform/1f000000.mali";
+        write(
+            root,
+            &format!("{mali}/available_frequencies"),
+            "151000 302000 555000 848000\n",
+        );
+        write(root, &format!("{mali}/scaling_min_freq"), "151000\n");
+        write(root, &format!("{mali}/scaling_max_freq"), "848000\n");
+
+        let mut gpu = GpuActuator::detect_at(root);
+        gpu.set_level(GPU_LEVELS);
+        assert_eq!(read(root, &format!("{mali}/scaling_min_freq")), "848000");
+        // No devfreq governor beside the scaling nodes.
+        gpu.set_governor(Some("performance"));
+        assert!(!root.join(mali).join("governor").exists());
+        gpu.restore();
+        assert_eq!(read(root, &format!("{mali}/scaling_min_freq")), "151000");
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "sys/kernel/gpu/gpu_freq_table",
+            "897000 800000 702000 572000 455000 377000 260000\n",
+        );
+        write(root, "sys/kernel/gpu/gpu_min_clock", "260000\n");
+        write(root, "sys/kernel/gpu/gpu_max_clock", "800000\n");
+
+        let mut gpu = GpuActuator::detect_at(root);
+        gpu.set_level(GPU_LEVELS);
+        assert_eq!(read(root, "sys/kernel/gpu/gpu_max_clock"), "897000");
+        assert_eq!(read(root, "sys/kernel/gpu/gpu_min_clock"), "897000");
+        gpu.release();
+        assert_eq!(read(root, "sys/kernel/gpu/gpu_min_clock"), "260000");
+        assert_eq!(read(root, "sys/kernel/gpu/gpu_max_clock"), "800000");
+    }
+
+    #[test]
+    fn power_nodes_are_held_and_put_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let kgsl = "sys/class/kgsl/kgsl-3d0";
+        write(root, &format!("{kgsl}/force_clk_on"), "0\n");
+        write(root, &format!("{kgsl}/force_bus_on"), "1\n");
+        write(root, &format!("{kgsl}/bus_split"), "1\n");
+
+        let mut gpu = GpuActuator::detect_at(root);
+        assert!(gpu.hold_power());
+        assert_eq!(read(root, &format!("{kgsl}/force_clk_on")), "1");
+        assert_eq!(read(root, &format!("{kgsl}/bus_split")), "0");
+        gpu.release_power();
+        assert_eq!(read(root, &format!("{kgsl}/force_clk_on")), "0");
+        assert_eq!(read(root, &format!("{kgsl}/force_bus_on")), "1");
+        assert_eq!(read(root, &format!("{kgsl}/bus_split")), "1");
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let policy = "sys/devices/platform/1f000000.mali/power_policy";
+        write(root, policy, "[adaptive] coarse_demand always_on\n");
+
+        let mut gpu = GpuActuator::detect_at(root);
+        gpu.hold_power();
+        assert_eq!(read(root, policy), "always_on");
+        gpu.restore();
+        assert_eq!(read(root, policy), "adaptive");
+
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!GpuActuator::detect_at(dir.path()).hold_power());
+    }
+
+    #[test]
+    fn mtk_opp_levels_map_to_indices() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "proc/gpufreqv2/gpu_working_opp_table",
+            "[00] freq: 886000, volt: 80000, vsram: 80000\n\
+             [01] freq: 800000, volt: 75000, vsram: 75000\n\
+             [02] freq: 650000, volt: 70000, vsram: 75000\n\
+             [03] freq: 500000, volt: 65000, vsram: 75000\n\
+             [04] freq: 350000, volt: 60000, vsram: 75000\n",
+        );
+        write(root, "proc/gpufreqv2/fix_target_opp_index", "-1\n");
+
+        let mut gpu = GpuActuator::detect_at(root);
+        gpu.raise();
+        assert_eq!(read(root, "proc/gpufreqv2/fix_target_opp_index"), "3");
+        gpu.set_level(GPU_LEVELS);
+        assert_eq!(read(root, "proc/gpufreqv2/fix_target_opp_index"), "0");
+        gpu.release();
+        assert_eq!(read(root, "proc/gpufreqv2/fix_target_opp_index"), "-1");
     }
 }
@@ -0,0 +1,311 @@
+// Game-session I/O QoS.
+//
+// Asset streaming stalls on storage look like CPU jank from the frame
+// side, and the usual culprits are other I/O: store updates, photo
+// backup, media scanning, background dexopt and F2FS's own garbage
+// collection. For the length of a game session:
+//
+// - the game's threads get best-effort level 0 I/O priority, and known
+//   background hogs the idle class;
+// - block devices switch to the scheduler configured for the applied
+//   profile (priorities only count under a scheduler that honours them,
+//   such as bfq or mq-deadline);
+// - F2FS background GC is pushed back and urgent GC switched off.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use tracing::debug;
+
+use crate::core::tweaks::saved::SavedNodes;
+
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+const IOPRIO_CLASS_BE: libc::c_int = 2;
+const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+/// Best-effort class, level 0: ahead of every other app without the
+/// starvation risk of the real-time class.
+pub const IOPRIO_BE_TOP: libc::c_int = IOPRIO_CLASS_BE << IOPRIO_CLASS_SHIFT;
+/// Idle class: served only when nothing else wants the disk.
+const IOPRIO_IDLE: libc::c_int = IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT;
+
+const F2FS_ROOT: &str = "/sys/fs/f2fs";
+/// GC thread sleep (ms) while paused; long enough that background GC
+/// doesn't run during a session.
+const F2FS_GC_DEFER_MS: &str = "600000";
+const F2FS_GC_SLEEP_NODES: [&str; 3] = [
+    "gc_min_sleep_time",
+    "gc_max_sleep_time",
+    "gc_no_gc_sleep_time",
+];
+
+pub fn ioprio_get(tid: i32) -> Option<libc::c_int> {
+    // SAFETY: ioprio_get only reads the target task's priority.
+    let r = unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, tid) };
+    libc::c_int::try_from(r).ok().filter(|&p| p >= 0)
+}
+
+pub fn ioprio_set(tid: i32, prio: libc::c_int) -> bool {
+    // SAFETY: ioprio_set takes plain integers; a gone tid just fails.
+    unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, tid, prio) == 0 }
+}
+
+/// Thread IDs of `pid`.
+pub fn task_ids(pid: i32) -> Vec<i32> {
+    let Ok(tasks) = fs::read_dir(format!("/proc/{pid}/task")) else {
+        return Vec::new();
+    };
+    tasks
+        .flatten()
+        .filter_map(|t| t.file_name().to_str().and_then(|n| n.parse().ok()))
+        .collect()
+}
+
+/// Current and available entries of a sysfs choice node such as a
+/// queue's `scheduler`, e.g. `mq-deadline [kyber] none`.
+pub(crate) fn parse_choice(s: &str) -> (Option<&str>, Vec<&str>) {
+    let mut current = None;
+    let available = s
+        .split_whitespace()
+        .map(
+            |name| match name.strip_prefix('[').and_then(|n| n.strip_suffix(']')) {
+                Some(n) => {
+                    current = Some(n);
+                    n
+                }
+                None => name,
+            },
+        )
+        .collect();
+    (current, available)
+}
+
+/// First entry of a comma-separated preference list that `available`
+/// offers.
+pub(crate) fn pick_choice<'a>(preference: &'a str, available: &[&str]) -> Option<&'a str> {
+    preference
+        .split(',')
+        .map(str::trim)
+        .find(|p| available.contains(p))
+}
+
+/// Process name used to match `[io] background_hogs`: the package for an
+/// app process, the binary's file name for a native one.
+fn process_name(cmdline: &[u8]) -> Option<&str> {
+    let argv0 = std::str::from_utf8(cmdline.split(|&b| b == 0).next()?).ok()?;
+    let name = argv0.rsplit('/').next()?.split(':').next()?;
+    (!name.is_empty()).then_some(name)
+}
+
+/// Block devices backed by real storage (no loop, ram, zram or
+/// device-mapper nodes).
+fn storage_queues() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir("/sys/block") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|e| {
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            !["loop", "ram", "zram", "dm-", "md"]
+                .iter()
+                .any(|p| name.starts_with(p))
+        })
+        .map(|e| e.path().join("queue"))
+        .filter(|q| q.join("scheduler").exists())
+        .collect()
+}
+
+/// I/O policy for one game session; see the module docs.
+#[derive(Debug, Default)]
+pub struct IoQos {
+    active: bool,
+    /// tid → I/O priority before the session.
+    saved_ioprio: HashMap<i32, libc::c_int>,
+    saved_schedulers: SavedNodes,
+    /// Preference list the schedulers were last switched for.
+    scheduler_choice: Option<String>,
+    saved_gc: SavedNodes,
+}
+
+impl IoQos {
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Give every thread of `pids` the top best-effort priority. Threads
+    /// someone else moved since (a launch boost releasing) are put back.
+    pub fn prioritize_game(&mut self, pids: &[i32]) {
+        self.active = true;
+        for &pid in pids {
+            for tid in task_ids(pid) {
+                self.set_ioprio(tid, IOPRIO_BE_TOP);
+            }
+        }
+    }
+
+    /// Drop every thread of processes named in `hogs` to the idle class.
+    pub fn idle_hogs(&mut self, hogs: &[String]) {
+        self.active = true;
+        if hogs.is_empty() {
+            return;
+        }
+        let Ok(entries) = fs::read_dir("/proc") else {
+            return;
+        };
+        for pid in entries
+            .flatten()
+            .filter_map(|e| e.file_name().to_str().and_then(|n| n.parse::<i32>().ok()))
+        {
+            let Ok(cmdline) = fs::read(format!("/proc/{pid}/cmdline")) else {
+                continue;
+            };
+            if !process_name(&cmdline).is_some_and(|n| hogs.iter().any(|h| h == n)) {
+                continue;
+            }
+            for tid in task_ids(pid) {
+                self.set_ioprio(tid, IOPRIO_IDLE);
+            }
+        }
+    }
+
+    fn set_ioprio(&mut self, tid: i32, prio: libc::c_int) {
+        let Some(current) = ioprio_get(tid) else {
+            return;
+        };
+        if current != prio && ioprio_set(tid, prio) {
+            self.saved_ioprio.entry(tid).or_insert(current);
+        }
+    }
+
+    /// Switch every storage queue to the first scheduler in `preference`
+    /// (comma-separated) it offers; `None` puts the originals back.
+    pub fn set_scheduler(&mut self, preference: Option<&str>) {
+        if preference == self.scheduler_choice.as_deref() {
+            return;
+        }
+        let Some(preference) = preference else {
+            self.restore_schedulers();
+            return;
+        };
+        self.active = true;
+        self.scheduler_choice = Some(preference.to_string());
+        for queue in storage_queues() {
+            let node = queue.join("scheduler");
+            let Ok(content) = fs::read_to_string(&node) else {
+                continue;
+            };
+            let (current, available) = parse_choice(&content);
+            let (Some(current), Some(target)) = (current, pick_choice(preference, &available))
+            else {
+                continue;
+            };
+            if current == target {
+                continue;
+            }
+            if fs::write(&node, target).is_ok() {
+                debug!(
+                    "I/O scheduler for {} {} -> {}",
+                    queue.display(),
+                    current,
+                    target
+                );
+                self.saved_schedulers.record(node, current.to_string());
+            }
+        }
+    }
+
+    fn restore_schedulers(&mut self) {
+        self.scheduler_choice = None;
+        for (node, value) in self.saved_schedulers.take_all() {
+            let _ = fs::write(&node, &value);
+            debug!("I/O scheduler for {} restored to {}", node.display(), value);
+        }
+    }
+
+    /// Defer F2FS background GC and switch off urgent GC on every F2FS
+    /// volume. Safe to repeat; Android's idle maintenance may turn urgent
+    /// GC back on.
+    pub fn pause_f2fs_gc(&mut self) {
+        self.active = true;
+        let Ok(entries) = fs::read_dir(F2FS_ROOT) else {
+            return;
+        };
+        for dir in entries.flatten().map(|e| e.path()) {
+            let urgent = dir.join("gc_urgent");
+            if urgent.exists() {
+                self.write_gc(urgent, "0");
+            }
+            for node in F2FS_GC_SLEEP_NODES {
+                let path = dir.join(node);
+                if path.exists() {
+                    self.write_gc(path, F2FS_GC_DEFER_MS);
+                }
+            }
+        }
+    }
+
+    fn write_gc(&mut self, path: PathBuf, value: &str) {
+        if let Some(old) = self.saved_gc.write(&path, value) {
+            debug!("F2FS {} {} -> {}", path.display(), old, value);
+        }
+    }
+
+    pub fn restore_f2fs_gc(&mut self) {
+        // Sleep times before `gc_urgent`, so GC doesn't briefly run with
+        // the deferred timings.
+        let mut saved = self.saved_gc.take_all();
+        saved.sort_by_key(|(p, _)| p.ends_with("gc_urgent"));
+        for (path, value) in saved {
+            let _ = fs::write(&path, &value);
+        }
+    }
+
+    /// Put every priority, scheduler and GC setting back.
+    pub fn restore(&mut self) {
+        if !self.active {
+            return;
+        }
+        self.active = false;
+        let n = self.saved_ioprio.len();
+        for (tid, prio) in self.saved_ioprio.drain() {
+            ioprio_set(tid, prio);
+        }
+        self.restore_schedulers();
+        self.restore_f2fs_gc();
+        debug!("I/O QoS released ({} threads restored)", n);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_picks_scheduler() {
+        let (current, available) = parse_choice("mq-deadline [kyber] none\n");
+        assert_eq!(current, Some("kyber"));
+        assert_eq!(available, ["mq-deadline", "kyber", "none"]);
+        assert_eq!(
+            pick_choice("bfq, mq-deadline", &available),
+            Some("mq-deadline")
+        );
+        assert_eq!(pick_choice("bfq", &available), None);
+        assert_eq!(parse_choice("[none]\n"), (Some("none"), vec!["none"]));
+    }
+
+    #[test]
+    fn names_processes_for_hog_matching() {
+        assert_eq!(
+            process_name(b"com.android.vending:background\0"),
+            Some("com.android.vending")
+        );
+        assert_eq!(
+            process_name(b"/apex/com.android.art/bin/dex2oat64\0--dex-file=x\0"),
+            Some("dex2oat64")
+        );
+        assert_eq!(process_name(b""), None);
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, Instant};
+
+use tracing::debug;
+
+use crate::core::tweaks::ceiling::CoreLayout;
+use crate::core::tweaks::io_qos::{IOPRIO_BE_TOP, ioprio_get, ioprio_set, task_ids};
+use crate::core::tweaks::storage;
+
+/// Cold-start boost: every core's `scaling_min_freq` raised to its
+/// `scaling_max_freq`, storage devfreq locked high and the game's threads
+/// moved to the top best-effort I/O priority. Floors and I/O priorities
+/// are captured when applied and put back by [`LaunchBoost::release`].
+pub struct LaunchBoost {
+    cores: Vec<usize>,
+    saved_floors: Vec<(usize, String)>,
+    /// tid → I/O priority before the boost.
+    saved_ioprio: HashMap<i32, libc::c_int>,
+    until: Option<Instant>,
+}
+
+impl LaunchBoost {
+    pub fn new(layout: &CoreLayout) -> Self {
+        Self {
+            cores: layout.all_core_ids.clone(),
+            saved_floors: Vec::new(),
+            saved_ioprio: HashMap::new(),
+            until: None,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.until.is_some()
+    }
+
+    /// When the boost runs out, if one is active.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.until
+    }
+
+    /// Raise the floors and lock storage for at most `duration`. A boost
+    /// already running keeps its deadline.
+    pub fn start(&mut self, duration: Duration) {
+        if self.until.is_some() {
+            return;
+        }
+        self.until = Some(Instant::now() + duration);
+        for &c in &self.cores {
+            let min_path = cpufreq_path(c, "scaling_min_freq");
+            let Ok(orig) = fs::read_to_string(&min_path) else {
+                continue;
+            };
+            let Some(max) = fs::read_to_string(cpufreq_path(c, "scaling_max_freq"))
+                .ok()
+                .and_then(|s| s.trim().parse::<u64>().ok())
+            else {
+                continue;
+            };
+            if fs::write(&min_path, max.to_string()).is_ok() {
+                self.saved_floors.push((c, orig.trim().to_string()));
+            }
+        }
+        crate::core::profile::warn_on_err(storage::lock_storage_freq(), "lock storage freq");
+        debug!(target: "auriya::launch", "Launch boost on ({} cores)", self.saved_floors.len());
+    }
+
+    /// Move every thread of `pids` not yet boosted to the launch I/O
+    /// priority. Cheap to repeat: loaders spawn threads as they go.
+    pub fn boost_io(&mut self, pids: &[i32]) {
+        if self.until.is_none() {
+            return;
+        }
+        for &pid in pids {
+            for tid in task_ids(pid) {
+                if self.saved_ioprio.contains_key(&tid) {
+                    continue;
+                }
+                let Some(prev) = ioprio_get(tid) else {
+                    continue;
+                };
+                if ioprio_set(tid, IOPRIO_BE_TOP) {
+                    self.saved_ioprio.insert(tid, prev);
+                }
+            }
+        }
+    }
+
+    /// Drop the boost once its deadline has passed.
+    pub fn expire(&mut self, keep_storage: bool) {
+        if self.until.is_some_and(|u| Instant::now() >= u) {
+            self.release(keep_storage);
+        }
+    }
+
+    /// Put floors and I/O priorities back now. `keep_storage` leaves the
+    /// storage lock in place for a profile that wants it too.
+    pub fn release(&mut self, keep_storage: bool) {
+        if self.until.take().is_none() {
+            return;
+        }
+        for (c, orig) in self.saved_floors.drain(..) {
+            let _ = fs::write(cpufreq_path(c, "scaling_min_freq"), orig);
+        }
+        for (tid, prev) in self.saved_ioprio.drain() {
+            ioprio_set(tid, prev);
+        }
+        if !keep_storage {
+            crate::core::profile::warn_on_err(
+                storage::unlock_storage_freq(),
+                "unlock storage freq",
+            );
+        }
+        debug!(target: "auriya::launch", "Launch boost off");
+    }
+}
+
+fn cpufreq_path(core: usize, node: &str) -> String {
+    format!("/sys/devices/system/cpu/cpu{core}/cpufreq/{node}")
+}
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
 
 #[derive(Debug, Clone)]
@@ -181,10 +182,6 @@ pub fn adjust_for_gaming() -> Result<()> {
         Err(e) => warn!("Failed to apply gaming LMK: {}", e),
     }
 
-    if let Err(e) = set_swappiness(10) {
-        warn!("Failed to set  swappiness: {}", e);
-    }
-
     let vfs_path = "/proc/sys/vm/vfs_cache_pressure";
     if Path::new(vfs_path).exists() {
         let _ = fs::write(vfs_path, "80");
@@ -210,10 +207,6 @@ pub fn restore_balanced() -> Result<()> {
         Err(e) => warn!("Failed to apply balanced LMK: {}", e),
     }
 
-    if let Err(e) = set_swappiness(60) {
-        warn!("Failed to set balanced swappiness: {}", e);
-    }
-
     let vfs_path = "/proc/sys/vm/vfs_cache_pressure";
     if Path::new(vfs_path).exists() {
         let _ = fs::write(vfs_path, "100");
@@ -284,8 +277,247 @@ pub fn set_swappiness(value: u32) -> Result<()> {
     Ok(())
 }
 
-pub fn drop_caches() -> Result<()> {
-    fs::write("/proc/sys/vm/drop_caches", "3").context("Failed to drop caches")?;
-    debug!("Kernel caches and buffers dropped");
-    Ok(())
+/// What session-start memory preparation found and did.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryPrep {
+    /// Memory PSI `some avg10` at the time.
+    pub psi: Option<f32>,
+    pub available_mb: Option<u64>,
+    /// Memory compaction was requested.
+    pub compacted: bool,
+    /// Reclaimable slab (dentries, inodes) was dropped. The page cache is
+    /// left alone: it holds the assets the game is about to read.
+    pub trimmed: bool,
+    /// Why nothing was attempted, when nothing was.
+    pub skipped: Option<&'static str>,
+}
+
+impl std::fmt::Display for MemoryPrep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let action = match (self.compacted, self.trimmed, self.skipped) {
+            (_, _, Some(why)) => format!("skipped ({why})"),
+            (true, true, _) => "compacted, trimmed slab".to_string(),
+            (true, false, _) => "compacted".to_string(),
+            (false, true, _) => "trimmed slab".to_string(),
+            (false, false, _) => "nothing (pressure low)".to_string(),
+        };
+        let psi = self
+            .psi
+            .map(|p| format!("{p:.1}"))
+            .unwrap_or_else(|| "N/A".to_string());
+        let avail = self
+            .available_mb
+            .map(|m| format!("{m}MB"))
+            .unwrap_or_else(|| "N/A".to_string());
+        write!(f, "{action} (psi={psi} avail={avail})")
+    }
+}
+
+impl MemoryPrep {
+    pub fn skipped(why: &'static str) -> Self {
+        Self {
+            skipped: Some(why),
+            ..Self::default()
+        }
+    }
+}
+
+/// Decide session-start memory preparation from current pressure: compact
+/// when memory PSI `some avg10` is at or above `psi_threshold`, and also
+/// trim reclaimable slab when `MemAvailable` is below `min_available_mb`.
+/// The writes happen on a short-lived thread; compaction can take a while
+/// and the caller is on the launch path.
+pub fn prepare_session(psi_threshold: f32, min_available_mb: u64) -> MemoryPrep {
+    let psi = crate::core::psi::memory_some_avg10();
+    let available_mb = fs::read_to_string("/proc/meminfo")
+        .ok()
+        .and_then(|m| parse_meminfo_kb(&m, "MemAvailable"))
+        .map(|kb| kb / 1024);
+    let (compacted, trimmed) = plan_prep(psi, available_mb, psi_threshold, min_available_mb);
+    if compacted || trimmed {
+        let _ = std::thread::Builder::new()
+            .name("auriya-memprep".into())
+            .spawn(move || {
+                if trimmed && let Err(e) = fs::write("/proc/sys/vm/drop_caches", "2") {
+                    debug!("Failed to trim slab caches: {}", e);
+                }
+                if compacted && let Err(e) = fs::write("/proc/sys/vm/compact_memory", "1") {
+                    debug!("Failed to compact memory: {}", e);
+                }
+            });
+    }
+    MemoryPrep {
+        psi,
+        available_mb,
+        compacted,
+        trimmed,
+        skipped: None,
+    }
+}
+
+/// (compact, trim) for the given readings. Unknown PSI counts as no
+/// pressure.
+fn plan_prep(
+    psi: Option<f32>,
+    available_mb: Option<u64>,
+    psi_threshold: f32,
+    min_available_mb: u64,
+) -> (bool, bool) {
+    if psi.is_none_or(|p| p < psi_threshold) {
+        return (false, false);
+    }
+    (true, available_mb.is_some_and(|m| m < min_available_mb))
+}
+
+fn parse_meminfo_kb(meminfo: &str, key: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find_map(|l| l.strip_prefix(key)?.strip_prefix(':'))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Game processes shielded from the low-memory killer: `oom_score_adj`
+/// lowered for each and, optionally, `memory.low` raised on its cgroup v2
+/// memory cgroup. ActivityManager rewrites `oom_score_adj` whenever an
+/// app changes state (most of all when it leaves the foreground), so
+/// [`OomGuard::protect`] runs every tick: a value it finds changed becomes
+/// the one to restore, and ours goes back on top.
+#[derive(Debug, Default)]
+pub struct OomGuard {
+    adj: Option<i32>,
+    /// pid → `oom_score_adj` to put back.
+    saved: HashMap<i32, String>,
+    /// `memory.low` file → previous value.
+    memcg: HashMap<PathBuf, String>,
+}
+
+impl OomGuard {
+    /// The `oom_score_adj` currently enforced, if any process is shielded.
+    pub fn adj(&self) -> Option<i32> {
+        self.adj.filter(|_| !self.saved.is_empty())
+    }
+
+    /// Shielded PIDs, ascending.
+    pub fn pids(&self) -> Vec<i32> {
+        let mut pids: Vec<i32> = self.saved.keys().copied().collect();
+        pids.sort_unstable();
+        pids
+    }
+
+    pub fn memcg_protected(&self) -> bool {
+        !self.memcg.is_empty()
+    }
+
+    pub fn protect(&mut self, pids: &[i32], adj: i32, memcg: bool) {
+        let value = adj.to_string();
+        let previous = self.adj.map(|a| a.to_string());
+        self.saved
+            .retain(|pid, _| Path::new(&format!("/proc/{pid}")).exists());
+        for &pid in pids {
+            let path = format!("/proc/{pid}/oom_score_adj");
+            let Ok(current) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let current = current.trim();
+            if current == value {
+                continue;
+            }
+            if fs::write(&path, &value).is_ok() {
+                // Our own older value (adj changed on reload) is not the
+                // one to restore.
+                if !self.saved.contains_key(&pid) || previous.as_deref() != Some(current) {
+                    self.saved.insert(pid, current.to_string());
+                }
+                debug!("oom_score_adj for PID {} {} -> {}", pid, current, value);
+            }
+        }
+        self.adj = Some(adj);
+
+        if !memcg {
+            self.restore_memcg();
+            return;
+        }
+        for &pid in pids {
+            let Some(cgroup) = fs::read_to_string(format!("/proc/{pid}/cgroup"))
+                .ok()
+                .and_then(|s| parse_cgroup_v2_path(&s).map(str::to_string))
+            else {
+                continue;
+            };
+            // Protection only reaches a cgroup as far as its ancestors
+            // allow, so raise every level below the root.
+            let mut dir = PathBuf::from(CGROUP_V2_ROOT);
+            for part in cgroup.split('/').filter(|p| !p.is_empty()) {
+                dir.push(part);
+                let file = dir.join("memory.low");
+                if self.memcg.contains_key(&file) {
+                    continue;
+                }
+                if let Ok(prev) = fs::read_to_string(&file)
+                    && fs::write(&file, "max").is_ok()
+                {
+                    debug!("memory.low for {} {} -> max", dir.display(), prev.trim());
+                    self.memcg.insert(file, prev.trim().to_string());
+                }
+            }
+        }
+    }
+
+    /// Put every saved value back.
+    pub fn restore(&mut self) {
+        for (pid, value) in self.saved.drain() {
+            let _ = fs::write(format!("/proc/{pid}/oom_score_adj"), &value);
+            debug!("oom_score_adj for PID {} restored to {}", pid, value);
+        }
+        self.adj = None;
+        self.restore_memcg();
+    }
+
+    fn restore_memcg(&mut self) {
+        for (file, value) in self.memcg.drain() {
+            let _ = fs::write(&file, &value);
+        }
+    }
+}
+
+pub(crate) const CGROUP_V2_ROOT: &str = "/sys/fs/cgroup";
+
+/// The unified-hierarchy (`0::`) path from `/proc/<pid>/cgroup`.
+pub(crate) fn parse_cgroup_v2_path(cgroup: &str) -> Option<&str> {
+    cgroup
+        .lines()
+        .find_map(|l| l.strip_prefix("0::"))
+        .filter(|p| *p != "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preps_only_under_pressure() {
+        let meminfo = "MemTotal:       11700000 kB\nMemAvailable:    900000 kB\n";
+        assert_eq!(parse_meminfo_kb(meminfo, "MemAvailable"), Some(900_000));
+        assert_eq!(parse_meminfo_kb(meminfo, "MemFree"), None);
+
+        assert_eq!(plan_prep(None, Some(100), 10.0, 1024), (false, false));
+        assert_eq!(plan_prep(Some(5.0), Some(100), 10.0, 1024), (false, false));
+        assert_eq!(plan_prep(Some(12.0), Some(4096), 10.0, 1024), (true, false));
+        assert_eq!(plan_prep(Some(12.0), Some(878), 10.0, 1024), (true, true));
+        assert_eq!(
+            MemoryPrep::skipped("launch boost active").to_string(),
+            "skipped (launch boost active) (psi=N/A avail=N/A)"
+        );
+    }
+
+    #[test]
+    fn finds_unified_cgroup_path() {
+        let cgroup = "4:memory:/apps/uid_10234\n3:cpuset:/top-app\n0::/uid_10234/pid_4242\n";
+        assert_eq!(parse_cgroup_v2_path(cgroup), Some("/uid_10234/pid_4242"));
+        assert_eq!(parse_cgroup_v2_path("0::/\n"), None);
+        assert_eq!(parse_cgroup_v2_path("3:cpuset:/top-app\n"), None);
+    }
 }
@@ -1,11 +1,22 @@
 pub mod ceiling;
+pub mod cgroups;
 pub mod cpu;
+pub mod energy_model;
+pub mod freezer;
 pub mod gpu;
 pub mod init;
+pub mod io_qos;
+pub mod launch_boost;
 pub mod memory;
 pub mod paths;
+pub mod saved;
 pub mod sched;
 pub mod storage;
+pub mod swap;
+#[cfg(test)]
+pub(crate) mod test_util;
+pub mod threads;
+pub mod touch_boost;
 pub mod touchpanel;
 pub mod vendor;
 pub mod vendor_lock;
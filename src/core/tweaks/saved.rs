@@ -0,0 +1,106 @@
+// Capture-before-write for sysfs and procfs tunables.
+//
+// Every tweak that outlives a profile writes through [`SavedNodes`]: the
+// first write to a node records the value it replaced, and the way back
+// puts exactly that value back rather than an assumed default, so a
+// kernel or vendor setting the daemon never knew about survives a game
+// session untouched.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Node → value before we first wrote it.
+#[derive(Debug, Default)]
+pub struct SavedNodes(HashMap<PathBuf, String>);
+
+impl SavedNodes {
+    /// Write `value` to `node`, remembering the value it replaced the first
+    /// time round. Returns the replaced value when something was written;
+    /// missing nodes and nodes already at `value` are left alone.
+    /// Whitespace runs in the old value collapse to one space, so
+    /// multi-value nodes compare and restore cleanly.
+    pub fn write(&mut self, node: &Path, value: &str) -> Option<String> {
+        let current = fs::read_to_string(node).ok()?;
+        let current = current.split_whitespace().collect::<Vec<_>>().join(" ");
+        if current == value || fs::write(node, value).is_err() {
+            return None;
+        }
+        self.0
+            .entry(node.to_path_buf())
+            .or_insert_with(|| current.clone());
+        Some(current)
+    }
+
+    /// Remember `original` for a node the caller wrote itself.
+    pub fn record(&mut self, node: PathBuf, original: String) {
+        self.0.entry(node).or_insert(original);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Put back `node`, if we changed it.
+    pub fn restore(&mut self, node: &Path) {
+        if let Some(value) = self.0.remove(node) {
+            let _ = fs::write(node, value);
+        }
+    }
+
+    /// Put back every node `keep` doesn't claim.
+    pub fn restore_unless(&mut self, keep: impl Fn(&Path) -> bool) {
+        for (node, value) in self.take_where(|n| !keep(n)) {
+            let _ = fs::write(node, value);
+        }
+    }
+
+    /// Put back everything, in no particular order.
+    pub fn restore_all(&mut self) {
+        for (node, value) in self.0.drain() {
+            let _ = fs::write(node, value);
+        }
+    }
+
+    /// Forget and return the nodes matching `pred`, for callers that must
+    /// restore them in a particular order.
+    pub fn take_where(&mut self, pred: impl Fn(&Path) -> bool) -> Vec<(PathBuf, String)> {
+        let keys: Vec<PathBuf> = self.0.keys().filter(|n| pred(n)).cloned().collect();
+        keys.into_iter()
+            .filter_map(|k| self.0.remove_entry(&k))
+            .collect()
+    }
+
+    /// Forget and return every node.
+    pub fn take_all(&mut self) -> Vec<(PathBuf, String)> {
+        self.0.drain().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_value_wins_and_is_put_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a");
+        let b = dir.path().join("b");
+        fs::write(&a, "95\t85\n").unwrap();
+        fs::write(&b, "1\n").unwrap();
+
+        let mut saved = SavedNodes::default();
+        assert_eq!(saved.write(&a, "80 70"), Some("95 85".into()));
+        assert_eq!(saved.write(&a, "60 50"), Some("80 70".into()));
+        assert_eq!(saved.write(&b, "1"), None);
+        assert_eq!(saved.write(&dir.path().join("missing"), "1"), None);
+        saved.write(&b, "0");
+
+        saved.restore_unless(|n| n == b);
+        assert_eq!(fs::read_to_string(&a).unwrap(), "95 85");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "0");
+        saved.restore_all();
+        assert_eq!(fs::read_to_string(&b).unwrap(), "1");
+        assert!(saved.is_empty());
+    }
+}
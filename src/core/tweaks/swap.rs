@@ -0,0 +1,387 @@
+// ZRAM layout and swap policy.
+//
+// Android swaps to a compressed RAM disk, zram0, set up by init from the
+// vendor fstab. Its compressor, size and (on kernels with
+// CONFIG_ZRAM_WRITEBACK) backing device can only change while the device
+// is reset, so [`SwapPolicy::configure_zram`] does that once at startup:
+// swapoff, reset, write the new layout, mkswap, swapon. The layout found
+// first is kept and written back the same way by [`SwapPolicy::restore`].
+//
+// `vm.swappiness` and `vm.page-cluster` follow the applied profile. A
+// fixed low swappiness during games starves the page cache on low-RAM
+// devices (the game's own assets get evicted instead of idle anon pages
+// being compressed), so the default depends on installed RAM. Readahead
+// from zram buys nothing, hence `page-cluster` 0 while gaming.
+//
+// On game entry, idle zram pages can be recompressed with a stronger
+// secondary algorithm or written back to the backing device, freeing RAM
+// for the game.
+
+use std::ffi::CString;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use tracing::{debug, info, warn};
+
+use crate::core::config::settings::SwapConfig;
+use crate::core::profile::ProfileMode;
+use crate::core::tweaks::io_qos::{parse_choice, pick_choice};
+use crate::core::tweaks::memory::set_swappiness;
+
+const ZRAM: &str = "/sys/block/zram0";
+const ZRAM_DEV: &str = "/dev/block/zram0";
+const SWAPPINESS: &str = "/proc/sys/vm/swappiness";
+const PAGE_CLUSTER: &str = "/proc/sys/vm/page-cluster";
+/// Devices with this much RAM or less keep swapping freely while gaming.
+const LOW_RAM_MB: u64 = 4096;
+
+/// Swappiness for `mode` when `[swap.swappiness]` has no entry. `None`
+/// keeps the device's value.
+pub fn default_swappiness(mode: ProfileMode, total_ram_mb: u64) -> Option<u32> {
+    match mode {
+        ProfileMode::Performance | ProfileMode::Fast if total_ram_mb <= LOW_RAM_MB => Some(100),
+        ProfileMode::Performance | ProfileMode::Fast => Some(10),
+        ProfileMode::Balance | ProfileMode::Powersave => None,
+    }
+}
+
+/// The parts of zram0's setup that only change across a reset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZramLayout {
+    pub comp_algorithm: String,
+    /// Bytes.
+    pub disksize: u64,
+    pub backing_dev: Option<String>,
+    /// Secondary algorithm for recompression.
+    pub recomp_algorithm: Option<String>,
+}
+
+/// What zram0 runs with and what the kernel offers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZramInfo {
+    pub layout: ZramLayout,
+    pub algorithms: Vec<String>,
+    /// The kernel can write pages back to a backing device.
+    pub writeback: bool,
+    /// The kernel can recompress pages with a secondary algorithm.
+    pub recompress: bool,
+}
+
+impl std::fmt::Display for ZramInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let l = &self.layout;
+        write!(
+            f,
+            "{} {} MB (offers {}), backing {}, recompress {}",
+            l.comp_algorithm,
+            l.disksize >> 20,
+            self.algorithms.join(" "),
+            l.backing_dev.as_deref().unwrap_or("none"),
+            l.recomp_algorithm.as_deref().unwrap_or("none"),
+        )
+    }
+}
+
+impl ZramInfo {
+    pub fn detect() -> Option<Self> {
+        let dir = Path::new(ZRAM);
+        let read = |node: &str| fs::read_to_string(dir.join(node)).ok();
+        let algorithms = read("comp_algorithm")?;
+        let (current, available) = parse_choice(&algorithms);
+        let backing_dev = read("backing_dev")
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty() && s != "none");
+        Some(Self {
+            layout: ZramLayout {
+                comp_algorithm: current?.to_string(),
+                disksize: read("disksize")?.trim().parse().ok()?,
+                backing_dev,
+                recomp_algorithm: read("recomp_algorithm").and_then(|s| parse_recomp(&s)),
+            },
+            algorithms: available.into_iter().map(str::to_string).collect(),
+            writeback: dir.join("writeback").exists(),
+            recompress: dir.join("recompress").exists(),
+        })
+    }
+}
+
+/// Selected priority-1 algorithm from `recomp_algorithm`, e.g.
+/// `#1: lzo lzo-rle lz4 [zstd]`.
+fn parse_recomp(s: &str) -> Option<String> {
+    s.lines()
+        .find_map(|l| l.trim().strip_prefix("#1:"))
+        .and_then(|l| parse_choice(l).0)
+        .map(str::to_string)
+}
+
+/// The layout `cfg` asks for on top of `info`, or `None` when it matches
+/// what is there already. Unsupported requests are dropped.
+fn plan_layout(info: &ZramInfo, cfg: &SwapConfig) -> Option<ZramLayout> {
+    let available: Vec<&str> = info.algorithms.iter().map(String::as_str).collect();
+    let current = &info.layout;
+    let recomp_wanted = cfg.recompress_on_entry && info.recompress;
+    let target = ZramLayout {
+        comp_algorithm: pick_choice(&cfg.comp_algorithm, &available)
+            .unwrap_or(&current.comp_algorithm)
+            .to_string(),
+        disksize: match cfg.disksize_mb {
+            0 => current.disksize,
+            mb => mb << 20,
+        },
+        backing_dev: match cfg.backing_dev.trim() {
+            "" => current.backing_dev.clone(),
+            _ if !info.writeback => current.backing_dev.clone(),
+            dev => Some(dev.to_string()),
+        },
+        recomp_algorithm: if recomp_wanted {
+            pick_choice(&cfg.recomp_algorithm, &available)
+                .map(str::to_string)
+                .or_else(|| current.recomp_algorithm.clone())
+        } else {
+            current.recomp_algorithm.clone()
+        },
+    };
+    (target != *current).then_some(target)
+}
+
+fn swap_active() -> bool {
+    fs::read_to_string("/proc/swaps").is_ok_and(|s| s.lines().any(|l| l.contains("zram0")))
+}
+
+fn write_node(node: &str, value: &str) -> Result<()> {
+    let path = Path::new(ZRAM).join(node);
+    fs::write(&path, value).with_context(|| format!("write {value:?} to {}", path.display()))
+}
+
+/// Reset zram0 and bring it back up with `layout`.
+fn apply_layout(layout: &ZramLayout) -> Result<()> {
+    let dev = CString::new(ZRAM_DEV)?;
+    if swap_active() {
+        // SAFETY: `dev` is a valid NUL-terminated path.
+        if unsafe { libc::swapoff(dev.as_ptr()) } != 0 {
+            return Err(std::io::Error::last_os_error()).context("swapoff zram0");
+        }
+    }
+    write_node("reset", "1")?;
+    if let Some(backing) = &layout.backing_dev {
+        write_node("backing_dev", backing)?;
+    }
+    write_node("comp_algorithm", &layout.comp_algorithm)?;
+    if let Some(recomp) = &layout.recomp_algorithm {
+        write_node("recomp_algorithm", &format!("algo={recomp}"))?;
+    }
+    write_node("disksize", &layout.disksize.to_string())?;
+    let mkswap = std::process::Command::new("mkswap")
+        .arg(ZRAM_DEV)
+        .output()
+        .context("run mkswap")?;
+    if !mkswap.status.success() {
+        anyhow::bail!("mkswap zram0 failed");
+    }
+    // SAFETY: as above.
+    if unsafe { libc::swapon(dev.as_ptr(), 0) } != 0 {
+        return Err(std::io::Error::last_os_error()).context("swapon zram0");
+    }
+    Ok(())
+}
+
+/// Swap knobs and zram layout owned by the daemon; see the module docs.
+#[derive(Debug, Default)]
+pub struct SwapPolicy {
+    /// `(swappiness, page-cluster)` last asked for.
+    choice: Option<(Option<u32>, Option<u32>)>,
+    saved_swappiness: Option<u32>,
+    saved_page_cluster: Option<String>,
+    /// zram0 layout before [`Self::configure_zram`] changed it.
+    saved_layout: Option<ZramLayout>,
+    /// Idle pages were handled for the current game session.
+    maintained: bool,
+}
+
+impl SwapPolicy {
+    /// Log zram0's setup and, with `manage_zram`, switch it to the
+    /// configured layout. Resetting zram0 pulls every swapped page back
+    /// into RAM first, so this only runs at startup.
+    pub fn configure_zram(&mut self, cfg: &SwapConfig) {
+        let Some(info) = ZramInfo::detect() else {
+            debug!(target: "auriya::swap", "No zram0");
+            return;
+        };
+        info!(target: "auriya::swap", "zram0: {}", info);
+        if !cfg.manage_zram {
+            return;
+        }
+        let Some(target) = plan_layout(&info, cfg) else {
+            debug!(target: "auriya::swap", "zram0 already matches [swap]");
+            return;
+        };
+        match apply_layout(&target) {
+            Ok(()) => {
+                info!(
+                    target: "auriya::swap",
+                    "zram0 now {} {} MB",
+                    target.comp_algorithm,
+                    target.disksize >> 20
+                );
+                self.saved_layout = Some(info.layout);
+            }
+            Err(e) => {
+                warn!(target: "auriya::swap", "Failed to reconfigure zram0: {e:#}");
+                // Leave it usable with what it had.
+                if let Err(e) = apply_layout(&info.layout) {
+                    warn!(target: "auriya::swap", "Failed to bring zram0 back: {e:#}");
+                }
+            }
+        }
+    }
+
+    /// Set swappiness and `page-cluster`; `None` puts the device's value
+    /// back. Cheap to call every tick.
+    pub fn set(&mut self, swappiness: Option<u32>, page_cluster: Option<u32>) {
+        if self.choice == Some((swappiness, page_cluster)) {
+            return;
+        }
+        self.choice = Some((swappiness, page_cluster));
+
+        match swappiness {
+            Some(value) => {
+                if self.saved_swappiness.is_none() {
+                    self.saved_swappiness = fs::read_to_string(SWAPPINESS)
+                        .ok()
+                        .and_then(|s| s.trim().parse().ok());
+                }
+                let _ = set_swappiness(value);
+            }
+            None => {
+                if let Some(value) = self.saved_swappiness.take() {
+                    let _ = set_swappiness(value);
+                }
+            }
+        }
+
+        match page_cluster {
+            Some(value) => {
+                if self.saved_page_cluster.is_none() {
+                    self.saved_page_cluster = fs::read_to_string(PAGE_CLUSTER)
+                        .ok()
+                        .map(|s| s.trim().to_string());
+                }
+                if fs::write(PAGE_CLUSTER, value.to_string()).is_ok() {
+                    debug!(target: "auriya::swap", "page-cluster set to {}", value);
+                }
+            }
+            None => {
+                if let Some(value) = self.saved_page_cluster.take() {
+                    let _ = fs::write(PAGE_CLUSTER, value);
+                }
+            }
+        }
+    }
+
+    /// Recompress and/or write back idle zram pages, once per game
+    /// session. Runs on its own thread; both can take seconds.
+    pub fn maintain(&mut self, recompress: bool, writeback: bool) {
+        if self.maintained {
+            return;
+        }
+        self.maintained = true;
+        let Some(info) = ZramInfo::detect() else {
+            return;
+        };
+        let recompress = recompress && info.recompress && info.layout.recomp_algorithm.is_some();
+        let writeback = writeback && info.writeback && info.layout.backing_dev.is_some();
+        if !recompress && !writeback {
+            return;
+        }
+        std::thread::spawn(move || {
+            if let Err(e) = write_node("idle", "all") {
+                debug!(target: "auriya::swap", "Cannot mark zram pages idle: {e:#}");
+                return;
+            }
+            if recompress {
+                match write_node("recompress", "type=idle") {
+                    Ok(()) => debug!(target: "auriya::swap", "Recompressed idle zram pages"),
+                    Err(e) => debug!(target: "auriya::swap", "zram recompress failed: {e:#}"),
+                }
+            }
+            if writeback {
+                match write_node("writeback", "idle") {
+                    Ok(()) => debug!(target: "auriya::swap", "Wrote idle zram pages back"),
+                    Err(e) => debug!(target: "auriya::swap", "zram writeback failed: {e:#}"),
+                }
+            }
+        });
+    }
+
+    /// Allow [`Self::maintain`] again for the next session.
+    pub fn end_session(&mut self) {
+        self.maintained = false;
+    }
+
+    /// Put swappiness, `page-cluster` and the zram0 layout back.
+    pub fn restore(&mut self) {
+        self.set(None, None);
+        if let Some(layout) = self.saved_layout.take() {
+            match apply_layout(&layout) {
+                Ok(()) => debug!(target: "auriya::swap", "zram0 layout restored"),
+                Err(e) => warn!(target: "auriya::swap", "Failed to restore zram0: {e:#}"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info() -> ZramInfo {
+        ZramInfo {
+            layout: ZramLayout {
+                comp_algorithm: "lzo-rle".into(),
+                disksize: 2048 << 20,
+                backing_dev: None,
+                recomp_algorithm: None,
+            },
+            algorithms: vec!["lzo".into(), "lzo-rle".into(), "lz4".into(), "zstd".into()],
+            writeback: false,
+            recompress: true,
+        }
+    }
+
+    #[test]
+    fn plans_layout_from_config() {
+        let mut cfg = SwapConfig::default();
+        let target = plan_layout(&info(), &cfg).unwrap();
+        assert_eq!(target.comp_algorithm, "lz4");
+        assert_eq!(target.disksize, 2048 << 20);
+
+        cfg.comp_algorithm = "lzo-rle".into();
+        assert_eq!(plan_layout(&info(), &cfg), None);
+        // Writeback unsupported: the backing device is ignored.
+        cfg.backing_dev = "/dev/block/by-name/userdata".into();
+        assert_eq!(plan_layout(&info(), &cfg), None);
+
+        cfg.recompress_on_entry = true;
+        cfg.disksize_mb = 3072;
+        let target = plan_layout(&info(), &cfg).unwrap();
+        assert_eq!(target.recomp_algorithm.as_deref(), Some("zstd"));
+        assert_eq!(target.disksize, 3072 << 20);
+
+        assert_eq!(
+            parse_recomp("#1: lzo lzo-rle lz4 [zstd]\n").as_deref(),
+            Some("zstd")
+        );
+        assert_eq!(parse_recomp(""), None);
+    }
+
+    #[test]
+    fn swappiness_defaults_follow_ram() {
+        assert_eq!(
+            default_swappiness(ProfileMode::Performance, 3072),
+            Some(100)
+        );
+        assert_eq!(default_swappiness(ProfileMode::Fast, 12288), Some(10));
+        assert_eq!(default_swappiness(ProfileMode::Balance, 3072), None);
+    }
+}
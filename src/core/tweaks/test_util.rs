@@ -0,0 +1,19 @@
+// Fake sysfs fixtures for the tweak tests.
+
+use std::fs;
+use std::path::Path;
+
+/// Write `value` to `root/rel`, creating the directories on the way.
+pub fn write(root: &Path, rel: &str, value: &str) {
+    let path = root.join(rel);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, value).unwrap();
+}
+
+/// Contents of `root/rel`, trimmed.
+pub fn read(root: &Path, rel: &str) -> String {
+    fs::read_to_string(root.join(rel))
+        .unwrap()
+        .trim()
+        .to_string()
+}
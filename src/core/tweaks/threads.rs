@@ -0,0 +1,268 @@
+// Per-thread CPU placement for a game's process set.
+//
+// Affinity is per-thread, so pinning the game PID only ever moved its main
+// thread. The placer walks every thread of every game process instead and
+// splits them in two roles:
+//
+//   - hot:    engine render/main threads (by `comm`) and any thread whose
+//             measured runtime keeps it near a full core. Pinned to
+//             prime+big.
+//   - worker: everything else. Kept off the prime cores so they don't
+//             steal it from the hot threads.
+//
+// Threads are re-placed only when new or when their role changes, so a
+// rescan of a steady game costs one task-dir walk and no syscalls.
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+use tracing::debug;
+
+use crate::core::fas::signals::{is_render_thread, read_thread_runtimes};
+use crate::core::tweaks::cpu;
+
+/// Runtime share of one core that promotes an unnamed thread to hot.
+const HOT_RUNTIME_SHARE: f64 = 0.6;
+/// A runtime-promoted thread drops back to worker below this share.
+const COOL_RUNTIME_SHARE: f64 = 0.3;
+/// Cap on runtime-promoted threads per scan, so a busy job system doesn't
+/// pile every worker onto the prime core.
+const MAX_RUNTIME_HOT: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadRole {
+    Hot,
+    Worker,
+}
+
+impl std::fmt::Display for ThreadRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Hot => write!(f, "hot"),
+            Self::Worker => write!(f, "worker"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacedThread {
+    pub pid: i32,
+    pub tid: i32,
+    pub comm: String,
+    pub role: ThreadRole,
+    /// Affinity mask applied, 0 when the write failed.
+    pub mask: u64,
+}
+
+pub struct ThreadPlacer {
+    hot_mask: u64,
+    worker_mask: u64,
+    online_mask: u64,
+    /// tid → cumulative runtime (ns) at the last scan.
+    runtimes: HashMap<i32, u64>,
+    last_scan: Option<Instant>,
+    placed: BTreeMap<i32, PlacedThread>,
+}
+
+impl Default for ThreadPlacer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ThreadPlacer {
+    pub fn new() -> Self {
+        let cores = cpu::get_online_cores().unwrap_or_default();
+        let online_mask = cores.iter().fold(0u64, |m, &c| m | (1u64 << c));
+        let (little, big, prime) = cpu::classify_cores(&cores);
+        let (hot_mask, worker_mask) = placement_masks(little, big, prime);
+        Self {
+            hot_mask,
+            worker_mask,
+            online_mask,
+            runtimes: HashMap::new(),
+            last_scan: None,
+            placed: BTreeMap::new(),
+        }
+    }
+
+    /// Threads placed so far, in tid order.
+    pub fn placements(&self) -> impl Iterator<Item = &PlacedThread> {
+        self.placed.values()
+    }
+
+    /// Walk the threads of `pids`, classify them and apply affinity to the
+    /// ones that are new or changed role. Returns how many were (re)placed.
+    pub fn scan(&mut self, pids: &[i32]) -> usize {
+        if self.hot_mask == 0 {
+            return 0;
+        }
+        let now = Instant::now();
+        let wall_ns = self
+            .last_scan
+            .map(|at| now.duration_since(at).as_nanos() as f64)
+            .filter(|&w| w > 0.0);
+        self.last_scan = Some(now);
+
+        let mut seen: Vec<(i32, i32, String, Option<f64>)> = Vec::new();
+        let mut runtimes = HashMap::new();
+        for &pid in pids {
+            for (tid, comm, runtime) in read_thread_runtimes(pid) {
+                let share = wall_ns.and_then(|wall| {
+                    self.runtimes
+                        .get(&tid)
+                        .map(|b| runtime.saturating_sub(*b) as f64 / wall)
+                });
+                runtimes.insert(tid, runtime);
+                seen.push((pid, tid, comm, share));
+            }
+        }
+        self.runtimes = runtimes;
+
+        let previously_hot = |tid: i32| {
+            self.placed
+                .get(&tid)
+                .is_some_and(|p| p.role == ThreadRole::Hot)
+        };
+        let hot = select_hot(
+            seen.iter()
+                .map(|(_, tid, comm, share)| (*tid, comm.as_str(), *share, previously_hot(*tid))),
+        );
+
+        let mut changed = 0;
+        let mut placed = BTreeMap::new();
+        for (pid, tid, comm, _) in seen {
+            let role = if hot.contains(&tid) {
+                ThreadRole::Hot
+            } else {
+                ThreadRole::Worker
+            };
+            if let Some(prev) = self.placed.remove(&tid)
+                && prev.role == role
+                && prev.mask != 0
+            {
+                placed.insert(tid, prev);
+                continue;
+            }
+            let want = match role {
+                ThreadRole::Hot => self.hot_mask,
+                ThreadRole::Worker => self.worker_mask,
+            };
+            let mask = match cpu::set_thread_affinity(tid, want) {
+                Ok(()) => want,
+                Err(e) => {
+                    debug!(target: "auriya::threads", "affinity tid={tid} ({comm}): {e}");
+                    0
+                }
+            };
+            if role == ThreadRole::Hot {
+                debug!(target: "auriya::threads", "Hot thread {comm} tid={tid} pid={pid} → cpus {}", cpu::mask_to_cpu_list(mask));
+            }
+            changed += 1;
+            placed.insert(
+                tid,
+                PlacedThread {
+                    pid,
+                    tid,
+                    comm,
+                    role,
+                    mask,
+                },
+            );
+        }
+        self.placed = placed;
+        changed
+    }
+
+    /// Give every placed thread that is still alive the full online mask
+    /// back and forget the game.
+    pub fn clear(&mut self) {
+        if self.placed.is_empty() {
+            return;
+        }
+        for t in std::mem::take(&mut self.placed).into_values() {
+            let _ = cpu::set_thread_affinity(t.tid, self.online_mask);
+        }
+        self.runtimes.clear();
+        self.last_scan = None;
+    }
+}
+
+/// `(hot, worker)` masks from the core tiers. Without a prime tier the
+/// hot threads get the big cores and workers stay unrestricted.
+fn placement_masks(little: u64, big: u64, prime: u64) -> (u64, u64) {
+    let all = little | big | prime;
+    if prime == 0 {
+        (big, all)
+    } else {
+        (prime | big, all & !prime)
+    }
+}
+
+/// Pick hot threads from `(tid, comm, runtime share, was hot)`. Named
+/// render/main threads are always hot. Others qualify by runtime — at
+/// most [`MAX_RUNTIME_HOT`], busiest first — with hysteresis so a thread
+/// that was hot only drops out below [`COOL_RUNTIME_SHARE`].
+fn select_hot<'a>(threads: impl Iterator<Item = (i32, &'a str, Option<f64>, bool)>) -> Vec<i32> {
+    let mut hot = Vec::new();
+    let mut by_runtime: Vec<(i32, f64)> = Vec::new();
+    for (tid, comm, share, was_hot) in threads {
+        if is_render_thread(comm) {
+            hot.push(tid);
+            continue;
+        }
+        let threshold = if was_hot {
+            COOL_RUNTIME_SHARE
+        } else {
+            HOT_RUNTIME_SHARE
+        };
+        match share {
+            Some(s) if s >= threshold => by_runtime.push((tid, s)),
+            // No runtime delta yet (first scan, new thread): keep a
+            // previously hot thread where it is.
+            None if was_hot => by_runtime.push((tid, f64::INFINITY)),
+            _ => {}
+        }
+    }
+    by_runtime.sort_by(|a, b| b.1.total_cmp(&a.1));
+    hot.extend(by_runtime.into_iter().take(MAX_RUNTIME_HOT).map(|(t, _)| t));
+    hot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workers_stay_off_prime() {
+        let (hot, worker) = placement_masks(0x0f, 0x70, 0x80);
+        assert_eq!(hot, 0xf0);
+        assert_eq!(worker, 0x7f);
+        // Two tiers: no prime to protect.
+        assert_eq!(placement_masks(0x0f, 0xf0, 0), (0xf0, 0xff));
+    }
+
+    #[test]
+    fn selects_named_and_busy_threads() {
+        let threads = [
+            (10, "UnityMain", Some(0.2), false),
+            (11, "UnityGfxDeviceW", None, false),
+            (12, "Job.Worker 0", Some(0.9), false),
+            (13, "Job.Worker 1", Some(0.7), false),
+            (14, "Job.Worker 2", Some(0.65), false),
+            (15, "AudioTrack", Some(0.1), false),
+        ];
+        let hot = select_hot(threads.into_iter());
+        assert_eq!(hot, vec![10, 11, 12, 13]);
+    }
+
+    #[test]
+    fn runtime_hot_thread_has_hysteresis() {
+        let hot = select_hot([(12, "Thread-7", Some(0.4), true)].into_iter());
+        assert_eq!(hot, vec![12]);
+        let hot = select_hot([(12, "Thread-7", Some(0.4), false)].into_iter());
+        assert!(hot.is_empty());
+        let hot = select_hot([(12, "Thread-7", Some(0.2), true)].into_iter());
+        assert!(hot.is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::time::{Duration, Instant};
+
+use tracing::debug;
+
+use crate::core::tweaks::ceiling::CoreLayout;
+
+/// Short `scaling_min_freq` raise on the little and big clusters after a
+/// touch. Works on any cpufreq driver, unlike the vendor `cpu_boost`
+/// module. The original floors are captured on the first boost and put
+/// back by [`TouchBoost::release`].
+pub struct TouchBoost {
+    little: Vec<usize>,
+    big: Vec<usize>,
+    little_default_khz: Option<u64>,
+    big_default_khz: Option<u64>,
+    saved: Vec<(usize, String)>,
+    until: Option<Instant>,
+}
+
+impl TouchBoost {
+    pub fn new(layout: &CoreLayout) -> Self {
+        Self {
+            little: layout.little_ids.clone(),
+            big: layout.big_ids.clone(),
+            little_default_khz: median(&layout.little_freqs_khz),
+            big_default_khz: median(&layout.big_freqs_khz),
+            saved: Vec::new(),
+            until: None,
+        }
+    }
+
+    /// When the running boost ends, if one is active.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.until
+    }
+
+    /// Start or extend a boost for `duration`. `None` freqs use the
+    /// cluster's median available frequency. Floors are only written on
+    /// the first touch of a burst; later touches just push the deadline.
+    pub fn trigger(&mut self, duration: Duration, little_khz: Option<u64>, big_khz: Option<u64>) {
+        let until = Instant::now() + duration;
+        self.until = Some(self.until.map_or(until, |u| u.max(until)));
+        if !self.saved.is_empty() {
+            return;
+        }
+        let targets = [
+            (&self.little, little_khz.or(self.little_default_khz)),
+            (&self.big, big_khz.or(self.big_default_khz)),
+        ];
+        for (cores, khz) in targets {
+            let Some(khz) = khz else {
+                continue;
+            };
+            for &c in cores {
+                let path = min_freq_path(c);
+                let Ok(orig) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                let cap = fs::read_to_string(max_freq_path(c))
+                    .ok()
+                    .and_then(|s| s.trim().parse::<u64>().ok())
+                    .unwrap_or(u64::MAX);
+                if fs::write(&path, khz.min(cap).to_string()).is_ok() {
+                    self.saved.push((c, orig.trim().to_string()));
+                }
+            }
+        }
+        debug!(target: "auriya::input", "Touch boost on ({} cores)", self.saved.len());
+    }
+
+    /// Drop the boost once its deadline has passed.
+    pub fn expire(&mut self) {
+        if self.until.is_some_and(|u| Instant::now() >= u) {
+            self.release();
+        }
+    }
+
+    /// Put the captured floors back immediately.
+    pub fn release(&mut self) {
+        self.until = None;
+        if self.saved.is_empty() {
+            return;
+        }
+        for (c, orig) in self.saved.drain(..) {
+            let _ = fs::write(min_freq_path(c), orig);
+        }
+        debug!(target: "auriya::input", "Touch boost off");
+    }
+}
+
+fn min_freq_path(core: usize) -> String {
+    format!("/sys/devices/system/cpu/cpu{core}/cpufreq/scaling_min_freq")
+}
+
+fn max_freq_path(core: usize) -> String {
+    format!("/sys/devices/system/cpu/cpu{core}/cpufreq/scaling_max_freq")
+}
+
+/// Middle entry of an ascending frequency table.
+fn median(freqs: &[u64]) -> Option<u64> {
+    freqs.get(freqs.len() / 2).copied()
+}
@@ -6,7 +6,10 @@
 //!
 //!   - a tracked game process exiting,
 //!   - the companion service dying (its liveness lock released),
-//!   - a module update being staged.
+//!   - a module update being staged,
+//!   - a touch on the touchscreen during a game (input boost),
+//!   - a whitelisted game starting up (launch boost),
+//!   - a PSI trigger firing on a memory or I/O stall during a game.
 //!
 //! Each of these has a producer running on its own thread; they all funnel
 //! into one [`mpsc`] channel that the daemon consumes from inside its
@@ -26,6 +29,15 @@ pub enum DaemonEvent {
     /// daemon should stop gracefully so the new version takes over on the
     /// next boot.
     ModuleUpdate,
+    /// A finger went down or moved on the touchscreen. Only sent while the
+    /// touch boost is armed (game session in the foreground).
+    Touch,
+    /// A fresh process of this whitelisted package (this PID) joined the
+    /// top-app cpuset. Only sent while no game session is running.
+    Launch(String, i32),
+    /// A PSI trigger on this resource fired: stall time crossed its
+    /// threshold within the window. Only sent during a game session.
+    Psi(crate::core::psi::Resource),
 }
 
 pub type EventSender = mpsc::Sender<DaemonEvent>;
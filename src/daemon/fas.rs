@@ -14,8 +14,9 @@
 // bottom of `tick`, gated by bottleneck classification.
 
 use crate::core::{
-    fas::bottleneck::{BottleneckDetector, BottleneckType},
+    fas::bottleneck::{BottleneckDetector, BottleneckType, ClassSource, Classification},
     fas::buffer::{BufferState, FrameBuffer, TargetFps},
+    fas::signals::SignalSampler,
     fas::source::FrameSource,
     scaling::{PlatformCapabilities, ScalingAction},
     thermal::ThermalMonitor,
@@ -42,7 +43,7 @@ pub struct FasTuning {
     /// FPS headroom subtracted from the target (per-mode `margin`). Higher =
     /// the controller treats "exactly target" as slower, biasing toward boosts.
     pub margin_fps: f64,
-    /// Skin-temperature ceiling (°C); above it FAS forces `Reduce`.
+    /// Ceiling (°C) for the `[thermal] fas` zones; above it FAS forces `Reduce`.
     pub thermal_threshold: f32,
     /// Bottleneck-detector coefficient-of-variation split
     pub cv_threshold: f64,
@@ -111,13 +112,22 @@ pub struct FasController {
     caps: PlatformCapabilities,
     package: String,
     pid: Option<i32>,
+    /// Every process of the game, for load signals.
+    game_pids: Vec<i32>,
     last_attached_pkg: String,
     state: FasState,
     waiting_since: Option<Instant>,
     util_sampler: UtilSampler,
+    signals: SignalSampler,
     target_fps_offset: f64,
     kp: f64,
     tuning: FasTuning,
+    /// Per-game `margin`, replacing the mode's while set.
+    margin_override: Option<f64>,
+    /// Latest bottleneck classification, until collected.
+    last_bottleneck: Option<BottleneckType>,
+    /// `[thermal] fas` reading from the latest tick.
+    last_temp: Option<f32>,
 }
 
 impl FasController {
@@ -125,7 +135,7 @@ impl FasController {
     /// shared `EbpfFrameStream` — the eBPF probe is already loaded by the
     /// caller so FAS creation cannot fail.
     pub fn new(
-        rx: tokio::sync::broadcast::Receiver<Duration>,
+        rx: tokio::sync::broadcast::Receiver<crate::core::ebpf::FrameEvent>,
         target_fps_config: TargetFps,
         tuning: FasTuning,
     ) -> Self {
@@ -154,22 +164,31 @@ impl FasController {
             caps,
             package: String::new(),
             pid: None,
+            game_pids: Vec::new(),
             last_attached_pkg: String::new(),
             state: FasState::NotWorking,
             waiting_since: None,
             util_sampler: UtilSampler::new(),
+            signals: SignalSampler::new(),
             target_fps_offset: 0.0,
             kp: KP_DEFAULT,
             tuning,
+            margin_override: None,
+            last_bottleneck: None,
+            last_temp: None,
         }
     }
 
     pub fn reset(&mut self) {
         self.package.clear();
         self.pid = None;
+        self.game_pids.clear();
         self.last_attached_pkg.clear();
         self.buffer.clear();
         self.bottleneck.reset();
+        self.signals.reset();
+        self.last_bottleneck = None;
+        self.last_temp = None;
         self.transition_not_working();
     }
 
@@ -181,16 +200,42 @@ impl FasController {
             self.last_attached_pkg.clear();
             self.buffer.clear();
             self.bottleneck.reset();
+            self.signals.reset();
             self.transition_not_working();
+        } else if pid.is_some() && self.pid != pid {
+            // Same game, new primary process (restart mid-session): keep
+            // the frame history, just follow the PID for load signals.
+            self.pid = pid;
         }
     }
 
+    /// The game's whole process set, so a render thread in a secondary
+    /// process shows up in the load signals.
+    pub fn set_game_pids(&mut self, pids: &[i32]) {
+        self.game_pids.clear();
+        self.game_pids.extend_from_slice(pids);
+    }
+
     pub fn set_tuning(&mut self, tuning: FasTuning) {
         self.tuning = tuning;
         self.bottleneck =
             BottleneckDetector::new(self.tuning.cv_threshold, self.tuning.debounce_frames);
     }
 
+    pub fn set_margin_override(&mut self, margin: Option<f64>) {
+        self.margin_override = margin;
+    }
+
+    /// Bottleneck class from the latest working tick, once.
+    pub fn take_bottleneck(&mut self) -> Option<BottleneckType> {
+        self.last_bottleneck.take()
+    }
+
+    /// `(temperature, limit)` of the `[thermal] fas` zones as of the latest tick.
+    pub fn last_thermal(&self) -> Option<(f32, f32)> {
+        self.last_temp.map(|t| (t, self.tuning.thermal_threshold))
+    }
+
     pub fn set_target_fps(&mut self, fps: u32) {
         self.buffer = FrameBuffer::new(TargetFps::Single(fps));
         self.bottleneck.reset();
@@ -271,11 +316,22 @@ impl FasController {
             self.transition_not_working();
         }
 
-        let temp = self.thermal.get_max_temp().unwrap_or(0.0);
+        // `fas` zones gate on the per-mode threshold; the broader `safety`
+        // selection has its own, higher limit (see `[thermal]`).
+        let readings = self.thermal.readings();
+        self.last_temp = readings.fas;
+        let temp = readings.fas.unwrap_or(0.0);
         if temp > self.tuning.thermal_threshold {
             tracing::debug!(target: "auriya::fas", "Thermal throttle: {:.1}°C (limit {:.1})", temp, self.tuning.thermal_threshold);
             return Ok(ScalingAction::Reduce);
         }
+        let safety_limit = crate::core::thermal::current_map().safety_threshold;
+        if let Some(safety) = readings.safety
+            && safety > safety_limit
+        {
+            tracing::debug!(target: "auriya::fas", "Thermal safety: {:.1}°C (limit {:.1})", safety, safety_limit);
+            return Ok(ScalingAction::Reduce);
+        }
 
         if self.state != FasState::Working {
             return Ok(ScalingAction::Maintain);
@@ -287,8 +343,9 @@ impl FasController {
 
         self.update_target_offset();
 
+        let margin = self.margin_override.unwrap_or(self.tuning.margin_fps);
         let adjusted_target_fps =
-            (f64::from(target_fps) + self.target_fps_offset - self.tuning.margin_fps).max(1.0);
+            (f64::from(target_fps) + self.target_fps_offset - margin).max(1.0);
 
         let last_frame = match self.buffer.last_frametime() {
             Some(f) => f,
@@ -302,16 +359,31 @@ impl FasController {
         // or GPU-only boosts. When dynamic_governor is disabled, skip it and
         // treat every boost as Balanced (full profile) — a coarser but
         // predictable fallback that never mis-attributes the bottleneck.
-        let bottleneck = if self.tuning.dynamic_governor_enabled {
+        let class = if self.tuning.dynamic_governor_enabled {
             let frametimes = self.buffer.recent_frametimes(60);
-            self.bottleneck.classify(&frametimes, target_fps)
+            let pids = if self.game_pids.is_empty() {
+                self.pid.into_iter().collect()
+            } else {
+                self.game_pids.clone()
+            };
+            let signals = self.signals.sample(&pids);
+            self.bottleneck
+                .classify_with_signals(&frametimes, target_fps, signals.as_ref())
         } else {
-            BottleneckType::Balanced
+            Classification {
+                kind: BottleneckType::Balanced,
+                confidence: 1.0,
+                source: ClassSource::FrametimeCv,
+            }
         };
+        let bottleneck = class.kind;
+        if self.tuning.dynamic_governor_enabled {
+            self.last_bottleneck = Some(bottleneck);
+        }
 
         tracing::debug!(
             target: "auriya::fas",
-            "fps_long={:.1} fps_short={:.1} target={} adj_target={:.2} jank={} ctl={:.0}kHz off={:.2} bneck={}",
+            "fps_long={:.1} fps_short={:.1} target={} adj_target={:.2} jank={} ctl={:.0}kHz off={:.2} bneck={} conf={:.2} via={}",
             self.buffer.current_fps_long,
             self.buffer.current_fps_short,
             target_fps,
@@ -324,6 +396,11 @@ impl FasController {
                 BottleneckType::Cpu => "CPU",
                 BottleneckType::Balanced => "BAL",
                 BottleneckType::Unknown => "?",
+            },
+            class.confidence,
+            match class.source {
+                ClassSource::Signals => "signals",
+                ClassSource::FrametimeCv => "cv",
             }
         );
 
@@ -544,6 +621,20 @@ mod tests {
                 debounce_frames: 7,
             },
             ceiling: CeilingConfig::default(),
+            thermal: ThermalConfig::default(),
+            touch_boost: TouchBoostConfig::default(),
+            ingame_idle: InGameIdleConfig::default(),
+            learning: LearningConfig::default(),
+            adaptive_refresh: AdaptiveRefreshConfig::default(),
+            launch: LaunchConfig::default(),
+            memory: MemoryConfig::default(),
+            freezer: FreezerConfig::default(),
+            io: IoConfig::default(),
+            prewarm: PrewarmConfig::default(),
+            psi: PsiConfig::default(),
+            swap: SwapConfig::default(),
+            cgroups: CgroupConfig::default(),
+            walt: WaltConfig::default(),
             modes: m,
         }
     }
@@ -17,7 +17,7 @@ pub enum ProfileMode {
     Fast,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Command {
     Help,
     Status,
@@ -46,11 +46,23 @@ pub enum Command {
         Option<String>,
         Option<Vec<u32>>,
         Option<String>,
+        Option<bool>,
+        Option<bool>,
+        Option<f64>,
+        Option<String>,
+        Option<bool>,
+        Option<f32>,
+        Option<String>,
     ),
+    GameSuggest(String),
     SetFps(u32),
     GetFps,
     GetSupportedRates,
     GetStats,
+    ThermalZones,
+    Freezer,
+    Prewarm,
+    Doctor,
 }
 
 impl FromStr for Command {
@@ -87,6 +99,10 @@ impl FromStr for Command {
             ["GET_FPS"] | ["GETFPS"] => Ok(Command::GetFps),
             ["GET_SUPPORTED_RATES"] | ["GETRATES"] => Ok(Command::GetSupportedRates),
             ["GET_STATS"] | ["GETSTATS"] => Ok(Command::GetStats),
+            ["THERMAL_ZONES"] | ["THERMALZONES"] => Ok(Command::ThermalZones),
+            ["FREEZER"] => Ok(Command::Freezer),
+            ["PREWARM"] => Ok(Command::Prewarm),
+            ["DOCTOR"] => Ok(Command::Doctor),
 
             ["INJECT", pkg] => Ok(Command::Inject(pkg.to_string())),
             ["CLEAR_INJECT"] | ["CLEARINJECT"] => Ok(Command::ClearInject),
@@ -113,6 +129,13 @@ impl FromStr for Command {
                 let mut mode = None;
                 let mut fps_array = None;
                 let mut ceiling = None;
+                let mut touch_boost = None;
+                let mut idle_detect = None;
+                let mut margin = None;
+                let mut resolution = None;
+                let mut oom_protect = None;
+                let mut uclamp_min = None;
+                let mut gpu_governor = None;
                 for arg in rest {
                     if let Some(gov) = arg.strip_prefix("gov=") {
                         governor = Some(gov.to_string());
@@ -134,6 +157,23 @@ impl FromStr for Command {
                         mode = Some(mode_val.to_string());
                     } else if let Some(ceiling_val) = arg.strip_prefix("ceiling=") {
                         ceiling = Some(ceiling_val.to_string());
+                    } else if let Some(tb_val) = arg.strip_prefix("touch_boost=") {
+                        touch_boost = tb_val.parse::<bool>().ok();
+                    } else if let Some(idle_val) = arg.strip_prefix("idle_detect=") {
+                        idle_detect = idle_val.parse::<bool>().ok();
+                    } else if let Some(margin_val) = arg.strip_prefix("margin=") {
+                        margin = margin_val.parse::<f64>().ok();
+                    } else if let Some(res_val) = arg.strip_prefix("res=") {
+                        resolution = Some(res_val.to_string());
+                    } else if let Some(oom_val) = arg.strip_prefix("oom=") {
+                        oom_protect = oom_val.parse::<bool>().ok();
+                    } else if let Some(uclamp_val) = arg.strip_prefix("uclamp_min=") {
+                        uclamp_min = uclamp_val
+                            .parse::<f32>()
+                            .ok()
+                            .filter(|v| (0.0..=100.0).contains(v));
+                    } else if let Some(gpu_gov) = arg.strip_prefix("gpu_gov=") {
+                        gpu_governor = Some(gpu_gov.to_string());
                     }
                 }
 
@@ -146,8 +186,18 @@ impl FromStr for Command {
                     mode,
                     fps_array,
                     ceiling,
+                    touch_boost,
+                    idle_detect,
+                    margin,
+                    resolution,
+                    oom_protect,
+                    uclamp_min,
+                    gpu_governor,
                 ))
             }
+            ["GAME_SUGGEST", pkg] | ["GAMESUGGEST", pkg] => {
+                Ok(Command::GameSuggest(pkg.to_string()))
+            }
 
             _ => Err("unknown command (try HELP)"),
         }
@@ -21,6 +21,11 @@ const HELP: &str = "CMDS:
         - SET_GOVERNOR <governor>
         - ADD_GAME <pkg>
         - REMOVE_GAME <pkg>
+        - GAME_SUGGEST <pkg>
+        - THERMAL_ZONES
+        - FREEZER
+        - PREWARM
+        - DOCTOR
  ";
 
 /// Handle a single IPC client connection.
@@ -52,6 +57,16 @@ pub async fn handle_client(stream: UnixStream, h: IpcHandles) -> Result<()> {
                     .map(|g| g.clone())
                     .unwrap_or_default();
                 match (st.pkg, st.pid) {
+                    (Some(p), Some(id)) if st.pids.len() > 1 => format!(
+                        "PKG={} PID={} PIDS={}\n",
+                        p,
+                        id,
+                        st.pids
+                            .iter()
+                            .map(|p| p.to_string())
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    ),
                     (Some(p), Some(id)) => format!("PKG={} PID={}\n", p, id),
                     (Some(p), None) => format!("PKG={} PID=None\n", p),
                     _ => "PKG=None PID=None\n".into(),
@@ -123,6 +138,80 @@ pub async fn handle_client(stream: UnixStream, h: IpcHandles) -> Result<()> {
                                 .map(|v| format!("{:.1}", v))
                                 .unwrap_or_else(|| "N/A".to_string()),
                         ));
+                        telemetry_lines.push_str(&format!(
+                            "TEMP_FAS={} TEMP_SKIN={}\n",
+                            thermal
+                                .fas_temp_c
+                                .map(|v| format!("{:.1}", v))
+                                .unwrap_or_else(|| "N/A".to_string()),
+                            thermal
+                                .skin_temp_c
+                                .map(|v| format!("{:.1}", v))
+                                .unwrap_or_else(|| "N/A".to_string()),
+                        ));
+                    }
+                    if let Some(ingame) = st.ingame {
+                        telemetry_lines.push_str(&format!("INGAME={}\n", ingame));
+                    }
+                    match st.oom_score_adj {
+                        Some(adj) => telemetry_lines.push_str(&format!(
+                            "OOM_PROTECT=on OOM_ADJ={} PIDS={} MEMCG={}\n",
+                            adj,
+                            st.oom_protected
+                                .iter()
+                                .map(|p| p.to_string())
+                                .collect::<Vec<_>>()
+                                .join(","),
+                            if st.memcg_protected { "on" } else { "off" },
+                        )),
+                        None if st.game_session => {
+                            telemetry_lines.push_str("OOM_PROTECT=off\n");
+                        }
+                        None => {}
+                    }
+                    if let Some(psi) = st.psi {
+                        let fmt = |l: Option<crate::core::psi::PsiLevels>| {
+                            l.map(|l| format!("{:.1}", l.some))
+                                .unwrap_or_else(|| "N/A".to_string())
+                        };
+                        telemetry_lines.push_str(&format!(
+                            "PSI_CPU={} PSI_MEM={} PSI_IO={}\n",
+                            fmt(psi.cpu),
+                            fmt(psi.memory),
+                            fmt(psi.io),
+                        ));
+                    }
+                    telemetry_lines.push_str(&format!(
+                        "{} GAME_GROUP={}\n",
+                        st.sched_groups,
+                        st.game_group.as_deref().unwrap_or("-"),
+                    ));
+                    if st.walt != Default::default() {
+                        telemetry_lines.push_str(&format!("{}\n", st.walt));
+                    }
+                    if st.freezer_active {
+                        telemetry_lines.push_str(&format!(
+                            "FROZEN={} FREEZE_FAILED={}\n",
+                            st.frozen.len(),
+                            st.freeze_failures.len(),
+                        ));
+                    }
+                    if !st.hot_threads.is_empty() || st.worker_threads > 0 {
+                        telemetry_lines.push_str(&format!(
+                            "THREADS_HOT={} THREADS_WORKER={}\n",
+                            st.hot_threads.len(),
+                            st.worker_threads,
+                        ));
+                        for t in &st.hot_threads {
+                            telemetry_lines.push_str(&format!(
+                                "THREAD tid={} pid={} comm={} role={} cpus={}\n",
+                                t.tid,
+                                t.pid,
+                                t.comm,
+                                t.role,
+                                crate::core::tweaks::cpu::mask_to_cpu_list(t.mask),
+                            ));
+                        }
                     }
                 }
 
@@ -242,6 +331,13 @@ pub async fn handle_client(stream: UnixStream, h: IpcHandles) -> Result<()> {
                         refresh_rate: None,
                         mode: Some("performance".to_string()),
                         ceiling: None,
+                        touch_boost: None,
+                        idle_detect: None,
+                        margin: None,
+                        resolution: None,
+                        oom_protect: None,
+                        uclamp_min: None,
+                        gpu_governor: None,
                     };
                     // Copy-on-write: clone the shared snapshot only when we
                     // actually mutate it (rare, IPC-driven), keeping the
@@ -318,6 +414,13 @@ pub async fn handle_client(stream: UnixStream, h: IpcHandles) -> Result<()> {
                 mode,
                 fps_array,
                 ceiling,
+                touch_boost,
+                idle_detect,
+                margin,
+                resolution,
+                oom_protect,
+                uclamp_min,
+                gpu_governor,
             )) => {
                 use crate::core::config::gamelist::GameProfileUpdate;
                 if let Ok(mut gl) = h.shared_config.write() {
@@ -329,6 +432,13 @@ pub async fn handle_client(stream: UnixStream, h: IpcHandles) -> Result<()> {
                         mode,
                         fps_array,
                         ceiling,
+                        touch_boost,
+                        idle_detect,
+                        margin,
+                        resolution,
+                        oom_protect,
+                        uclamp_min,
+                        gpu_governor,
                     };
                     let g = std::sync::Arc::make_mut(&mut gl);
                     match g.update(&pkg, upd) {
@@ -345,6 +455,41 @@ pub async fn handle_client(stream: UnixStream, h: IpcHandles) -> Result<()> {
                     "ERR lock poisoned\n".to_string()
                 }
             }
+            Ok(Command::GameSuggest(pkg)) => {
+                match h.learned.read() {
+                    Ok(store) => match store.games.get(&pkg) {
+                        Some(obs) => {
+                            let mut out = format!("PKG={} SESSIONS={}\n", pkg, obs.sessions);
+                            // Before the window closes, show what the
+                            // observations so far would suggest.
+                            let (proposal, state) = match &obs.proposal {
+                                Some(p) => (Some(p.clone()), "learned"),
+                                None => (obs.propose(0), "pending"),
+                            };
+                            out.push_str(&format!("STATE={}\n", state));
+                            if let Some(p) = proposal {
+                                out.push_str(&format!(
+                                    "TARGET_FPS={} MARGIN={} MODE={} CEILING={}\n",
+                                    p.target_fps
+                                        .iter()
+                                        .map(|f| f.to_string())
+                                        .collect::<Vec<_>>()
+                                        .join(","),
+                                    p.margin,
+                                    p.mode,
+                                    p.ceiling
+                                ));
+                                for e in &p.evidence {
+                                    out.push_str(&format!("EVIDENCE {}\n", e));
+                                }
+                            }
+                            out
+                        }
+                        None => format!("ERR GAME_SUGGEST no observations for {}\n", pkg),
+                    },
+                    Err(_) => "ERR lock poisoned\n".to_string(),
+                }
+            }
             Ok(Command::SetFps(fps)) => {
                 (h.set_fps)(fps).await;
                 format!("OK SET_FPS {}\n", fps)
@@ -368,14 +513,11 @@ pub async fn handle_client(stream: UnixStream, h: IpcHandles) -> Result<()> {
                 }
             }
             Ok(Command::GetSupportedRates) => {
-                use std::collections::BTreeSet;
-                let rates: Vec<u32> = h
+                let rates = h
                     .supported_modes
-                    .iter()
-                    .map(|m| m.fps.round() as u32)
-                    .collect::<BTreeSet<_>>()
-                    .into_iter()
-                    .collect();
+                    .read()
+                    .map(|m| crate::core::display::rates_for(&m, None))
+                    .unwrap_or_default();
 
                 match serde_json::to_string(&rates) {
                     Ok(json) => format!("{}\n", json),
@@ -417,6 +559,8 @@ pub async fn handle_client(stream: UnixStream, h: IpcHandles) -> Result<()> {
                         st.and_then(|s| s.pkg.as_deref()),
                         st.map(|s| s.profile).unwrap_or_default(),
                         st.map(|s| s.game_session).unwrap_or(false),
+                        st.and_then(|s| s.launch.as_ref()),
+                        st.and_then(|s| s.psi),
                     )
                 };
 
@@ -425,6 +569,118 @@ pub async fn handle_client(stream: UnixStream, h: IpcHandles) -> Result<()> {
                     Err(e) => format!("ERR JSON {:?}\n", e),
                 }
             }
+            Ok(Command::ThermalZones) => {
+                use crate::core::thermal;
+                let map = thermal::current_map();
+                let mut out = String::new();
+                for z in thermal::discover_zones() {
+                    let roles = map.roles_for(&z.kind);
+                    out.push_str(&format!(
+                        "ZONE={} TYPE={} TEMP={} ROLES={}\n",
+                        z.index,
+                        if z.kind.is_empty() { "?" } else { &z.kind },
+                        z.temp_c
+                            .map(|v| format!("{:.1}", v))
+                            .unwrap_or_else(|| "N/A".to_string()),
+                        if roles.is_empty() {
+                            "-".to_string()
+                        } else {
+                            roles.join(",")
+                        },
+                    ));
+                }
+                if out.is_empty() {
+                    "ERR NO_THERMAL_ZONES\n".to_string()
+                } else {
+                    out
+                }
+            }
+            Ok(Command::Freezer) => {
+                let st = h
+                    .current_state
+                    .read()
+                    .ok()
+                    .map(|g| g.clone())
+                    .unwrap_or_default();
+                let mut out = format!(
+                    "FREEZER={} BACKEND={} FROZEN={} FAILED={}\n",
+                    if st.freezer_active { "on" } else { "off" },
+                    st.freezer_backend.unwrap_or("none"),
+                    st.frozen.len(),
+                    st.freeze_failures.len(),
+                );
+                for f in &st.frozen {
+                    out.push_str(&format!("FROZEN PID={} PKG={}\n", f.pid, f.pkg));
+                }
+                for f in &st.freeze_failures {
+                    out.push_str(&format!(
+                        "FAILED PID={} PKG={} ERR={}\n",
+                        f.pid, f.pkg, f.error
+                    ));
+                }
+                out
+            }
+            Ok(Command::Prewarm) => {
+                let records = h
+                    .current_state
+                    .read()
+                    .map(|s| s.prewarm.clone())
+                    .unwrap_or_default();
+                let pct = |v: Option<f32>| {
+                    v.map(|p| format!("{:.0}%", p))
+                        .unwrap_or_else(|| "N/A".to_string())
+                };
+                let mut out = String::new();
+                for r in &records {
+                    out.push_str(&format!(
+                        "PKG={} LAUNCHES={} LAST_HIT={} AVG_HIT={} IDLE_RUNS={} ASSETS_MB={} WARMED_MB={}\n",
+                        r.pkg,
+                        r.launches,
+                        pct(r.last_hit_pct),
+                        pct(r.avg_hit_pct),
+                        r.idle_runs,
+                        r.asset_bytes >> 20,
+                        r.warmed_bytes >> 20,
+                    ));
+                }
+                if out.is_empty() {
+                    "ERR NO_PREWARM_RUNS\n".to_string()
+                } else {
+                    out
+                }
+            }
+            Ok(Command::Doctor) => {
+                use crate::core::tweaks::{ceiling::CoreLayout, energy_model};
+                let em = energy_model::table();
+                let layout = CoreLayout::detect();
+                let mut out = format!(
+                    "SOC={} EM={}\n",
+                    crate::core::tweaks::vendor::detect::detect_soc(),
+                    em.source,
+                );
+                for (name, ids) in [
+                    ("little", &layout.little_ids),
+                    ("big", &layout.big_ids),
+                    ("prime", &layout.prime_ids),
+                ] {
+                    let Some(&first) = ids.first() else {
+                        continue;
+                    };
+                    let cluster = em.clusters.iter().find(|c| c.cpus.contains(&first));
+                    out.push_str(&format!(
+                        "CLUSTER={} CPUS={} EFFICIENT_KHZ={} MAX_KHZ={}\n",
+                        name,
+                        crate::core::tweaks::cgroups::cpulist(ids),
+                        cluster
+                            .map(|c| c.efficient_khz.to_string())
+                            .unwrap_or_else(|| "N/A".to_string()),
+                        cluster
+                            .map(|c| c.max_khz.to_string())
+                            .unwrap_or_else(|| "N/A".to_string()),
+                    ));
+                }
+                out
+            }
             Err(e) => format!("ERR {}\n", e),
         };
         if !resp.is_empty() {
@@ -51,7 +51,8 @@ pub struct IpcHandles {
     /// `settings.dnd.default_enable`. Snapshotted at IPC init.
     pub dnd_default: bool,
     pub current_log_level: Arc<RwLock<LogLevelCmd>>,
-    pub supported_modes: Arc<Vec<crate::core::display::DisplayMode>>,
+    pub supported_modes: Arc<RwLock<Vec<crate::core::display::DisplayMode>>>,
+    pub learned: Arc<RwLock<crate::core::learning::LearnedStore>>,
 }
 
 pub async fn start<P: AsRef<Path>>(path: P, h: IpcHandles) -> Result<()> {
@@ -78,6 +79,7 @@ pub async fn start<P: AsRef<Path>>(path: P, h: IpcHandles) -> Result<()> {
             dnd_default: h.dnd_default,
             current_log_level: h.current_log_level.clone(),
             supported_modes: h.supported_modes.clone(),
+            learned: h.learned.clone(),
         };
         tokio::spawn(async move {
             if let Err(e) = handle_client(stream, hc).await {
@@ -0,0 +1,193 @@
+// Launch boost lifecycle for the daemon.
+//
+// A launch starts from the launch watcher (fresh top-app process), from
+// the companion focusing a whitelisted game that has no PID yet, or on
+// game entry when the game's process is only seconds old. The boost runs
+// until the game settles or `[launch] max_boost_ms` passes, whichever
+// comes first; timing carries on past the boost until the game settles.
+
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use tracing::{debug, info};
+
+use crate::core::launch::{self, Launch};
+use crate::core::prewarm::PrewarmKind;
+use crate::core::profile::ProfileMode;
+use crate::daemon::run::Daemon;
+
+impl Daemon {
+    /// Start boosting and timing a launch of `pkg`, or attach a newly
+    /// known PID to the one in flight. A PID that turns out to predate
+    /// the launch means the game was resumed, not started, and the launch
+    /// is dropped.
+    pub(crate) fn on_launch_detected(&mut self, pkg: &str, pid: Option<i32>) {
+        if !self.launch_config.enabled
+            || (self.is_in_game_session() && self.last.pkg.as_deref() == Some(pkg))
+        {
+            return;
+        }
+        match self.launch.as_mut() {
+            Some(l) if l.pkg == pkg => {
+                if let Some(pid) = pid
+                    && l.pid.is_none()
+                    && !l.set_pid(pid)
+                {
+                    self.abort_launch("resumed, not launched");
+                }
+                return;
+            }
+            Some(_) => {
+                self.abort_launch("another game launched");
+                self.memory_prep = None;
+            }
+            None => {}
+        }
+        if pid.is_some_and(|p| !launch::is_cold_start(p)) {
+            return;
+        }
+
+        info!(target: "auriya::launch", "Launch of {} detected{}", pkg, pid.map(|p| format!(" (PID {p})")).unwrap_or_default());
+        self.prepare_session_memory(pkg);
+        self.launch = Some(Launch::new(pkg, pid));
+        self.launch_io.reset();
+        self.launch_boost
+            .start(Duration::from_millis(self.launch_config.max_boost_ms));
+        if let Some(pid) = pid {
+            self.launch_boost.boost_io(&[pid]);
+        }
+        if self.prewarm_config.enabled {
+            self.prewarmer.start(
+                PrewarmKind::Launch,
+                vec![pkg.to_string()],
+                self.prewarm_config.budget_mb << 20,
+                self.prewarm_config.max_memory_psi,
+            );
+        }
+    }
+
+    /// Per-tick launch bookkeeping: extends the I/O boost to new threads,
+    /// feeds the settle check once the game session is up, and records
+    /// the launch time when it settles. A launch whose game session has
+    /// ended, or that never settles, is dropped unrecorded.
+    pub(crate) fn track_launch(&mut self) {
+        let in_session = self.is_in_game_session()
+            && self.launch.as_ref().map(|l| l.pkg.as_str()) == self.last.pkg.as_deref();
+        let Some(l) = self.launch.as_mut() else {
+            return;
+        };
+        let now = Instant::now();
+
+        if !in_session {
+            if l.entered {
+                self.abort_launch("game session ended");
+            } else if l.expired(now)
+                || l.pid
+                    .is_some_and(|p| !crate::core::dumpsys::activity::is_pid_valid(p))
+            {
+                self.abort_launch("never reached a game session");
+            }
+            return;
+        }
+
+        self.launch_boost.boost_io(&self.game_pids);
+        let (_, read_kbps) = self.launch_io.sample(&self.game_pids);
+        let frames = self.fps_meter.read().is_some();
+        match l.update(frames, read_kbps, self.ingame_config.loading_read_kbps, now) {
+            Some(took) => {
+                let pkg = l.pkg.clone();
+                let boosted = self.launch_boost.is_active();
+                info!(target: "auriya::launch", "{} launched in {} ms{}", pkg, took.as_millis(), if boosted { " (boosted)" } else { "" });
+                self.launch_log.record(&pkg, took);
+                self.launch = None;
+                self.release_launch_boost();
+            }
+            None if l.expired(now) => self.abort_launch("did not settle"),
+            None => {}
+        }
+    }
+
+    /// Drop the launch in flight without recording it.
+    pub(crate) fn abort_launch(&mut self, reason: &str) {
+        if let Some(l) = self.launch.take() {
+            debug!(target: "auriya::launch", "Dropped launch of {} ({})", l.pkg, reason);
+        }
+        self.release_launch_boost();
+    }
+
+    /// Called when the boost deadline passes; the launch itself stays
+    /// timed until it settles.
+    pub(crate) fn expire_launch_boost(&mut self) {
+        let keep_storage = self.profile_locks_storage();
+        self.launch_boost.expire(keep_storage);
+    }
+
+    fn release_launch_boost(&mut self) {
+        let keep_storage = self.profile_locks_storage();
+        self.launch_boost.release(keep_storage);
+    }
+
+    /// The applied profile locks storage frequency itself, so the boost
+    /// must not unlock it on the way out.
+    fn profile_locks_storage(&self) -> bool {
+        matches!(
+            self.last.profile_mode,
+            Some(ProfileMode::Performance | ProfileMode::Fast)
+        )
+    }
+
+    /// Prewarm the whitelisted games, most launched first, while the
+    /// device charges with the screen off and nothing is running.
+    pub(crate) fn sync_idle_prewarm(&mut self, gamelist: &crate::core::config::GameList) {
+        let cfg = &self.prewarm_config;
+        if !cfg.enabled
+            || !cfg.idle
+            || self.last.screen_awake != Some(false)
+            || self.is_in_game_session()
+            || self.launch.is_some()
+            || !self
+                .prewarmer
+                .idle_due(Duration::from_secs(cfg.idle_interval_h * 3600))
+        {
+            return;
+        }
+        let charging = crate::core::telemetry::battery::snapshot()
+            .status
+            .is_some_and(|s| s == "Charging" || s == "Full");
+        if !charging {
+            return;
+        }
+        let mut pkgs: Vec<(u32, String)> = gamelist
+            .game
+            .iter()
+            .map(|g| {
+                (
+                    self.launch_log.summary(&g.package, false).count,
+                    g.package.clone(),
+                )
+            })
+            .collect();
+        pkgs.sort_by_key(|(count, _)| std::cmp::Reverse(*count));
+        let pkgs = pkgs.into_iter().map(|(_, p)| p).collect();
+        if self.prewarmer.start(
+            PrewarmKind::Idle,
+            pkgs,
+            cfg.budget_mb << 20,
+            cfg.max_memory_psi,
+        ) {
+            self.prewarmer.mark_idle_run();
+        }
+    }
+
+    /// Arm the launch watcher whenever no game session is running.
+    pub(crate) fn sync_launch_watch(&mut self) {
+        let armed =
+            self.launch_config.enabled && !self.is_in_game_session() && !self.is_suspended();
+        if self.launch_armed.swap(armed, Ordering::Relaxed) != armed {
+            debug!(target: "auriya::launch", "Launch watcher {}", if armed { "armed" } else { "disarmed" });
+        }
+        if !self.launch_config.enabled && self.launch.is_some() {
+            self.abort_launch("launch boost disabled");
+        }
+    }
+}
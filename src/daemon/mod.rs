@@ -3,6 +3,7 @@ pub mod config;
 pub mod event;
 pub mod fas;
 pub mod ipc;
+pub mod launch;
 pub mod run;
 pub mod state;
 pub mod tick;
@@ -7,9 +7,9 @@ use crate::core::tweaks::vendor::{detect, mtk};
 use crate::daemon::event::{self, DaemonEvent, EventSender};
 use crate::daemon::state::{CurrentState, LastState};
 use anyhow::Result;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::path::PathBuf;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use tokio::{signal, time};
@@ -55,6 +55,10 @@ const COMPANION_PROC: &str = "AuriyaSysMon";
 /// Companion APK path (matches `COMPANION_APK` in service.sh).
 const COMPANION_APK: &str = "/data/adb/modules/auriya/system/etc/auriya/service.apk";
 
+/// How long boosts stay off after an I/O PSI trigger, about two in-game
+/// ticks so the loading check can take over.
+pub(crate) const PSI_IO_HOLD: Duration = Duration::from_secs(1);
+
 pub(crate) fn update_current_profile_file(mode: ProfileMode) {
     let val = match mode {
         ProfileMode::Performance => "1",
@@ -133,30 +137,121 @@ pub struct Daemon {
     /// (10 s) cadences remain fixed; only this "normal" interval is
     /// user-configurable. Reloadable.
     pub(crate) normal_interval_ms: u64,
-    pub(crate) supported_modes: Arc<Vec<crate::core::display::DisplayMode>>,
+    /// `daemon.session_grace_ms`. Reloadable.
+    pub(crate) session_grace: Duration,
+    /// When focus left the current game while its process lived on.
+    pub(crate) grace_since: Option<std::time::Instant>,
+    /// `appsSupportedModes`, re-queried on screen-on and when stale at
+    /// game entry so foldables and resolution switches are picked up.
+    pub(crate) supported_modes: Arc<RwLock<Vec<crate::core::display::DisplayMode>>>,
+    pub(crate) modes_queried_at: Option<std::time::Instant>,
     /// Currently-active refresh-rate override (Hz) we've asked the
     /// companion to apply. `None` means we have not pushed a custom rate
     /// since the daemon started or since the last release.
     pub(crate) applied_refresh_rate: Option<u32>,
+    /// User refresh-rate settings captured by the `settings put` fallback.
+    pub(crate) saved_refresh_settings: Option<crate::core::display::RefreshSettings>,
+    /// `wm size` override in effect for the current game.
+    pub(crate) applied_resolution: Option<(u32, u32)>,
+    /// `wm size` override in place before the first game resolution.
+    pub(crate) saved_resolution: Option<crate::core::display::ResolutionOverride>,
+    /// Frame-rate-driven refresh for the current game session, when
+    /// `[adaptive_refresh]` applies to it.
+    pub(crate) adaptive_refresh: Option<crate::core::adaptive_refresh::AdaptiveRefresh>,
+    pub(crate) adaptive_refresh_config: crate::core::config::settings::AdaptiveRefreshConfig,
     pub(crate) cached_whitelist: HashSet<String>,
     pub(crate) status_cache: SystemStatusCache,
     pub(crate) vendor_lock: crate::core::tweaks::vendor_lock::VendorLock,
     /// Cached FPS config string to avoid resetting FAS state every tick.
     pub(crate) last_fps_config: Option<String>,
     pub(crate) pid_tracker: Option<crate::core::pid_tracker::PidTracker>,
+    /// The tracked game's process set (`last.pid` is its primary member).
+    /// Affinity, priority and the frame probe apply to all of them.
+    pub(crate) game_pids: Vec<i32>,
+    /// Per-thread affinity for `game_pids` while a performance profile is
+    /// active.
+    pub(crate) thread_placer: crate::core::tweaks::threads::ThreadPlacer,
     pub(crate) ceiling_controller: crate::core::tweaks::ceiling::CeilingController,
     pub(crate) ceiling_config: crate::core::tweaks::ceiling::CeilingConfig,
+    /// Gamelist whose `ceiling` names were last checked against
+    /// `ceiling_config`; a reload of either checks them again.
+    pub(crate) ceiling_checked: Option<Arc<crate::core::config::GameList>>,
     pub(crate) current_ceiling: Option<crate::core::tweaks::ceiling::CeilingLevel>,
+    /// Low ceiling currently forced by `[thermal] ceiling_threshold`.
+    pub(crate) thermal_ceiling: bool,
+    /// Read by the touch watcher threads; true only while a game session
+    /// with touch boost enabled is in the foreground.
+    pub(crate) touch_armed: Arc<AtomicBool>,
+    /// Touch watcher threads with an open device; without one there is no
+    /// touch signal and in-game idle detection stays off.
+    pub(crate) touch_readers: Arc<AtomicUsize>,
+    pub(crate) touch_boost: crate::core::tweaks::touch_boost::TouchBoost,
+    pub(crate) touch_config: crate::core::config::settings::TouchBoostConfig,
+    /// Boost allowed for the current game (`touch_armed` alone only means
+    /// touches are being watched).
+    pub(crate) touch_boost_enabled: bool,
+    pub(crate) last_touch: Option<std::time::Instant>,
+    pub(crate) ingame: crate::core::ingame::InGameDetector,
+    pub(crate) ingame_io: crate::core::ingame::IoSampler,
+    pub(crate) ingame_config: crate::core::config::settings::InGameIdleConfig,
+    /// Per-game observations and learned proposals (`learned.json`).
+    pub(crate) learner: crate::core::learning::GameLearner,
+    /// Read by the launch watcher thread; true while no game session runs.
+    pub(crate) launch_armed: Arc<AtomicBool>,
+    /// The game launch being boosted and timed, if any.
+    pub(crate) launch: Option<crate::core::launch::Launch>,
+    pub(crate) launch_boost: crate::core::tweaks::launch_boost::LaunchBoost,
+    /// Storage reads of the launching game, for the settle check.
+    pub(crate) launch_io: crate::core::ingame::IoSampler,
+    pub(crate) launch_config: crate::core::config::settings::LaunchConfig,
+    /// Per-game launch times (`launch.json`).
+    pub(crate) launch_log: crate::core::launch::LaunchLog,
+    /// LMK shielding of the current game's processes.
+    pub(crate) oom_guard: crate::core::tweaks::memory::OomGuard,
+    pub(crate) memory_config: crate::core::config::settings::MemoryConfig,
+    /// Memory preparation done for the current game session, if any yet.
+    pub(crate) memory_prep: Option<crate::core::tweaks::memory::MemoryPrep>,
+    /// Background apps frozen for the current game session.
+    pub(crate) freezer: crate::core::tweaks::freezer::AppFreezer,
+    pub(crate) freezer_config: crate::core::config::settings::FreezerConfig,
+    /// Session I/O priorities, block scheduler and F2FS GC.
+    pub(crate) io_qos: crate::core::tweaks::io_qos::IoQos,
+    pub(crate) io_config: crate::core::config::settings::IoConfig,
+    /// Page-cache prewarming of game assets.
+    pub(crate) prewarmer: crate::core::prewarm::Prewarmer,
+    pub(crate) prewarm_config: crate::core::config::settings::PrewarmConfig,
+    /// uclamp/cpuset of the role groups and the game's own group.
+    pub(crate) cgroups: crate::core::tweaks::cgroups::CgroupController,
+    pub(crate) cgroup_config: crate::core::config::settings::CgroupConfig,
+    /// Qualcomm WALT migration thresholds and core_ctl.
+    pub(crate) walt: crate::core::tweaks::vendor::walt::Walt,
+    pub(crate) walt_config: crate::core::config::settings::WaltConfig,
+    /// Swappiness, page-cluster and zram0 layout.
+    pub(crate) swap: crate::core::tweaks::swap::SwapPolicy,
+    pub(crate) swap_config: crate::core::config::settings::SwapConfig,
+    pub(crate) total_ram_mb: u64,
+    /// System PSI, sampled every tick and on trigger events.
+    pub(crate) psi: crate::core::psi::PsiMonitor,
+    /// Read by the PSI watcher thread; true while a game session runs.
+    pub(crate) psi_armed: Arc<AtomicBool>,
+    /// Memory pressure crossed `[memory] memcg_escalate_psi` this session,
+    /// so the game's memcg stays protected until it ends.
+    pub(crate) memcg_escalated: bool,
+    /// Last I/O PSI trigger during the session; boosts hold off for
+    /// [`PSI_IO_HOLD`] after it.
+    pub(crate) io_stall_at: Option<std::time::Instant>,
     pub(crate) telemetry_hub: TelemetryHub,
     pub(crate) fps_meter: FpsMeter,
     pub(crate) ebpf: Option<crate::core::ebpf::EbpfFrameStream>,
-    /// PID the eBPF frame probe is currently attached to, if any. Used to
-    /// detach when leaving a game session so the worker thread goes idle.
-    pub(crate) attached_ebpf_pid: Option<i32>,
-    /// Last DnD filter pushed to the companion. Driven by the game-session
-    /// lifecycle (not profile transitions) so DnD toggles reliably on every
-    /// game enter/exit. `None` until the first sync.
-    pub(crate) last_dnd: Option<crate::core::cmd_writer::DndFilter>,
+    /// PIDs the eBPF frame probe is currently attached to. Used to narrow
+    /// down to the presenting process and to detach when leaving a game
+    /// session so the worker thread goes idle.
+    pub(crate) attached_ebpf_pids: BTreeSet<i32>,
+    /// Priority DnD is on because a game session asked for it, over the
+    /// user's own DnD being off. Driven by the game-session lifecycle (not
+    /// profile transitions) so DnD toggles reliably on every game
+    /// enter/exit.
+    pub(crate) dnd_engaged: bool,
     /// Producer side of the out-of-band event channel. Cloned to the
     /// background threads (PID tracker, companion lock watcher) so they
     /// can wake the tick loop instantly.
@@ -166,7 +261,7 @@ pub struct Daemon {
 impl Daemon {
     pub fn new(
         cfg: DaemonConfig,
-        supported_modes: Arc<Vec<crate::core::display::DisplayMode>>,
+        supported_modes: Arc<RwLock<Vec<crate::core::display::DisplayMode>>>,
         status_cache: SystemStatusCache,
         event_tx: EventSender,
     ) -> Result<Self> {
@@ -183,6 +278,7 @@ impl Daemon {
             .parse::<ProfileMode>()
             .unwrap_or(ProfileMode::Balance);
         debug!(target: "auriya::daemon", "Default mode: {:?}", default_mode);
+        let session_grace = Duration::from_millis(cfg.settings.daemon.session_grace_ms);
 
         let (fas_controller, fps_meter, ebpf) = {
             let e = match crate::core::ebpf::EbpfFrameStream::new(cfg.settings.fas.poll_interval_ms)
@@ -239,24 +335,38 @@ impl Daemon {
             .map(|g| g.package.clone())
             .collect();
 
-        let ceiling_config = {
-            let c = &cfg.settings.ceiling;
-            crate::core::tweaks::ceiling::CeilingConfig {
-                default: c
-                    .default
-                    .parse::<crate::core::tweaks::ceiling::CeilingLevel>()
-                    .unwrap_or(crate::core::tweaks::ceiling::CeilingLevel::Balance),
-                low_freq_little_khz: c.low_freq_little_khz,
-                low_freq_big_khz: c.low_freq_big_khz,
-            }
-        };
+        let ceiling_config =
+            crate::core::tweaks::ceiling::CeilingConfig::from_settings(&cfg.settings.ceiling);
 
         let core_layout = crate::core::tweaks::ceiling::CoreLayout::detect();
+        crate::core::thermal::set_map(crate::core::thermal::ThermalMap::from_config(
+            &cfg.settings.thermal,
+        ));
 
         // Idle/foreground cadence from config. Clamp to a 100 ms floor so a
         // misconfigured tiny value cannot turn the tick loop into a busy-spin.
         let normal_interval_ms = cfg.settings.daemon.check_interval_ms.max(100);
 
+        let touch_config = cfg.settings.touch_boost.clone();
+        let ingame_config = cfg.settings.ingame_idle.clone();
+        let adaptive_refresh_config = cfg.settings.adaptive_refresh.clone();
+        let learner = crate::core::learning::GameLearner::new(
+            crate::core::config::learned_path(),
+            cfg.settings.learning.clone(),
+        );
+        let launch_config = cfg.settings.launch.clone();
+        let memory_config = cfg.settings.memory.clone();
+        let freezer_config = cfg.settings.freezer.clone();
+        let io_config = cfg.settings.io.clone();
+        let prewarm_config = cfg.settings.prewarm.clone();
+        let swap_config = cfg.settings.swap.clone();
+        let cgroup_config = cfg.settings.cgroups.clone();
+        let cgroups = crate::core::tweaks::cgroups::CgroupController::new(
+            crate::core::tweaks::cgroups::cpulist(&core_layout.little_ids),
+        );
+        let walt_config = cfg.settings.walt.clone();
+        let total_ram_mb = crate::core::tweaks::memory::get_total_ram_mb().unwrap_or(4096);
+
         Ok(Self {
             cfg,
             _shared_settings: shared_settings,
@@ -270,8 +380,16 @@ impl Daemon {
             balance_governor,
             default_mode,
             normal_interval_ms,
+            session_grace,
+            grace_since: None,
             supported_modes,
+            modes_queried_at: Some(std::time::Instant::now()),
             applied_refresh_rate: None,
+            applied_resolution: None,
+            saved_resolution: None,
+            saved_refresh_settings: None,
+            adaptive_refresh: None,
+            adaptive_refresh_config,
             cached_whitelist,
             tick_count: 0,
             companion_alive: true,
@@ -280,14 +398,54 @@ impl Daemon {
             vendor_lock: crate::core::tweaks::vendor_lock::VendorLock::new(),
             last_fps_config: None,
             pid_tracker: None,
+            game_pids: Vec::new(),
+            thread_placer: crate::core::tweaks::threads::ThreadPlacer::new(),
             ceiling_controller: crate::core::tweaks::ceiling::CeilingController::new(),
             ceiling_config,
+            ceiling_checked: None,
             current_ceiling: None,
+            thermal_ceiling: false,
+            touch_config,
+            touch_boost_enabled: false,
+            last_touch: None,
+            ingame: crate::core::ingame::InGameDetector::default(),
+            ingame_io: crate::core::ingame::IoSampler::default(),
+            ingame_config,
+            learner,
+            launch_armed: Arc::new(AtomicBool::new(false)),
+            launch: None,
+            launch_boost: crate::core::tweaks::launch_boost::LaunchBoost::new(&core_layout),
+            launch_io: crate::core::ingame::IoSampler::default(),
+            launch_config,
+            launch_log: crate::core::launch::LaunchLog::new(crate::core::config::launch_path()),
+            oom_guard: crate::core::tweaks::memory::OomGuard::default(),
+            memory_config,
+            memory_prep: None,
+            freezer: crate::core::tweaks::freezer::AppFreezer::new(),
+            freezer_config,
+            io_qos: crate::core::tweaks::io_qos::IoQos::default(),
+            io_config,
+            prewarmer: crate::core::prewarm::Prewarmer::default(),
+            prewarm_config,
+            cgroups,
+            cgroup_config,
+            walt: crate::core::tweaks::vendor::walt::Walt::detect(),
+            walt_config,
+            swap: crate::core::tweaks::swap::SwapPolicy::default(),
+            swap_config,
+            total_ram_mb,
+            psi: crate::core::psi::PsiMonitor::default(),
+            psi_armed: Arc::new(AtomicBool::new(false)),
+            memcg_escalated: false,
+            io_stall_at: None,
+            touch_armed: Arc::new(AtomicBool::new(false)),
+            touch_readers: Arc::new(AtomicUsize::new(0)),
+            touch_boost: crate::core::tweaks::touch_boost::TouchBoost::new(&core_layout),
             telemetry_hub: TelemetryHub::new(&core_layout),
             fps_meter,
             ebpf,
-            attached_ebpf_pid: None,
-            last_dnd: None,
+            attached_ebpf_pids: BTreeSet::new(),
+            dnd_engaged: false,
             event_tx,
         })
     }
@@ -302,9 +460,35 @@ impl Daemon {
     }
 
     pub(crate) fn set_pid(&mut self, pid: Option<i32>) {
-        self.last.pid = pid;
-        self.pid_tracker =
-            pid.map(|p| crate::core::pid_tracker::PidTracker::spawn(p, self.event_tx.clone()));
+        match pid {
+            Some(p) => self.track_game(p, vec![p]),
+            None => {
+                self.last.pid = None;
+                self.game_pids.clear();
+                self.pid_tracker = None;
+            }
+        }
+    }
+
+    /// Track `pids` as the game's process set with `primary` as the
+    /// focused process. Respawns the tracker only when the set changed.
+    pub(crate) fn track_game(&mut self, primary: i32, mut pids: Vec<i32>) {
+        if !pids.contains(&primary) {
+            pids.push(primary);
+            pids.sort_unstable();
+        }
+        self.last.pid = Some(primary);
+        if self.pid_tracker.is_some() && self.game_pids == pids {
+            return;
+        }
+        if pids.len() > 1 {
+            debug!(target: "auriya::daemon", "Tracking game process set {:?} (primary {})", pids, primary);
+        }
+        self.pid_tracker = Some(crate::core::pid_tracker::PidTracker::spawn_set(
+            &pids,
+            self.event_tx.clone(),
+        ));
+        self.game_pids = pids;
     }
 
     #[inline]
@@ -358,6 +542,34 @@ impl Daemon {
                     self.normal_interval_ms = new_interval;
                 }
 
+                self.session_grace = Duration::from_millis(new_settings.daemon.session_grace_ms);
+                crate::core::thermal::set_map(crate::core::thermal::ThermalMap::from_config(
+                    &new_settings.thermal,
+                ));
+                self.touch_config = new_settings.touch_boost.clone();
+                self.ingame_config = new_settings.ingame_idle.clone();
+                self.learner.set_config(new_settings.learning.clone());
+                self.adaptive_refresh_config = new_settings.adaptive_refresh.clone();
+                self.launch_config = new_settings.launch.clone();
+                self.memory_config = new_settings.memory.clone();
+                self.freezer_config = new_settings.freezer.clone();
+                self.io_config = new_settings.io.clone();
+                self.prewarm_config = new_settings.prewarm.clone();
+                self.swap_config = new_settings.swap.clone();
+                self.cgroup_config = new_settings.cgroups.clone();
+                self.walt_config = new_settings.walt.clone();
+                let ceiling_config = crate::core::tweaks::ceiling::CeilingConfig::from_settings(
+                    &new_settings.ceiling,
+                );
+                if ceiling_config != self.ceiling_config {
+                    // Drop the active level so an edited definition is
+                    // re-applied on the next tick.
+                    self.ceiling_controller.restore();
+                    self.current_ceiling = None;
+                    self.ceiling_config = ceiling_config;
+                    self.ceiling_checked = None;
+                }
+
                 if let Some(ref fas_arc) = self.fas_controller {
                     let tuning = crate::daemon::fas::FasTuning::from_settings(&new_settings);
                     if let Ok(mut f) = fas_arc.try_lock() {
@@ -479,6 +691,7 @@ impl Daemon {
             dnd_default: cfg.settings.dnd.default_enable,
             current_log_level,
             supported_modes: self.supported_modes.clone(),
+            learned: self.learner.shared(),
         };
 
         tokio::spawn(async move {
@@ -543,20 +756,29 @@ impl Daemon {
     /// Apply refresh rate via the fallback path (`settings put system`)
     /// when the companion is not available. `0` restores default.
     /// Returns `true` on success.
-    pub(crate) fn apply_refresh_rate_fallback(&self, hz: u32) -> bool {
-        let ok = if !self.companion_alive {
+    ///
+    /// The fallback captures the user's own settings before its first
+    /// write and `0` puts exactly those back, whichever path is live by
+    /// then (the companion only restores what it captured itself).
+    pub(crate) fn apply_refresh_rate_fallback(&mut self, hz: u32) -> bool {
+        use crate::core::display::{RefreshSettings, force_refresh_rate};
+
+        let ok = if hz == 0
+            && let Some(saved) = self.saved_refresh_settings.take()
+        {
+            debug!(target: "auriya::companion", "RR fallback: restoring user settings {:?}", saved);
+            saved.restore()
+        } else if !self.companion_alive {
             debug!(target: "auriya::companion", "RR fallback: setting {hz}Hz");
-            let r1 = std::process::Command::new("settings")
-                .args(["put", "system", "min_refresh_rate", &hz.to_string()])
-                .status()
-                .map(|s| s.success())
-                .unwrap_or(false);
-            let r2 = std::process::Command::new("settings")
-                .args(["put", "system", "peak_refresh_rate", &hz.to_string()])
-                .status()
-                .map(|s| s.success())
-                .unwrap_or(false);
-            r1 && r2
+            if hz == 0 {
+                // Nothing captured: drop the overrides back to the system default.
+                RefreshSettings::default().restore()
+            } else {
+                if self.saved_refresh_settings.is_none() {
+                    self.saved_refresh_settings = Some(RefreshSettings::capture());
+                }
+                force_refresh_rate(hz)
+            }
         } else {
             crate::core::cmd_writer::shared()
                 .write_refresh_rate(hz)
@@ -623,19 +845,100 @@ impl Daemon {
         self.restart_companion();
     }
 
+    /// Record the touch for idle detection and start or extend the touch
+    /// boost. The watcher only sends while armed, but the flag may have
+    /// dropped since the event was queued.
+    pub(crate) fn on_touch(&mut self) {
+        if !self.touch_armed.load(std::sync::atomic::Ordering::Relaxed) {
+            return;
+        }
+        self.last_touch = Some(std::time::Instant::now());
+        if !self.touch_boost_enabled
+            || self.ingame.state() == crate::core::ingame::InGameState::Loading
+            || self.io_stalled()
+        {
+            return;
+        }
+        self.touch_boost.trigger(
+            Duration::from_millis(self.touch_config.duration_ms),
+            self.touch_config.little_freq_khz,
+            self.touch_config.big_freq_khz,
+        );
+    }
+
+    /// React to a PSI trigger without waiting for the next tick. Memory
+    /// stalls escalate the game's memcg protection and run a freeze pass
+    /// at once; I/O stalls drop any running boost and hold boosts off
+    /// briefly, since more CPU cannot help a game waiting on storage.
+    pub(crate) fn on_psi(&mut self, resource: crate::core::psi::Resource) {
+        use crate::core::psi::Resource;
+
+        if !self.psi_armed.load(std::sync::atomic::Ordering::Relaxed) {
+            return;
+        }
+        let levels = self.psi.sample().get(resource);
+        debug!(target: "auriya::psi", "{} pressure trigger fired ({:?})", resource.as_str(), levels);
+        match resource {
+            Resource::Memory => {
+                self.escalate_memcg();
+                let gamelist = match self.shared_gamelist.read() {
+                    Ok(g) => Arc::clone(&g),
+                    Err(_) => return,
+                };
+                self.sync_oom_guard(&gamelist);
+                self.sync_freezer(true);
+            }
+            Resource::Io => {
+                self.io_stall_at = Some(std::time::Instant::now());
+                self.touch_boost.release();
+            }
+            Resource::Cpu => {}
+        }
+    }
+
     /// Release every host-state override the daemon owns before exiting,
-    /// so a graceful stop (Ctrl-C or a staged module update) does not
+    /// so a graceful stop (Ctrl-C, SIGTERM or a staged module update) does not
     /// leave mount-binds or offlined cores behind. The `CeilingController`
     /// also restores on `Drop`, but doing it explicitly keeps the ordering
     /// deterministic and covers `VendorLock`, which has no `Drop`.
-    pub(crate) fn shutdown_cleanup(&mut self) {
+    pub(crate) async fn shutdown_cleanup(&mut self) {
         debug!(target: "auriya::daemon", "Releasing overrides for graceful shutdown");
         self.vendor_lock.unlock_all();
+        self.touch_boost.release();
+        self.abort_launch("daemon stopping");
+        self.oom_guard.restore();
+        self.freezer.thaw_all();
+        self.io_qos.restore();
+        self.swap.restore();
+        self.cgroups.restore();
+        self.walt.restore();
+        crate::core::tweaks::gpu::restore();
+        self.learner.end();
+        if self.applied_resolution.take().is_some() {
+            self.saved_resolution
+                .take()
+                .unwrap_or_default()
+                .restore()
+                .await;
+        }
+        if self.applied_refresh_rate.take().is_some() {
+            self.apply_refresh_rate_fallback(0);
+        }
+        self.sync_dnd(crate::core::cmd_writer::DndFilter::All);
         self.ceiling_controller.restore();
         self.ceiling_controller.online_all();
     }
 }
 
+/// Sleep until `deadline`, or forever when there is none. Lets an
+/// optional timer sit in the main `select!`.
+async fn sleep_until_opt(deadline: Option<std::time::Instant>) {
+    match deadline {
+        Some(d) => time::sleep_until(time::Instant::from_std(d)).await,
+        None => std::future::pending().await,
+    }
+}
+
 pub async fn run_with_config_and_logger(cfg: &DaemonConfig, reload: ReloadHandle) -> Result<()> {
     run_with_config(cfg, reload).await
 }
@@ -644,11 +947,11 @@ pub async fn run_with_config(cfg: &DaemonConfig, filter_handle: ReloadHandle) ->
     let supported_modes = match crate::core::display::get_app_supported_modes().await {
         Ok(modes) => {
             debug!(target: "auriya::daemon", "Cached {} supported display modes", modes.len());
-            Arc::new(modes)
+            Arc::new(RwLock::new(modes))
         }
         Err(e) => {
             error!(target: "auriya::daemon", "Failed to cache supported modes: {}", e);
-            Arc::new(Vec::new())
+            Arc::new(RwLock::new(Vec::new()))
         }
     };
 
@@ -685,6 +988,17 @@ pub async fn run_with_config(cfg: &DaemonConfig, filter_handle: ReloadHandle) ->
         debug!(target: "auriya::daemon", "MTK PPM fix applied");
     }
 
+    {
+        let swap_config = daemon.swap_config.clone();
+        let mut swap = std::mem::take(&mut daemon.swap);
+        daemon.swap = tokio::task::spawn_blocking(move || {
+            swap.configure_zram(&swap_config);
+            swap
+        })
+        .await
+        .unwrap_or_default();
+    }
+
     daemon.init_ipc(filter_handle).await;
 
     tokio::time::sleep(time::Duration::from_millis(200)).await;
@@ -696,8 +1010,28 @@ pub async fn run_with_config(cfg: &DaemonConfig, filter_handle: ReloadHandle) ->
 
     crate::daemon::companion_lock::start_companion_lock_watcher(daemon.event_tx.clone());
 
+    crate::daemon::watcher::start_launch_watcher(
+        daemon.shared_gamelist.clone(),
+        daemon.launch_armed.clone(),
+        daemon.event_tx.clone(),
+    );
+
+    crate::daemon::watcher::start_touch_watcher(
+        daemon.touch_armed.clone(),
+        daemon.touch_readers.clone(),
+        daemon.event_tx.clone(),
+    );
+
+    crate::daemon::watcher::start_psi_watcher(
+        daemon.psi_armed.clone(),
+        daemon.event_tx.clone(),
+        &daemon.cfg.settings.psi,
+    );
+
     debug!(target: "auriya::daemon", "Tick loop started (adaptive: {}ms idle, {}ms gaming)", daemon.normal_interval_ms, INGAME_INTERVAL_MS);
 
+    let mut sigterm = signal::unix::signal(signal::unix::SignalKind::terminate())?;
+
     daemon.tick().await;
 
     loop {
@@ -727,6 +1061,12 @@ pub async fn run_with_config(cfg: &DaemonConfig, filter_handle: ReloadHandle) ->
                      daemon.tick().await;
                 }
             }
+            _ = sleep_until_opt(daemon.touch_boost.deadline()) => {
+                daemon.touch_boost.expire();
+            }
+            _ = sleep_until_opt(daemon.launch_boost.deadline()) => {
+                daemon.expire_launch_boost();
+            }
             Some(ev) = event_rx.recv() => {
                 match ev {
                     DaemonEvent::PidExited(pid) => {
@@ -736,16 +1076,30 @@ pub async fn run_with_config(cfg: &DaemonConfig, filter_handle: ReloadHandle) ->
                     DaemonEvent::CompanionDied => {
                         daemon.on_companion_died();
                     }
+                    DaemonEvent::Touch => {
+                        daemon.on_touch();
+                    }
+                    DaemonEvent::Psi(resource) => {
+                        daemon.on_psi(resource);
+                    }
+                    DaemonEvent::Launch(pkg, pid) => {
+                        daemon.on_launch_detected(&pkg, Some(pid));
+                    }
                     DaemonEvent::ModuleUpdate => {
                         info!(target: "auriya::daemon", "Daemon | Module update staged, stopping gracefully");
-                        daemon.shutdown_cleanup();
+                        daemon.shutdown_cleanup().await;
                         break;
                     }
                 }
             }
             _ = signal::ctrl_c() => {
                 info!(target: "auriya::daemon", "Daemon | Received Ctrl-C, shutting down");
-                daemon.shutdown_cleanup();
+                daemon.shutdown_cleanup().await;
+                break;
+            }
+            _ = sigterm.recv() => {
+                info!(target: "auriya::daemon", "Daemon | Received SIGTERM, shutting down");
+                daemon.shutdown_cleanup().await;
                 break;
             }
         }
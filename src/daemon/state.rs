@@ -2,6 +2,8 @@
 pub struct CurrentState {
     pub pkg: Option<String>,
     pub pid: Option<i32>,
+    /// Every process in the tracked game's set (main + child processes).
+    pub pids: Vec<i32>,
     pub screen_awake: bool,
     pub battery_saver: bool,
     pub profile: crate::core::profile::ProfileMode,
@@ -21,6 +23,38 @@ pub struct CurrentState {
     /// foreground app. This is the authoritative "is a game running" signal
     /// the stats API exposes as `session.active`.
     pub game_session: bool,
+    /// Game threads pinned to prime/big by the thread placer.
+    pub hot_threads: Vec<crate::core::tweaks::threads::PlacedThread>,
+    /// Game threads kept off the prime cores.
+    pub worker_threads: usize,
+    /// Play / idle / loading for the active game session.
+    pub ingame: Option<crate::core::ingame::InGameState>,
+    /// `oom_score_adj` enforced on `oom_protected` while a game session
+    /// runs.
+    pub oom_score_adj: Option<i32>,
+    pub oom_protected: Vec<i32>,
+    /// `memory.low` raised on the game's memory cgroup.
+    pub memcg_protected: bool,
+    /// System PSI; `None` when the kernel has none.
+    pub psi: Option<crate::core::psi::PsiSnapshot>,
+    /// Page-cache prewarm history per game.
+    pub prewarm: Vec<crate::core::prewarm::PrewarmRecord>,
+    /// Freezer backend in use (`None` when the kernel has none) and
+    /// whether background apps are frozen right now.
+    pub freezer_backend: Option<&'static str>,
+    pub freezer_active: bool,
+    /// Background apps frozen for the session, and those that failed to
+    /// freeze.
+    pub frozen: Vec<crate::core::tweaks::freezer::FrozenApp>,
+    pub freeze_failures: Vec<crate::core::tweaks::freezer::FreezeFailure>,
+    /// Scheduling group mechanisms the kernel offers, and the dedicated
+    /// group the game runs in.
+    pub sched_groups: crate::core::tweaks::cgroups::SchedGroups,
+    pub game_group: Option<String>,
+    /// WALT sysctls and core_ctl clusters found.
+    pub walt: crate::core::tweaks::vendor::walt::WaltSupport,
+    /// Launch times of the launching game, or else of the session's game.
+    pub launch: Option<crate::core::launch::LaunchSummary>,
 }
 
 #[derive(Debug, Default, Clone)]
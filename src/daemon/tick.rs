@@ -1,12 +1,34 @@
 use crate::core::pid_tracker::PidTracker;
 use crate::core::profile::{self, ProfileMode};
 use crate::daemon::run::{
-    COMPANION_HEALTH_CHECK_TICKS, Daemon, bump_log, now_ms, should_log_change,
+    COMPANION_HEALTH_CHECK_TICKS, Daemon, PSI_IO_HOLD, bump_log, now_ms, should_log_change,
     update_current_profile_file,
 };
 use anyhow::Result;
+use std::collections::BTreeSet;
 use std::sync::Arc;
-use tracing::{debug, error, warn};
+use tracing::{debug, error, info, warn};
+
+/// Cooling (°C) below `[thermal] ceiling_threshold` required before the
+/// thermally forced Low ceiling is released.
+const CEILING_THERMAL_HYSTERESIS_C: f32 = 3.0;
+
+/// In-game ticks between process-set rescans (≈5 s at the 500 ms cadence).
+const GAME_PROC_RESCAN_TICKS: u64 = 10;
+
+/// Age after which cached display modes are re-queried on game entry.
+const DISPLAY_MODES_MAX_AGE: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// In-game ticks between thread placement scans (≈2 s), to catch threads
+/// the engine spawns after startup.
+const THREAD_RESCAN_TICKS: u64 = 4;
+
+/// In-game ticks between background freeze passes (≈10 s), for apps that
+/// drop to the background or get promoted mid-session.
+const FREEZER_RESCAN_TICKS: u64 = 20;
+
+/// In-game ticks between background I/O hog rescans (≈10 s).
+const IO_HOG_RESCAN_TICKS: u64 = 20;
 
 /// Local view of the relevant subset of `SystemStatus`. Defined here
 /// rather than reusing a shared struct because the tick loop only ever
@@ -38,7 +60,32 @@ impl Daemon {
             }
         };
 
-        if let Err(e) = self.process_tick_logic(&gamelist).await {
+        if !self
+            .ceiling_checked
+            .as_ref()
+            .is_some_and(|g| Arc::ptr_eq(g, &gamelist))
+        {
+            self.ceiling_config.check_gamelist(&gamelist);
+            self.ceiling_checked = Some(Arc::clone(&gamelist));
+        }
+
+        self.psi.sample();
+        let result = self.process_tick_logic(&gamelist).await;
+        self.track_launch();
+        self.sync_launch_watch();
+        self.sync_psi();
+        self.sync_oom_guard(&gamelist);
+        if !self.is_in_game_session() && self.launch.is_none() {
+            self.memory_prep = None;
+        }
+        self.sync_freezer(false);
+        self.sync_io_qos();
+        self.sync_swap();
+        self.sync_cgroups(&gamelist);
+        self.sync_walt();
+        self.sync_gpu_governor(&gamelist);
+        self.sync_idle_prewarm(&gamelist);
+        if let Err(e) = result {
             let err_msg = e.to_string();
             let now = now_ms();
 
@@ -63,7 +110,42 @@ impl Daemon {
             if let Ok(mut cur) = self.shared_current.write() {
                 cur.pkg = self.last.pkg.clone();
                 cur.pid = self.last.pid;
+                cur.pids = self.game_pids.clone();
+                cur.hot_threads = self
+                    .thread_placer
+                    .placements()
+                    .filter(|t| t.role == crate::core::tweaks::threads::ThreadRole::Hot)
+                    .cloned()
+                    .collect();
+                cur.worker_threads = self
+                    .thread_placer
+                    .placements()
+                    .filter(|t| t.role == crate::core::tweaks::threads::ThreadRole::Worker)
+                    .count();
                 cur.game_session = game_session;
+                cur.ingame = game_session.then(|| self.ingame.state());
+                cur.oom_score_adj = self.oom_guard.adj();
+                cur.oom_protected = self.oom_guard.pids();
+                cur.memcg_protected = self.oom_guard.memcg_protected();
+                let psi = self.psi.latest();
+                cur.psi = (psi != Default::default()).then_some(psi);
+                cur.freezer_backend = self.freezer.backend().map(|b| b.as_str());
+                cur.freezer_active = self.freezer.is_active();
+                cur.sched_groups = self.cgroups.groups();
+                cur.game_group = self.cgroups.game_group().map(str::to_string);
+                cur.walt = self.walt.support();
+                cur.prewarm = self.prewarmer.records();
+                cur.frozen = self.freezer.frozen();
+                cur.freeze_failures = self.freezer.failures();
+                cur.launch = match self.launch.as_ref() {
+                    Some(l) => Some(self.launch_log.summary(&l.pkg, true)),
+                    None => self
+                        .last
+                        .pkg
+                        .as_deref()
+                        .filter(|_| game_session)
+                        .map(|p| self.launch_log.summary(p, false)),
+                };
                 cur.screen_awake = self.last.screen_awake.unwrap_or(false);
                 cur.battery_saver = self.last.battery_saver.unwrap_or(false);
                 cur.profile = self.last.profile_mode.unwrap_or(self.default_mode);
@@ -84,6 +166,8 @@ impl Daemon {
                 }
             }
 
+            self.sync_touch_boost(&gamelist);
+
             if let Some(mode) = self.last.profile_mode {
                 update_current_profile_file(mode);
             }
@@ -118,6 +202,7 @@ impl Daemon {
             );
             // Screen off / battery saver: stop draining frames entirely.
             self.ebpf_detach();
+            self.thread_placer.clear();
             self.sync_dnd(crate::core::cmd_writer::DndFilter::All);
             self.last.screen_awake = Some(power.screen_awake);
             self.last.battery_saver = Some(power.battery_saver);
@@ -128,25 +213,32 @@ impl Daemon {
             debug!(target: "auriya::daemon", "Screen ON & saver OFF");
             self.last.screen_awake = Some(power.screen_awake);
             self.last.battery_saver = Some(power.battery_saver);
+            // Fold/unfold and resolution switches usually pass through a
+            // screen-off; pick up whatever the panel offers now.
+            self.refresh_display_modes().await;
         }
 
         let mut pkg_opt: Option<String> =
             self.override_foreground.read().ok().and_then(|o| o.clone());
 
         if pkg_opt.is_none() {
-            match self.status_cache.focused_package() {
-                Some(p) => pkg_opt = Some(p),
-                None => {
-                    self.handle_no_foreground().await;
-                    return Ok(());
-                }
-            }
+            pkg_opt = self.status_cache.focused_package();
+        }
+        if self.hold_game_session(pkg_opt.as_deref()) {
+            return Ok(());
         }
-        let pkg = pkg_opt.unwrap();
+        let Some(pkg) = pkg_opt else {
+            self.handle_no_foreground().await;
+            return Ok(());
+        };
 
         let pid_still_valid = self.pid_tracker.as_ref().is_some_and(PidTracker::is_alive);
 
         if self.last.pkg.as_deref() == Some(pkg.as_str()) && pid_still_valid {
+            self.refresh_game_procs(&pkg);
+            self.ebpf_sync();
+            self.place_game_threads(false);
+
             let fas_clone = self.fas_controller.clone();
             let (fas_enabled, global_dnd) = self
                 ._shared_settings
@@ -154,23 +246,42 @@ impl Daemon {
                 .map(|s| (s.fas.enabled, s.dnd.default_enable))
                 .unwrap_or((true, true));
 
+            let game_cfg = self.effective_game(gamelist, &pkg);
+            let game_cfg = game_cfg.as_deref();
+            let default_gov = self
+                .balance_governor
+                .read()
+                .ok()
+                .map(|g| g.clone())
+                .unwrap_or_else(|| "schedutil".to_string());
+            let governor = game_cfg
+                .filter(|c| !c.cpu_governor.is_empty())
+                .map(|c| c.cpu_governor.as_str())
+                .unwrap_or(&default_gov);
+            let enable_dnd = global_dnd && game_cfg.map(|c| c.enable_dnd).unwrap_or(true);
+
+            if game_cfg.is_some() {
+                self.update_ingame_state(game_cfg, governor, enable_dnd);
+                // Frame rates we hold down ourselves would only teach the
+                // learner its own limits back.
+                let capped = game_cfg.is_some_and(|c| c.target_fps.is_some())
+                    || self
+                        .current_ceiling
+                        .as_ref()
+                        .is_some_and(|l| l.is_capping());
+                if !self.boosts_suppressed()
+                    && !capped
+                    && let Some(reading) = self.fps_meter.read()
+                {
+                    self.learner.observe_fps(reading.fps);
+                }
+                self.sync_adaptive_refresh();
+            }
+
             if fas_enabled
                 && let Some(fas) = fas_clone
-                && gamelist.game.iter().any(|a| a.package == pkg)
+                && game_cfg.is_some()
             {
-                let game_cfg = gamelist.find(&pkg);
-                let default_gov = self
-                    .balance_governor
-                    .read()
-                    .ok()
-                    .map(|g| g.clone())
-                    .unwrap_or_else(|| "schedutil".to_string());
-                let governor = game_cfg
-                    .filter(|c| !c.cpu_governor.is_empty())
-                    .map(|c| c.cpu_governor.as_str())
-                    .unwrap_or(&default_gov);
-                let enable_dnd = global_dnd && game_cfg.map(|c| c.enable_dnd).unwrap_or(true);
-
                 if let Some(cfg) = game_cfg
                     && let Some(ref fps_cfg) = cfg.target_fps
                 {
@@ -188,8 +299,16 @@ impl Daemon {
                     }
                 }
 
+                // Re-evaluated every tick so the thermal ceiling can engage
+                // and release mid-session.
+                let ceiling_level = game_cfg
+                    .and_then(|c| c.ceiling.as_deref())
+                    .and_then(|s| self.ceiling_config.level(s));
+                self.apply_ceiling_for_state(ceiling_level, Some(&pkg));
+
+                let margin = game_cfg.and_then(|c| c.margin);
                 match self
-                    .run_fas_tick(&fas, &pkg, governor, self.last.pid, enable_dnd)
+                    .run_fas_tick(&fas, &pkg, governor, self.last.pid, enable_dnd, margin)
                     .await
                 {
                     Ok(_) => debug!(target: "auriya::fas", "FAS tick completed"),
@@ -220,6 +339,11 @@ impl Daemon {
         });
         match cached_pid {
             Some(pid) => {
+                let pids = crate::core::dumpsys::activity::game_processes(
+                    pkg,
+                    self.status_cache.focused_uid(),
+                    Some(pid),
+                );
                 let changed = self.last.pkg.as_deref() != Some(pkg) || self.last.pid != Some(pid);
                 if changed && should_log_change(&self.last, &self.cfg) {
                     debug!(target: "auriya::daemon", "Foreground {} PID={}", pkg, pid);
@@ -234,6 +358,9 @@ impl Daemon {
                         self.applied_refresh_rate = None;
                     }
                 }
+                if self.last.pkg.as_deref() != Some(pkg) {
+                    self.release_game_resolution().await;
+                }
 
                 let (fas_enabled, global_dnd) = self
                     ._shared_settings
@@ -241,7 +368,8 @@ impl Daemon {
                     .map(|s| (s.fas.enabled, s.dnd.default_enable))
                     .unwrap_or((true, true));
                 let _ = fas_enabled;
-                let game_cfg = gamelist.find(pkg);
+                let game_cfg = self.effective_game(gamelist, pkg);
+                let game_cfg = game_cfg.as_deref();
                 let default_gov = self
                     .balance_governor
                     .read()
@@ -253,19 +381,29 @@ impl Daemon {
                     .map(|c| c.cpu_governor.as_str())
                     .unwrap_or(&default_gov);
                 let enable_dnd = global_dnd && game_cfg.map(|c| c.enable_dnd).unwrap_or(true);
-                let target_mode = game_cfg
-                    .and_then(|c| c.mode.as_deref())
-                    .map(|m| match m.to_lowercase().as_str() {
-                        "powersave" | "3" => ProfileMode::Powersave,
-                        "balance" | "2" => ProfileMode::Balance,
-                        "fast" | "fas" | "4" => ProfileMode::Fast,
-                        _ => ProfileMode::Performance,
-                    })
-                    .unwrap_or(ProfileMode::Performance);
-
-                let entering_game = self.last.pkg.as_deref() != Some(pkg);
+                let target_mode = game_target_mode(game_cfg);
+
+                // No PID last tick means this is the session's first tick
+                // even when the package was already focused (launching).
+                let entering_game =
+                    self.last.pkg.as_deref() != Some(pkg) || self.last.pid.is_none();
                 if entering_game {
+                    self.on_launch_detected(pkg, Some(pid));
+                    self.prepare_session_memory(pkg);
                     self.vendor_lock.lock_all();
+                    self.ingame.reset();
+                    self.ingame_io.reset();
+                    self.learner.begin(pkg);
+                    if self
+                        .modes_queried_at
+                        .is_none_or(|t| t.elapsed() >= DISPLAY_MODES_MAX_AGE)
+                    {
+                        self.refresh_display_modes().await;
+                    }
+                    self.apply_game_resolution(pkg, game_cfg).await;
+                    self.adaptive_refresh = self.start_adaptive_refresh(game_cfg);
+                    // Entering counts as input: idle is measured from here.
+                    self.last_touch = Some(std::time::Instant::now());
 
                     let mode_str = match target_mode {
                         ProfileMode::Performance => "Performance",
@@ -284,10 +422,10 @@ impl Daemon {
                 if self.last.profile_mode != Some(target_mode) {
                     let res = match target_mode {
                         ProfileMode::Performance => {
-                            profile::apply_performance_with_config(governor, enable_dnd, Some(pid))
+                            profile::apply_performance_with_config(governor, enable_dnd, &pids)
                         }
                         ProfileMode::Fast => {
-                            profile::apply_performance_with_config(governor, enable_dnd, Some(pid))
+                            profile::apply_performance_with_config(governor, enable_dnd, &pids)
                         }
                         ProfileMode::Balance => profile::apply_balance(governor),
                         ProfileMode::Powersave => profile::apply_powersave(),
@@ -303,28 +441,37 @@ impl Daemon {
 
                 let ceiling_level = game_cfg
                     .and_then(|c| c.ceiling.as_deref())
-                    .and_then(|s| s.parse::<crate::core::tweaks::ceiling::CeilingLevel>().ok());
+                    .and_then(|s| self.ceiling_config.level(s));
                 self.apply_ceiling_for_state(ceiling_level, Some(pkg));
 
                 let rr = game_cfg.and_then(|c| c.refresh_rate);
                 let rr_changed = rr.is_some() && self.applied_refresh_rate != rr;
 
-                if rr_changed && self.apply_refresh_rate_fallback(rr.unwrap_or(0)) {
-                    debug!(target: "auriya::display", "Requested refresh rate {}Hz for {}", rr.unwrap_or(0), pkg);
-                    self.applied_refresh_rate = rr;
+                if let Some(requested) = rr.filter(|_| rr_changed) {
+                    let hz = self.resolve_refresh_rate(pkg, requested);
+                    if self.apply_refresh_rate_fallback(hz) {
+                        debug!(target: "auriya::display", "Requested refresh rate {}Hz for {}", hz, pkg);
+                        self.applied_refresh_rate = rr;
+                    }
                 }
 
-                self.ebpf_attach(pid);
+                self.track_game(pid, pids);
+                self.ebpf_sync();
+                self.place_game_threads(true);
                 self.sync_dnd(if enable_dnd {
                     crate::core::cmd_writer::DndFilter::Priority
                 } else {
                     crate::core::cmd_writer::DndFilter::All
                 });
                 self.last.pkg = Some(pkg.to_string());
-                self.set_pid(Some(pid));
                 Ok(())
             }
-            None => self.apply_balance_and_clear(pkg, "PID not found").await,
+            None => {
+                let res = self.apply_balance_and_clear(pkg, "PID not found").await;
+                // Focused before its process is up: a launch in progress.
+                self.on_launch_detected(pkg, None);
+                res
+            }
         }
     }
 
@@ -333,6 +480,9 @@ impl Daemon {
 
         if self.last.pkg.as_deref() != Some(pkg) {
             self.vendor_lock.unlock_all();
+            self.learner.end();
+            self.adaptive_refresh = None;
+            self.release_game_resolution().await;
 
             if self.applied_refresh_rate.is_some() {
                 debug!(target: "auriya::display", "Releasing game overrides for {} ({})", pkg, reason);
@@ -365,6 +515,7 @@ impl Daemon {
 
         self.apply_ceiling_for_state(None, None);
         self.ebpf_detach();
+        self.thread_placer.clear();
         if let Some(mut f) = self
             .fas_controller
             .as_ref()
@@ -393,39 +544,719 @@ impl Daemon {
         Ok(())
     }
 
-    /// Attach the eBPF frame probe to a (validated) game PID. Only called
-    /// for whitelisted games — the worker stays idle for every other app.
-    /// No-op when already tracking this PID.
-    fn ebpf_attach(&mut self, pid: i32) {
+    /// Point the frame probe at the game's process set. Until one member
+    /// is seen presenting, the probe is attached to all of them; after
+    /// that it narrows to the presenter so FAS and the FPS meter only see
+    /// a single producer's frame deltas. If the presenter leaves the set
+    /// (restart, child process exit) the probe widens again.
+    fn ebpf_sync(&mut self) {
         let Some(listener) = self.ebpf.as_ref() else {
             return;
         };
-        if self.attached_ebpf_pid == Some(pid) {
+        let wanted: BTreeSet<i32> =
+            match listener.presenter().filter(|p| self.game_pids.contains(p)) {
+                Some(p) => BTreeSet::from([p]),
+                None => self.game_pids.iter().copied().collect(),
+            };
+        if wanted == self.attached_ebpf_pids {
+            return;
+        }
+
+        for pid in self
+            .attached_ebpf_pids
+            .difference(&wanted)
+            .copied()
+            .collect::<Vec<_>>()
+        {
+            if let Err(e) = listener.detach(pid) {
+                warn!(target: "auriya::ebpf", "detach({pid}): {e}");
+            }
+            self.attached_ebpf_pids.remove(&pid);
+        }
+        for pid in wanted.difference(&self.attached_ebpf_pids.clone()) {
+            match listener.attach(*pid) {
+                Ok(_) => {
+                    self.attached_ebpf_pids.insert(*pid);
+                    debug!(target: "auriya::ebpf", "Attached frame probe to {pid}");
+                }
+                Err(e) => warn!(target: "auriya::ebpf", "attach({pid}): {e}"),
+            }
+        }
+    }
+
+    /// Re-scan the game's process set: picks up child processes spawned
+    /// after entry and a main process that restarted mid-session. Runs
+    /// every [`GAME_PROC_RESCAN_TICKS`] or immediately once the primary
+    /// PID is gone. New members get the same affinity/priority as the
+    /// originals while a performance profile is active.
+    fn refresh_game_procs(&mut self, pkg: &str) {
+        use crate::core::dumpsys::activity;
+
+        let primary_alive = self.last.pid.is_some_and(activity::is_pid_valid);
+        if primary_alive && !self.tick_count.is_multiple_of(GAME_PROC_RESCAN_TICKS) {
+            return;
+        }
+        let focused = self
+            .status_cache
+            .focused_pid()
+            .filter(|&p| activity::is_pid_valid(p));
+        let mut pids = activity::game_processes(pkg, self.status_cache.focused_uid(), focused);
+        // Keep members we already track even if this scan missed them
+        // (cmdline rewritten, transient /proc read failure).
+        if let Some(tracker) = self.pid_tracker.as_ref() {
+            for pid in tracker.alive_pids() {
+                if !pids.contains(&pid) {
+                    pids.push(pid);
+                }
+            }
+            pids.sort_unstable();
+        }
+        let Some(primary) = focused
+            .filter(|p| pids.contains(p))
+            .or(self.last.pid.filter(|p| pids.contains(p)))
+            .or(pids.first().copied())
+        else {
+            return;
+        };
+        if !primary_alive {
+            debug!(target: "auriya::daemon", "Primary PID {:?} for {} gone; continuing with {}", self.last.pid, pkg, primary);
+        }
+
+        let joined = pids.iter().any(|p| !self.game_pids.contains(p));
+        if matches!(
+            self.last.profile_mode,
+            Some(ProfileMode::Performance | ProfileMode::Fast)
+        ) {
+            for &pid in pids.iter().filter(|p| !self.game_pids.contains(p)) {
+                profile::warn_on_err(
+                    crate::core::tweaks::cpu::set_process_priority(pid),
+                    "set priority for new game process",
+                );
+            }
+        }
+        self.track_game(primary, pids);
+        // A new process's threads get placed now, not at the next rescan.
+        if joined {
+            self.place_game_threads(true);
+        }
+    }
+
+    /// Place the game's threads (hot → prime/big, workers off prime) while
+    /// a performance profile is active; undo the placement otherwise.
+    /// Rescans every [`THREAD_RESCAN_TICKS`] unless `force`d.
+    fn place_game_threads(&mut self, force: bool) {
+        if !matches!(
+            self.last.profile_mode,
+            Some(ProfileMode::Performance | ProfileMode::Fast)
+        ) {
+            self.thread_placer.clear();
+            return;
+        }
+        if !force && !self.tick_count.is_multiple_of(THREAD_RESCAN_TICKS) {
+            return;
+        }
+        let n = self.thread_placer.scan(&self.game_pids);
+        if n > 0 {
+            debug!(target: "auriya::daemon", "Placed {} game thread(s)", n);
+        }
+    }
+
+    /// Re-read `appsSupportedModes`, keeping the old list if dumpsys fails.
+    async fn refresh_display_modes(&mut self) {
+        self.modes_queried_at = Some(std::time::Instant::now());
+        let modes = match crate::core::display::get_app_supported_modes().await {
+            Ok(m) => m,
+            Err(e) => {
+                debug!(target: "auriya::display", "Display mode query failed: {e:#}");
+                return;
+            }
+        };
+        if let Ok(mut cached) = self.supported_modes.write()
+            && *cached != modes
+        {
+            debug!(target: "auriya::display", "Display modes changed ({} → {})", cached.len(), modes.len());
+            *cached = modes;
+        }
+    }
+
+    /// Snap `hz` to the nearest rate the panel offers at the current
+    /// resolution. Passed through unchanged when no modes are known.
+    fn resolve_refresh_rate(&self, pkg: &str, hz: u32) -> u32 {
+        let rates = self
+            .supported_modes
+            .read()
+            .map(|m| crate::core::display::rates_for(&m, self.applied_resolution))
+            .unwrap_or_default();
+        match crate::core::display::resolve_rate(&rates, hz) {
+            Some(r) if r != hz => {
+                warn!(target: "auriya::display", "{} asks for {}Hz, which the display does not offer {:?}; using {}Hz", pkg, hz, rates, r);
+                r
+            }
+            Some(r) => r,
+            None => hz,
+        }
+    }
+
+    /// Apply the game's `resolution`, snapped to a supported mode, or drop
+    /// a previous game's override when it has none.
+    async fn apply_game_resolution(
+        &mut self,
+        pkg: &str,
+        game_cfg: Option<&crate::core::config::gamelist::GameProfile>,
+    ) {
+        let Some(raw) = game_cfg.and_then(|c| c.resolution.as_deref()) else {
+            self.release_game_resolution().await;
+            return;
+        };
+        let Some(want) = crate::core::display::parse_resolution(raw) else {
+            warn!(target: "auriya::display", "{}: ignoring resolution {:?} (expected WIDTHxHEIGHT)", pkg, raw);
+            self.release_game_resolution().await;
+            return;
+        };
+        let size = self
+            .supported_modes
+            .read()
+            .ok()
+            .and_then(|m| crate::core::display::resolve_resolution(&m, want))
+            .unwrap_or(want);
+        if size != want && size != (want.1, want.0) {
+            warn!(target: "auriya::display", "{} asks for {}x{}, which the display does not offer; using {}x{}", pkg, want.0, want.1, size.0, size.1);
+        }
+        if self.applied_resolution == Some(size) {
+            return;
+        }
+        if self.saved_resolution.is_none() {
+            self.saved_resolution = Some(crate::core::display::ResolutionOverride::capture().await);
+        }
+        if crate::core::display::set_resolution(Some(size)).await {
+            debug!(target: "auriya::display", "Resolution {}x{} for {}", size.0, size.1, pkg);
+            self.applied_resolution = Some(size);
+        } else {
+            warn!(target: "auriya::display", "Failed to set resolution {}x{} for {}", size.0, size.1, pkg);
+        }
+    }
+
+    async fn release_game_resolution(&mut self) {
+        if self.applied_resolution.take().is_some() {
+            let saved = self.saved_resolution.take().unwrap_or_default();
+            debug!(target: "auriya::display", "Restoring display resolution {:?}", saved.0);
+            if !saved.restore().await {
+                warn!(target: "auriya::display", "Failed to restore display resolution");
+            }
+        }
+    }
+
+    /// Arm the PSI watcher for the game session and follow memory pressure
+    /// through it; everything pressure-driven resets once it ends.
+    fn sync_psi(&mut self) {
+        use std::sync::atomic::Ordering;
+
+        let in_session = self.is_in_game_session();
+        if self.psi_armed.swap(in_session, Ordering::Relaxed) != in_session {
+            debug!(target: "auriya::psi", "PSI watcher {}", if in_session { "armed" } else { "disarmed" });
+        }
+        if in_session {
+            self.escalate_memcg();
+        } else {
+            self.memcg_escalated = false;
+            self.io_stall_at = None;
+        }
+    }
+
+    /// Turn on memcg protection for the rest of the session once memory
+    /// pressure reaches `[memory] memcg_escalate_psi`.
+    pub(crate) fn escalate_memcg(&mut self) {
+        if self.memcg_escalated || self.memory_config.memcg_protect {
+            return;
+        }
+        let Some(some) = self.psi.latest().memory.map(|l| l.some) else {
+            return;
+        };
+        if some >= self.memory_config.memcg_escalate_psi {
+            info!(target: "auriya::psi", "Memory pressure {:.1}%, protecting the game's memcg for the session", some);
+            self.memcg_escalated = true;
+        }
+    }
+
+    /// Shield the game's processes from the low-memory killer for the
+    /// whole session, grace window included, and hand their original
+    /// `oom_score_adj` back once it ends. `oom_protect` on the game's
+    /// entry overrides `[memory] oom_protect`.
+    pub(crate) fn sync_oom_guard(&mut self, gamelist: &crate::core::config::GameList) {
+        let enabled = self
+            .last
+            .pkg
+            .as_deref()
+            .and_then(|p| gamelist.find(p))
+            .and_then(|g| g.oom_protect)
+            .unwrap_or(self.memory_config.oom_protect);
+        if enabled && self.is_in_game_session() {
+            self.oom_guard.protect(
+                &self.game_pids,
+                self.memory_config.oom_score_adj,
+                self.memory_config.memcg_protect || self.memcg_escalated,
+            );
+        } else {
+            self.oom_guard.restore();
+        }
+    }
+
+    /// Memory preparation, once per game session: at launch detection
+    /// before the launch boost starts, or on entry to a game that was
+    /// already running. Never while a boost is active, and never again
+    /// mid-session, so the game's own cached assets stay put.
+    pub(crate) fn prepare_session_memory(&mut self, pkg: &str) {
+        if self.memory_prep.is_some() {
+            return;
+        }
+        let prep = if self.launch_boost.is_active() {
+            crate::core::tweaks::memory::MemoryPrep::skipped("launch boost active")
+        } else {
+            crate::core::tweaks::memory::prepare_session(
+                self.memory_config.prep_psi_threshold,
+                self.memory_config.prep_min_available_mb,
+            )
+        };
+        info!(target: "auriya::session", "Memory prep for {}: {}", pkg, prep);
+        self.memory_prep = Some(prep);
+    }
+
+    /// Freeze background apps while the game has focus and the screen is
+    /// on; thaw them the moment either stops, including the grace window
+    /// so whatever the user switched to is not left frozen. `force` runs a
+    /// freeze pass now instead of waiting for the rescan interval.
+    pub(crate) fn sync_freezer(&mut self, force: bool) {
+        let active = self.freezer_config.enabled
+            && self.is_in_game_session()
+            && self.grace_since.is_none()
+            && self.last.screen_awake.unwrap_or(true);
+        if !active {
+            self.freezer.thaw_all();
+            return;
+        }
+        if !force
+            && self.freezer.is_active()
+            && !self.tick_count.is_multiple_of(FREEZER_RESCAN_TICKS)
+        {
+            return;
+        }
+        self.freezer.freeze_background(
+            &self.game_pids,
+            &self.freezer_config.allowlist,
+            self.freezer_config.min_adj,
+        );
+    }
+
+    /// Hold the session I/O policy while a game session runs, grace window
+    /// included, and put everything back once it ends. Game threads are
+    /// re-prioritised with the thread rescans, hogs and F2FS GC with the
+    /// slower process rescans.
+    fn sync_io_qos(&mut self) {
+        if !self.io_config.enabled || !self.is_in_game_session() {
+            self.io_qos.restore();
+            return;
+        }
+        let first = !self.io_qos.is_active();
+        if first || self.tick_count.is_multiple_of(THREAD_RESCAN_TICKS) {
+            self.io_qos.prioritize_game(&self.game_pids);
+        }
+        if first || self.tick_count.is_multiple_of(IO_HOG_RESCAN_TICKS) {
+            self.io_qos.idle_hogs(&self.io_config.background_hogs);
+            if self.io_config.pause_f2fs_gc {
+                self.io_qos.pause_f2fs_gc();
+            } else {
+                self.io_qos.restore_f2fs_gc();
+            }
+        }
+        let scheduler = self
+            .last
+            .profile_mode
+            .and_then(|m| self.io_config.scheduler_for(m))
+            .map(str::to_string);
+        self.io_qos.set_scheduler(scheduler.as_deref());
+    }
+
+    /// Role-group clamps and cpusets for the applied profile, and the
+    /// game's own boosted group for the length of the session when
+    /// `[cgroups] game_group` names one. Without a group, the game's
+    /// `uclamp_min` goes on top-app.
+    fn sync_cgroups(&mut self, gamelist: &crate::core::config::GameList) {
+        if !self.cgroup_config.enabled {
+            self.cgroups.restore();
+            return;
+        }
+        let in_session = self.is_in_game_session();
+        let game_min = self
+            .last
+            .pkg
+            .as_deref()
+            .filter(|_| in_session)
+            .and_then(|p| gamelist.find(p))
+            .and_then(|g| g.uclamp_min);
+        let group = self.cgroup_config.game_group.trim().to_string();
+        let mode = self.last.profile_mode.unwrap_or(self.default_mode);
+        let policy = self
+            .cgroup_config
+            .policy_for(mode)
+            .cloned()
+            .unwrap_or_default();
+        self.cgroups
+            .apply(&policy, if group.is_empty() { game_min } else { None });
+
+        if !in_session || group.is_empty() {
+            self.cgroups.release_game();
+            return;
+        }
+        if self.cgroups.game_group() != Some(group.as_str())
+            || self.tick_count.is_multiple_of(THREAD_RESCAN_TICKS)
+        {
+            self.cgroups.place_game(
+                &self.game_pids,
+                &group,
+                Some(game_min.unwrap_or(self.cgroup_config.game_uclamp_min)),
+            );
+        }
+    }
+
+    /// The game's `gpu_governor` for the length of its session; the
+    /// original governor otherwise.
+    fn sync_gpu_governor(&mut self, gamelist: &crate::core::config::GameList) {
+        let governor = self
+            .last
+            .pkg
+            .as_deref()
+            .filter(|_| self.is_in_game_session())
+            .and_then(|p| gamelist.find(p))
+            .and_then(|g| g.gpu_governor.as_deref());
+        crate::core::tweaks::gpu::set_governor(governor);
+    }
+
+    /// WALT thresholds and core_ctl limits for the applied profile.
+    fn sync_walt(&mut self) {
+        if !self.walt_config.enabled || !self.walt.is_available() {
+            self.walt.restore();
+            return;
+        }
+        let mode = self.last.profile_mode.unwrap_or(self.default_mode);
+        self.walt.apply(self.walt_config.policy_for(mode));
+    }
+
+    /// Swappiness and page-cluster for the applied profile, and the
+    /// once-per-session zram pass after any launch boost has finished.
+    fn sync_swap(&mut self) {
+        let mode = self.last.profile_mode.unwrap_or(self.default_mode);
+        let swappiness = self
+            .swap_config
+            .swappiness_for(mode)
+            .or_else(|| crate::core::tweaks::swap::default_swappiness(mode, self.total_ram_mb));
+        self.swap
+            .set(swappiness, self.swap_config.page_cluster_for(mode));
+        if !self.is_in_game_session() {
+            self.swap.end_session();
+        } else if !self.launch_boost.is_active() {
+            self.swap.maintain(
+                self.swap_config.recompress_on_entry,
+                self.swap_config.writeback_on_entry,
+            );
+        }
+    }
+
+    /// Keep the current game session through a brief trip elsewhere (shade,
+    /// overlay, a quick reply) while its process lives. Returns `true`
+    /// while the grace window holds, in which case the tick leaves every
+    /// game override, FAS and the probe as they are. Another whitelisted
+    /// game, the process dying or the window running out commits the exit.
+    fn hold_game_session(&mut self, focused: Option<&str>) -> bool {
+        let game = match self.last.pkg.as_deref() {
+            Some(g) if self.is_in_game_session() => g,
+            _ => {
+                self.grace_since = None;
+                return false;
+            }
+        };
+        if focused == Some(game) {
+            if let Some(since) = self.grace_since.take() {
+                debug!(target: "auriya::daemon", "Back in {} after {:?}; session kept", game, since.elapsed());
+                // Touches elsewhere weren't watched; don't call it idle.
+                self.last_touch = Some(std::time::Instant::now());
+            }
+            return false;
+        }
+        let switching_game = focused.is_some_and(|p| self.cached_whitelist.contains(p));
+        let alive = self.pid_tracker.as_ref().is_some_and(PidTracker::is_alive);
+        if switching_game || !alive || self.session_grace.is_zero() {
+            self.grace_since = None;
+            return false;
+        }
+        let since = match self.grace_since {
+            Some(t) => t,
+            None => {
+                debug!(target: "auriya::daemon", "Left {} for {}; holding session for {:?}", game, focused.unwrap_or("nothing"), self.session_grace);
+                *self.grace_since.insert(std::time::Instant::now())
+            }
+        };
+        if since.elapsed() < self.session_grace {
+            return true;
+        }
+        debug!(target: "auriya::daemon", "Grace window for {} expired", game);
+        self.grace_since = None;
+        false
+    }
+
+    /// Adaptive refresh for a game session, unless `[adaptive_refresh]` is
+    /// off, the game pins its own `refresh_rate`, or the panel has nothing
+    /// lower to offer.
+    fn start_adaptive_refresh(
+        &self,
+        game_cfg: Option<&crate::core::config::gamelist::GameProfile>,
+    ) -> Option<crate::core::adaptive_refresh::AdaptiveRefresh> {
+        let cfg = &self.adaptive_refresh_config;
+        if !cfg.enabled || game_cfg.is_some_and(|c| c.refresh_rate.is_some()) {
+            return None;
+        }
+        let rates = self
+            .supported_modes
+            .read()
+            .map(|m| crate::core::display::rates_for(&m, self.applied_resolution))
+            .unwrap_or_default();
+        crate::core::adaptive_refresh::AdaptiveRefresh::new(
+            &rates,
+            cfg.min_hz,
+            std::time::Duration::from_secs(cfg.hold_s),
+        )
+    }
+
+    /// Feed the measured frame rate to adaptive refresh and push any rate
+    /// change. Held while a level loads, since those frame rates say
+    /// nothing about the game. Returning to the top rate hands the panel
+    /// back to the user's own setting rather than pinning it.
+    fn sync_adaptive_refresh(&mut self) {
+        if self.ingame.state() == crate::core::ingame::InGameState::Loading {
             return;
         }
-        if let Some(prev) = self.attached_ebpf_pid {
-            let _ = listener.detach(prev);
+        let Some(fps) = self.fps_meter.read().map(|r| r.fps) else {
+            return;
+        };
+        let Some(ar) = self.adaptive_refresh.as_mut() else {
+            return;
+        };
+        let Some(hz) = ar.update(fps, std::time::Instant::now()) else {
+            return;
+        };
+        let request = if hz == ar.top() { 0 } else { hz };
+        if self.apply_refresh_rate_fallback(request) {
+            debug!(target: "auriya::display", "Adaptive refresh → {}Hz (fps {:.1})", hz, fps);
+            self.applied_refresh_rate = (request != 0).then_some(hz);
         }
-        match listener.attach(pid) {
-            Ok(_) => {
-                self.attached_ebpf_pid = Some(pid);
-                debug!(target: "auriya::ebpf", "Attached frame probe to {pid}");
+    }
+
+    /// The game's gamelist entry, with learned values filling whatever it
+    /// leaves unset when `[learning] auto_apply` is on.
+    fn effective_game<'g>(
+        &self,
+        gamelist: &'g crate::core::config::GameList,
+        pkg: &str,
+    ) -> Option<std::borrow::Cow<'g, crate::core::config::gamelist::GameProfile>> {
+        let cfg = gamelist.find(pkg)?;
+        Some(match self.learner.apply(cfg) {
+            Some(learned) => std::borrow::Cow::Owned(learned),
+            None => std::borrow::Cow::Borrowed(cfg),
+        })
+    }
+
+    /// Arm the touch watcher while a game session is in the foreground
+    /// (idle detection needs touches even with the boost off), and allow
+    /// the boost itself only when `[touch_boost]` or the game's own
+    /// `touch_boost` says so. Any running boost is dropped as soon as
+    /// that stops being true.
+    fn sync_touch_boost(&mut self, gamelist: &crate::core::config::GameList) {
+        use std::sync::atomic::Ordering;
+
+        let enabled = self
+            .last
+            .pkg
+            .as_deref()
+            .and_then(|p| gamelist.find(p))
+            .and_then(|g| g.touch_boost)
+            .unwrap_or(self.touch_config.enabled);
+        let armed = self.is_in_game_session() && self.grace_since.is_none() && !self.is_suspended();
+        if self.touch_armed.swap(armed, Ordering::Relaxed) != armed {
+            debug!(target: "auriya::input", "Touch watcher {}", if armed { "armed" } else { "disarmed" });
+        }
+        // The launch boost owns the frequency floors while it runs.
+        self.touch_boost_enabled = armed && enabled && !self.launch_boost.is_active();
+        if !self.touch_boost_enabled {
+            self.touch_boost.release();
+        }
+    }
+
+    /// Track lobby/AFK and loading screens for the focused game. Idle
+    /// drops to `[ingame_idle] idle_mode`; returning to play re-applies
+    /// the game's own profile. Loading only gates boosts (see
+    /// [`Self::boosts_suppressed`]).
+    fn update_ingame_state(
+        &mut self,
+        game_cfg: Option<&crate::core::config::gamelist::GameProfile>,
+        governor: &str,
+        enable_dnd: bool,
+    ) {
+        use crate::core::ingame::{InGameState, Sample, Thresholds};
+
+        let enabled = game_cfg
+            .and_then(|c| c.idle_detect)
+            .unwrap_or(self.ingame_config.enabled);
+        if !enabled {
+            if self.ingame.state() != InGameState::Active {
+                self.ingame.reset();
+                self.apply_game_mode(game_cfg, governor, enable_dnd);
+            }
+            return;
+        }
+
+        let (iowait_pct, read_kbps) = self.ingame_io.sample(&self.game_pids);
+        let fps = self.fps_meter.read().map(|r| r.fps);
+        let target_fps = self
+            .fas_controller
+            .as_ref()
+            .and_then(|f| f.try_lock().ok())
+            .map(|f| f.get_target_fps())
+            .unwrap_or(60);
+        let sample = Sample {
+            fps,
+            slow: fps.is_none_or(|f| f < f64::from(target_fps) * 0.9),
+            since_touch: (self
+                .touch_readers
+                .load(std::sync::atomic::Ordering::Relaxed)
+                > 0)
+            .then(|| self.last_touch.map(|t| t.elapsed()).unwrap_or_default()),
+            iowait_pct,
+            read_kbps,
+            io_pressure_pct: self.psi.latest().io.map(|l| f64::from(l.some)),
+        };
+        let cfg = &self.ingame_config;
+        let thresholds = Thresholds {
+            idle_after: std::time::Duration::from_secs(cfg.idle_after_s),
+            idle_max_fps: cfg.idle_max_fps,
+            loading_read_kbps: cfg.loading_read_kbps,
+            loading_iowait_pct: cfg.loading_iowait_pct,
+            loading_io_psi_pct: cfg.loading_io_psi_pct,
+        };
+        let Some(state) = self.ingame.update(&sample, &thresholds) else {
+            return;
+        };
+        debug!(target: "auriya::daemon", "In-game state → {} (fps={:?} iowait={:?} read={:?}KiB/s)", state, fps, iowait_pct, read_kbps);
+
+        match state {
+            InGameState::Idle => {
+                let mode = self
+                    .ingame_config
+                    .idle_mode
+                    .parse::<ProfileMode>()
+                    .unwrap_or(ProfileMode::Balance);
+                let res = match mode {
+                    ProfileMode::Powersave => profile::apply_powersave_with_dnd(enable_dnd),
+                    _ => {
+                        let gov = self
+                            .balance_governor
+                            .read()
+                            .map(|g| g.clone())
+                            .unwrap_or_else(|_| "schedutil".to_string());
+                        profile::apply_balance_with_dnd(&gov, enable_dnd)
+                    }
+                };
+                match res {
+                    Ok(()) => {
+                        self.last.profile_mode = Some(match mode {
+                            ProfileMode::Powersave => ProfileMode::Powersave,
+                            _ => ProfileMode::Balance,
+                        })
+                    }
+                    Err(e) => {
+                        error!(target: "auriya::profile", ?e, "Failed to apply in-game idle profile")
+                    }
+                }
             }
-            Err(e) => warn!(target: "auriya::ebpf", "attach({pid}): {e}"),
+            InGameState::Active => self.apply_game_mode(game_cfg, governor, enable_dnd),
+            InGameState::Loading => {}
         }
     }
 
+    /// Re-apply the game's configured profile if something (idle policy,
+    /// FAS reduce) left a different one in place.
+    fn apply_game_mode(
+        &mut self,
+        game_cfg: Option<&crate::core::config::gamelist::GameProfile>,
+        governor: &str,
+        enable_dnd: bool,
+    ) {
+        let target = game_target_mode(game_cfg);
+        if self.last.profile_mode == Some(target) {
+            return;
+        }
+        let res = match target {
+            ProfileMode::Performance | ProfileMode::Fast => {
+                profile::apply_performance_with_config(governor, enable_dnd, &self.game_pids)
+            }
+            ProfileMode::Balance => profile::apply_balance_with_dnd(governor, enable_dnd),
+            ProfileMode::Powersave => profile::apply_powersave_with_dnd(enable_dnd),
+        };
+        match res {
+            Ok(()) => self.last.profile_mode = Some(target),
+            Err(e) => error!(target: "auriya::profile", ?e, "Failed to re-apply {:?}", target),
+        }
+    }
+
+    /// FAS and touch boosts are pointless while the game idles or loads,
+    /// or right after an I/O stall.
+    pub(crate) fn boosts_suppressed(&self) -> bool {
+        self.ingame.state() != crate::core::ingame::InGameState::Active || self.io_stalled()
+    }
+
+    /// An I/O PSI trigger fired within [`PSI_IO_HOLD`].
+    pub(crate) fn io_stalled(&self) -> bool {
+        self.io_stall_at.is_some_and(|t| t.elapsed() < PSI_IO_HOLD)
+    }
+
     /// Push the desired DnD filter to the companion, but only when it
     /// changed. Driven by the game-session lifecycle so DnD turns on/off on
     /// every game enter/exit regardless of whether the CPU/GPU profile
     /// itself transitioned (the FAS path can leave `profile_mode` at Balance
     /// mid-game, which used to swallow the toggle).
-    fn sync_dnd(&mut self, desired: crate::core::cmd_writer::DndFilter) {
-        if self.last_dnd == Some(desired) {
-            return;
+    ///
+    /// Only DnD we turned on is ever turned off. If the user already had
+    /// DnD on when the session began it is left alone, and if they moved
+    /// to a stricter mode mid-session that is kept on exit.
+    pub(crate) fn sync_dnd(&mut self, desired: crate::core::cmd_writer::DndFilter) {
+        use crate::core::cmd_writer::DndFilter;
+        use crate::core::system_status::{ZEN_MODE_IMPORTANT_INTERRUPTIONS, ZEN_MODE_OFF};
+
+        match desired {
+            DndFilter::Priority => {
+                if self.dnd_engaged {
+                    return;
+                }
+                let zen = self.status_cache.zen_mode().unwrap_or(ZEN_MODE_OFF);
+                if zen != ZEN_MODE_OFF {
+                    debug!(target: "auriya::daemon", "DnD already on (zen_mode={}); leaving it to the user", zen);
+                    return;
+                }
+                crate::core::profile::request_dnd(desired);
+                self.dnd_engaged = true;
+            }
+            DndFilter::All => {
+                if !std::mem::take(&mut self.dnd_engaged) {
+                    return;
+                }
+                if let Some(zen) = self.status_cache.zen_mode()
+                    && zen > ZEN_MODE_IMPORTANT_INTERRUPTIONS
+                {
+                    debug!(target: "auriya::daemon", "DnD tightened to zen_mode={} during the session; keeping it", zen);
+                    return;
+                }
+                // Engaging required DnD to be off, so off is what goes back.
+                crate::core::profile::request_dnd(desired);
+            }
         }
-        crate::core::profile::request_dnd(desired);
-        self.last_dnd = Some(desired);
         debug!(target: "auriya::daemon", "DnD → {:?}", desired);
     }
 
@@ -435,7 +1266,7 @@ impl Daemon {
         let Some(listener) = self.ebpf.as_ref() else {
             return;
         };
-        if let Some(pid) = self.attached_ebpf_pid.take() {
+        for pid in std::mem::take(&mut self.attached_ebpf_pids) {
             match listener.detach(pid) {
                 Ok(_) => debug!(target: "auriya::ebpf", "Detached frame probe from {pid}"),
                 Err(e) => warn!(target: "auriya::ebpf", "detach({pid}): {e}"),
@@ -469,6 +1300,7 @@ impl Daemon {
 
         self.apply_ceiling_for_state(None, None);
         self.ebpf_detach();
+        self.thread_placer.clear();
         if let Some(mut f) = self
             .fas_controller
             .as_ref()
@@ -491,6 +1323,9 @@ impl Daemon {
             }
 
             self.vendor_lock.unlock_all();
+            self.learner.end();
+            self.adaptive_refresh = None;
+            self.release_game_resolution().await;
 
             if should_log_change(&self.last, &self.cfg) {
                 debug!(target: "auriya::daemon", "No foreground app detected");
@@ -515,21 +1350,46 @@ impl Daemon {
         game_governor: &str,
         pid: Option<i32>,
         enable_dnd: bool,
+        margin: Option<f64>,
     ) -> Result<bool> {
         use crate::core::{profile, scaling::ScalingAction};
 
         // The thermal ceiling now lives in the FasController's tuning
         // (resolved from settings.fas.thermal_threshold / the active
         // [modes.*] entry at construction), so the tick takes no argument.
-        let action = {
+        let (action, bottleneck, thermal) = {
             let mut fas_guard = fas.lock().await;
             fas_guard.set_package(pkg.to_string(), pid);
-            fas_guard.tick().await?
+            fas_guard.set_game_pids(&self.game_pids);
+            fas_guard.set_margin_override(margin);
+            let action = fas_guard.tick().await?;
+            (
+                action,
+                fas_guard.take_bottleneck(),
+                fas_guard.last_thermal(),
+            )
+        };
+        if !self.boosts_suppressed() {
+            if let Some(b) = bottleneck {
+                self.learner.observe_bottleneck(b);
+            }
+            if let Some((temp, limit)) = thermal {
+                self.learner.observe_temp(temp, limit);
+            }
+        }
+        let action = match action {
+            ScalingAction::BoostGpu | ScalingAction::BoostCpu | ScalingAction::BoostBalanced
+                if self.boosts_suppressed() =>
+            {
+                debug!(target: "auriya::fas", "FAS boost suppressed (in-game {})", self.ingame.state());
+                ScalingAction::Maintain
+            }
+            a => a,
         };
 
         match action {
             ScalingAction::BoostGpu => {
-                debug!(target: "auriya::fas", "FAS decision: BOOST_GPU → GPU-only boost");
+                debug!(target: "auriya::fas", "FAS decision: BOOST_GPU → GPU floor up one step");
                 if let Err(e) = profile::apply_gpu_boost() {
                     error!(target: "auriya::fas", ?e, "Failed to apply GPU boost");
                 }
@@ -538,7 +1398,7 @@ impl Daemon {
             }
             ScalingAction::BoostCpu => {
                 debug!(target: "auriya::fas", "FAS decision: BOOST_CPU → CPU-only boost");
-                if let Err(e) = profile::apply_cpu_boost(game_governor, self.last.pid) {
+                if let Err(e) = profile::apply_cpu_boost(game_governor, &self.game_pids) {
                     error!(target: "auriya::fas", ?e, "Failed to apply CPU boost");
                 }
                 self.last.profile_mode = Some(ProfileMode::Performance);
@@ -547,7 +1407,7 @@ impl Daemon {
             ScalingAction::BoostBalanced => {
                 if self.last.profile_mode != Some(ProfileMode::Performance) {
                     debug!(target: "auriya::fas", "FAS decision: BOOST_BAL → full PERFORMANCE");
-                    profile::apply_performance_with_config(game_governor, enable_dnd, None)?;
+                    profile::apply_performance_with_config(game_governor, enable_dnd, &[])?;
                     self.last.profile_mode = Some(ProfileMode::Performance);
                 } else {
                     debug!(target: "auriya::fas", "FAS decision: BOOST_BAL → already PERFORMANCE, skip");
@@ -558,11 +1418,22 @@ impl Daemon {
                 debug!(target: "auriya::fas", "FAS decision: MAINTAIN → no change");
                 Ok(true)
             }
+            ScalingAction::Reduce
+                if self.ingame.state() == crate::core::ingame::InGameState::Idle =>
+            {
+                debug!(target: "auriya::fas", "FAS decision: REDUCE → in-game idle, keeping idle_mode");
+                Ok(true)
+            }
             ScalingAction::Reduce => {
+                let level = profile::reduce_gpu_boost();
+                if level > 0 {
+                    debug!(target: "auriya::fas", "FAS decision: REDUCE → GPU floor down to {}", level);
+                    return Ok(true);
+                }
                 if self.last.profile_mode != Some(self.default_mode) {
                     let res = match self.default_mode {
                         ProfileMode::Performance | ProfileMode::Fast => {
-                            profile::apply_performance_with_config(game_governor, enable_dnd, None)
+                            profile::apply_performance_with_config(game_governor, enable_dnd, &[])
                         }
                         ProfileMode::Balance => {
                             profile::apply_balance_with_dnd(game_governor, enable_dnd)
@@ -589,12 +1460,17 @@ impl Daemon {
         game_override: Option<crate::core::tweaks::ceiling::CeilingLevel>,
         _pkg: Option<&str>,
     ) {
-        let level = match game_override {
+        let requested = match game_override {
             Some(l) => l,
-            None => self.ceiling_config.default,
+            None => self.ceiling_config.default.clone(),
+        };
+        let level = if self.ceiling_thermal_hot() {
+            crate::core::tweaks::ceiling::CeilingLevel::Low
+        } else {
+            requested
         };
 
-        if self.current_ceiling == Some(level) {
+        if self.current_ceiling.as_ref() == Some(&level) {
             return;
         }
 
@@ -603,9 +1479,52 @@ impl Daemon {
             "Applying ceiling level: {} (was {:?})",
             level, self.current_ceiling
         );
-        profile::apply_ceiling(&mut self.ceiling_controller, level, &self.ceiling_config);
+        profile::apply_ceiling(&mut self.ceiling_controller, &level, &self.ceiling_config);
         self.current_ceiling = Some(level);
     }
+
+    /// True while the `[thermal] ceiling` zones sit above
+    /// `ceiling_threshold`. Releases only after cooling by
+    /// [`CEILING_THERMAL_HYSTERESIS_C`] so the mount-bind freeze does not
+    /// flap at the boundary.
+    fn ceiling_thermal_hot(&mut self) -> bool {
+        let map = crate::core::thermal::current_map();
+        let Some(limit) = map.ceiling_threshold else {
+            self.thermal_ceiling = false;
+            return false;
+        };
+        let temp = map
+            .resolve(&crate::core::thermal::sweep(), &map.ceiling)
+            .unwrap_or(0.0);
+        let hot = if self.thermal_ceiling {
+            temp > limit - CEILING_THERMAL_HYSTERESIS_C
+        } else {
+            temp > limit
+        };
+        if hot != self.thermal_ceiling {
+            debug!(
+                target: "auriya::ceiling",
+                "Thermal ceiling {}: {:.1}°C (limit {:.1})",
+                if hot { "engaged" } else { "released" },
+                temp, limit
+            );
+            self.thermal_ceiling = hot;
+        }
+        hot
+    }
+}
+
+/// The profile a game asks for via its `mode`; Performance when unset.
+fn game_target_mode(game_cfg: Option<&crate::core::config::gamelist::GameProfile>) -> ProfileMode {
+    game_cfg
+        .and_then(|c| c.mode.as_deref())
+        .map(|m| match m.to_lowercase().as_str() {
+            "powersave" | "3" => ProfileMode::Powersave,
+            "balance" | "2" => ProfileMode::Balance,
+            "fast" | "fas" | "4" => ProfileMode::Fast,
+            _ => ProfileMode::Performance,
+        })
+        .unwrap_or(ProfileMode::Performance)
 }
 
 fn broadcast_intent(action: &str, extras: &[(&str, &str)]) {
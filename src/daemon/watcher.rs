@@ -2,6 +2,7 @@ use crate::core::config::{GameList, gamelist_path};
 use crate::daemon::event::{DaemonEvent, EventSender};
 use notify::{EventKind, RecursiveMode, Watcher};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use tokio::sync::mpsc;
 use tracing::{debug, error, warn};
@@ -157,3 +158,195 @@ pub fn start_module_update_watcher(event_tx: EventSender) {
         }
     });
 }
+
+/// Minimum spacing between `Touch` events sent for a moving finger. A
+/// swipe produces hundreds of evdev packets per second; the boost only
+/// needs to be kept alive, not re-triggered on each one.
+const TOUCH_MOVE_THROTTLE: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Read every touchscreen's evdev node and send [`DaemonEvent::Touch`] on
+/// touch-down and (throttled) finger movement. Events are dropped while
+/// `armed` is false, so outside a game session the threads just sit in
+/// `read` and nothing reaches the tick loop. `readers` counts the threads
+/// currently reading a device; idle detection relies on it being non-zero.
+pub fn start_touch_watcher(
+    armed: Arc<AtomicBool>,
+    readers: Arc<AtomicUsize>,
+    event_tx: EventSender,
+) {
+    use crate::core::input::{self, INPUT_EVENT_SIZE, TouchDecoder, TouchKind};
+    use std::io::Read;
+
+    let devices = input::touchscreen_devices();
+    if devices.is_empty() {
+        debug!(target: "auriya::input", "No touchscreen input device found, touch boost unavailable");
+        return;
+    }
+
+    for dev in devices {
+        let armed = armed.clone();
+        let readers = readers.clone();
+        let tx = event_tx.clone();
+        std::thread::spawn(move || {
+            let mut file = match std::fs::File::open(&dev) {
+                Ok(f) => f,
+                Err(e) => {
+                    warn!(target: "auriya::input", "Cannot open {}: {e}", dev.display());
+                    return;
+                }
+            };
+            readers.fetch_add(1, Ordering::Relaxed);
+            debug!(target: "auriya::input", "Touch watcher started on {}", dev.display());
+            let mut decoder = TouchDecoder::default();
+            let mut last_move = std::time::Instant::now();
+            let mut buf = [0u8; INPUT_EVENT_SIZE * 64];
+            loop {
+                let n = match file.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => n,
+                    Err(e) => {
+                        warn!(target: "auriya::input", "Touch watcher on {} stopped: {e}", dev.display());
+                        break;
+                    }
+                };
+                let mut send = false;
+                for raw in buf[..n].chunks_exact(INPUT_EVENT_SIZE) {
+                    let Some((ev_type, code, value)) = input::parse_event(raw) else {
+                        continue;
+                    };
+                    match decoder.feed(ev_type, code, value) {
+                        Some(TouchKind::Down) => send = true,
+                        Some(TouchKind::Move) if last_move.elapsed() >= TOUCH_MOVE_THROTTLE => {
+                            last_move = std::time::Instant::now();
+                            send = true;
+                        }
+                        _ => {}
+                    }
+                }
+                if send && armed.load(Ordering::Relaxed) {
+                    // Never block the reader; a full channel already has a
+                    // wake-up queued.
+                    let _ = tx.try_send(DaemonEvent::Touch);
+                }
+            }
+            readers.fetch_sub(1, Ordering::Relaxed);
+        });
+    }
+}
+
+/// Poll interval for the top-app cpuset while the launch watcher is armed.
+/// A single small cgroup file, so this stays cheap.
+const LAUNCH_POLL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Watch the top-app cpuset for a fresh process of a whitelisted package
+/// and send [`DaemonEvent::Launch`] for it. That usually lands well before
+/// the companion reports the game as focused with a PID. Only polls while
+/// `armed` (no game session running); without a top-app cpuset the thread
+/// exits and launches are caught from the focus change alone.
+pub fn start_launch_watcher(
+    shared_gamelist: Arc<RwLock<Arc<GameList>>>,
+    armed: Arc<AtomicBool>,
+    event_tx: EventSender,
+) {
+    use crate::core::launch;
+    use std::collections::HashSet;
+
+    if launch::top_app_pids().is_none() {
+        debug!(target: "auriya::launch", "No top-app cpuset, launch watcher unavailable");
+        return;
+    }
+
+    std::thread::spawn(move || {
+        debug!(target: "auriya::launch", "Launch watcher started");
+        // Baseline taken on arming: processes already in top-app then are
+        // not launches.
+        let mut seen: Option<HashSet<i32>> = None;
+        loop {
+            std::thread::sleep(LAUNCH_POLL);
+            if !armed.load(Ordering::Relaxed) {
+                seen = None;
+                continue;
+            }
+            let Some(now) = launch::top_app_pids() else {
+                continue;
+            };
+            if let Some(prev) = seen.as_ref()
+                && now.iter().any(|p| !prev.contains(p))
+            {
+                let packages: HashSet<String> = match shared_gamelist.read() {
+                    Ok(g) => g.game.iter().map(|g| g.package.clone()).collect(),
+                    Err(_) => HashSet::new(),
+                };
+                for &pid in now.difference(prev) {
+                    if let Some(pkg) = launch::cold_start_package(pid, &packages) {
+                        debug!(target: "auriya::launch", "{} (PID {}) joined top-app", pkg, pid);
+                        let _ = event_tx.try_send(DaemonEvent::Launch(pkg, pid));
+                    }
+                }
+            }
+            seen = Some(now);
+        }
+    });
+}
+
+/// Register kernel PSI triggers for memory and I/O stalls and send
+/// [`DaemonEvent::Psi`] when one fires while `armed` (game session
+/// running). The thread sleeps in `poll()` between firings; the kernel
+/// raises each trigger at most once per window. Without PSI (or without
+/// trigger support) nothing is registered and the tick-path samples are
+/// all there is.
+pub fn start_psi_watcher(
+    armed: Arc<AtomicBool>,
+    event_tx: EventSender,
+    config: &crate::core::config::settings::PsiConfig,
+) {
+    use crate::core::psi::{Resource, Trigger};
+
+    if !config.triggers {
+        return;
+    }
+    let window_us = config.window_ms * 1000;
+    let triggers: Vec<Trigger> = [
+        (Resource::Memory, config.memory_stall_ms),
+        (Resource::Io, config.io_stall_ms),
+    ]
+    .into_iter()
+    .filter_map(|(resource, stall_ms)| {
+        Trigger::register(resource, stall_ms * 1000, window_us)
+            .inspect_err(|e| {
+                debug!(target: "auriya::psi", "No {} PSI trigger: {e}", resource.as_str());
+            })
+            .ok()
+    })
+    .collect();
+    if triggers.is_empty() {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        debug!(target: "auriya::psi", "PSI watcher started ({} triggers)", triggers.len());
+        let mut fds: Vec<libc::pollfd> = triggers.iter().map(Trigger::pollfd).collect();
+        loop {
+            // SAFETY: `fds` holds valid pollfds for files `triggers` keeps
+            // open for the life of this thread.
+            let n = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, -1) };
+            if n < 0 {
+                if std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted {
+                    continue;
+                }
+                warn!(target: "auriya::psi", "PSI watcher stopped: {}", std::io::Error::last_os_error());
+                return;
+            }
+            for (fd, trigger) in fds.iter_mut().zip(&triggers) {
+                let revents = std::mem::take(&mut fd.revents);
+                if revents & libc::POLLERR != 0 {
+                    warn!(target: "auriya::psi", "{} PSI trigger lost, watcher stopped", trigger.resource.as_str());
+                    return;
+                }
+                if revents & libc::POLLPRI != 0 && armed.load(Ordering::Relaxed) {
+                    let _ = event_tx.try_send(DaemonEvent::Psi(trigger.resource));
+                }
+            }
+        }
+    });
+}